@@ -1,10 +1,16 @@
 use criterion::criterion_group;
 use criterion::criterion_main;
+use criterion::BenchmarkId;
 use criterion::Criterion;
 
 use fluent_langneg::convert_vec_str_to_langids_lossy;
 use fluent_langneg::negotiate_languages;
+use fluent_langneg::parse_accepted_languages;
+use fluent_langneg::testing::corpus::{
+    available_locales, requested_locales, RequestKind, ACCEPT_LANGUAGE_SAMPLES,
+};
 use fluent_langneg::LanguageIdentifier;
+use fluent_langneg::NegotiationStrategy;
 
 #[no_mangle]
 #[inline(never)]
@@ -35,5 +41,53 @@ fn negotiate_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, negotiate_bench);
+fn corpus_bench(c: &mut Criterion) {
+    for kind in [RequestKind::Hit, RequestKind::Miss, RequestKind::Maximize] {
+        let mut group = c.benchmark_group(format!("corpus/{kind:?}").to_lowercase());
+        for available_count in [10, 100, 1000] {
+            let available = available_locales(available_count);
+            for requested_count in [1, 5, 20] {
+                let requested = requested_locales(&available, requested_count, kind);
+                group.bench_with_input(
+                    BenchmarkId::from_parameter(format!("{available_count}x{requested_count}")),
+                    &requested,
+                    |b, requested| b.iter(|| do_negotiate(requested, &available)),
+                );
+            }
+        }
+        group.finish();
+    }
+}
+
+fn accept_language_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("accept_language");
+    for available_count in [10, 100, 1000] {
+        let available = available_locales(available_count);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(available_count),
+            &available,
+            |b, available| {
+                b.iter(|| {
+                    for header in ACCEPT_LANGUAGE_SAMPLES {
+                        let requested = parse_accepted_languages(header);
+                        negotiate_languages(
+                            &requested,
+                            available,
+                            available.first(),
+                            NegotiationStrategy::Filtering,
+                        );
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    negotiate_bench,
+    corpus_bench,
+    accept_language_bench
+);
 criterion_main!(benches);
@@ -0,0 +1,160 @@
+//! Generates the tables of likely subtags used without the `cldr` feature.
+//!
+//! Run with `cargo run --example likely_subtags --features cldr` and paste
+//! the printed version and each of the printed tables into
+//! `src/negotiate/likely_subtags.rs`.
+//!
+//! The tables are generated from the data of the `cldr` feature, see
+//! [`fluent_langneg::data_version`] to generate them from another
+//! CLDR release.
+
+use fluent_langneg::LocaleExpander;
+use icu_locid::subtags::{Language, Region, Script};
+use icu_locid::LanguageIdentifier;
+
+/// The most used languages, which the table covers.
+const LANGUAGES: &[&str] = &[
+    "af", "ak", "am", "ar", "as", "az", "be", "bg", "bn", "bo", "bs", "ca", "ceb", "ckb", "cs",
+    "cy", "da", "de", "dv", "el", "en", "es", "et", "eu", "fa", "ff", "fi", "fil", "fr", "ga",
+    "gl", "gu", "ha", "he", "hi", "hr", "ht", "hu", "hy", "id", "ig", "is", "it", "ja", "jv", "ka",
+    "kk", "km", "kn", "ko", "ku", "ky", "lb", "lo", "ln", "lt", "lv", "mg", "mk", "ml", "mn", "mr",
+    "ms", "mt", "my", "nb", "ne", "nl", "nn", "no", "ny", "om", "or", "pa", "pl", "ps", "pt", "qu",
+    "ro", "ru", "rw", "sa", "sd", "si", "sk", "sl", "sn", "so", "sq", "sr", "st", "su", "sv", "sw",
+    "ta", "te", "tg", "th", "ti", "tk", "tr", "tt", "ug", "uk", "ur", "uz", "vi", "wo", "xh", "yo",
+    "yue", "zh", "zu",
+];
+
+fn maximize(lc: &LocaleExpander, input: LanguageIdentifier) -> LanguageIdentifier {
+    let mut langid = input.clone();
+    lc.maximize(&mut langid);
+    assert!(
+        !langid.language.is_empty() && langid.script.is_some() && langid.region.is_some(),
+        "Missing likely subtags for \"{}\"",
+        input
+    );
+    langid
+}
+
+fn main() {
+    let lc = LocaleExpander::new_extended();
+    let mut entries: Vec<LanguageIdentifier> = LANGUAGES
+        .iter()
+        .map(|lang| maximize(&lc, lang.parse().expect("Invalid language")))
+        .collect();
+    entries.sort_by_key(|langid| langid.language);
+
+    println!(
+        "pub(crate) const CLDR_VERSION: &str = \"{}\";",
+        fluent_langneg::data_version()
+    );
+
+    println!("// LIKELY_SUBTAGS");
+    for langid in &entries {
+        println!(
+            "    (language!(\"{}\"), script!(\"{}\"), region!(\"{}\")),",
+            langid.language,
+            langid.script.unwrap(),
+            langid.region.unwrap()
+        );
+    }
+
+    // The scripts and regions of the most used languages.
+    let mut scripts: Vec<Script> = entries.iter().filter_map(|l| l.script).collect();
+    scripts.sort();
+    scripts.dedup();
+    let mut regions: Vec<Region> = entries.iter().filter_map(|l| l.region).collect();
+    regions.sort();
+    regions.dedup();
+
+    // Combinations of the most used languages with another script or region
+    // than their likely ones, for which CLDR has a likely region or script
+    // other than the ones of the language.
+    let mut all_scripts: Vec<Script> = scripts.clone();
+    let mut all_regions: Vec<Region> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| [a, b]))
+        .filter_map(|code| Region::try_from_bytes(&code).ok())
+        .collect();
+    all_regions.extend(
+        ["419", "001", "150"]
+            .iter()
+            .filter_map(|r| r.parse::<Region>().ok()),
+    );
+
+    println!("// LANGUAGE_REGION_SCRIPTS");
+    for likely in &entries {
+        for region in &all_regions {
+            let mut langid: LanguageIdentifier = (likely.language, None, Some(*region)).into();
+            lc.maximize(&mut langid);
+            match langid.script {
+                Some(script) if Some(script) != likely.script => {
+                    all_scripts.push(script);
+                    println!(
+                        "    (language!(\"{}\"), region!(\"{}\"), script!(\"{}\")),",
+                        likely.language, region, script
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    all_scripts.sort();
+    all_scripts.dedup();
+
+    println!("// LANGUAGE_SCRIPT_REGIONS");
+    for likely in &entries {
+        for script in &all_scripts {
+            if Some(*script) == likely.script {
+                continue;
+            }
+            let mut langid: LanguageIdentifier = (likely.language, Some(*script), None).into();
+            lc.maximize(&mut langid);
+            match langid.region {
+                Some(region) if Some(region) != likely.region => println!(
+                    "    (language!(\"{}\"), script!(\"{}\"), region!(\"{}\")),",
+                    likely.language, script, region
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    println!("// REGION_SCRIPT_LANGUAGES");
+    for region in &regions {
+        let by_region = maximize(&lc, (Language::UND, None, Some(*region)).into());
+        for script in &scripts {
+            if Some(*script) == by_region.script {
+                continue;
+            }
+            let by_script = maximize(&lc, (Language::UND, Some(*script), None).into());
+            let langid = maximize(&lc, (Language::UND, Some(*script), Some(*region)).into());
+            if langid.language != by_script.language {
+                println!(
+                    "    (region!(\"{}\"), script!(\"{}\"), language!(\"{}\")),",
+                    region, script, langid.language
+                );
+            }
+        }
+    }
+
+    println!("// SCRIPT_SUBTAGS");
+    for script in scripts {
+        let langid = maximize(&lc, (Language::UND, Some(script), None).into());
+        println!(
+            "    (script!(\"{}\"), language!(\"{}\"), region!(\"{}\")),",
+            script,
+            langid.language,
+            langid.region.unwrap()
+        );
+    }
+
+    println!("// REGION_SUBTAGS");
+    for region in regions {
+        let langid = maximize(&lc, (Language::UND, None, Some(region)).into());
+        println!(
+            "    (region!(\"{}\"), language!(\"{}\"), script!(\"{}\")),",
+            region,
+            langid.language,
+            langid.script.unwrap()
+        );
+    }
+}
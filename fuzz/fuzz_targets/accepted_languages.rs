@@ -0,0 +1,10 @@
+#![no_main]
+
+use fluent_langneg::parse_accepted_languages;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for langid in parse_accepted_languages(input) {
+        assert_eq!(langid, langid.to_string().parse().unwrap());
+    }
+});
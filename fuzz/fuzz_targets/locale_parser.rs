@@ -0,0 +1,14 @@
+#![no_main]
+
+use fluent_langneg::canonicalize;
+use icu_locid::Locale;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(locale) = Locale::try_from_bytes(input) {
+        assert_eq!(locale, locale.to_string().parse().unwrap());
+    }
+    if let Ok(langid) = canonicalize::parse(input) {
+        assert_eq!(langid, langid.to_string().parse().unwrap());
+    }
+});
@@ -0,0 +1,39 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use fluent_langneg::arbitrary::{ArbitraryLangId, ArbitraryLocale};
+use fluent_langneg::negotiate::negotiate_languages_with;
+use fluent_langneg::{NegotiationOptions, NegotiationStrategy};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    requested: Vec<ArbitraryLocale>,
+    available: Vec<ArbitraryLangId>,
+    default: Option<usize>,
+    options: NegotiationOptions,
+}
+
+fuzz_target!(|input: Input| {
+    let Input {
+        requested,
+        available,
+        default,
+        options,
+    } = input;
+    let default = default.and_then(|idx| available.get(idx));
+
+    let supported = negotiate_languages_with(&requested, &available, default, &options);
+    for locale in &supported {
+        assert!(available.iter().any(|a| std::ptr::eq(a, *locale)));
+    }
+    if options.strategy == NegotiationStrategy::Lookup && !options.always_add_default {
+        assert!(supported.len() <= 1);
+    }
+    if let Some(default) = default {
+        let always = options.strategy != NegotiationStrategy::Lookup || options.always_add_default;
+        if always && !options.skip_covered_default && !options.dedup {
+            assert!(supported.contains(&default));
+        }
+    }
+});
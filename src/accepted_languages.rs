@@ -30,12 +30,171 @@
 //! language negotiation only uses the order of locales, not the weights.
 //!
 
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::string_slice,
+    clippy::panic
+)]
+
+use std::fmt::Write;
+
 use icu_locid::LanguageIdentifier;
 
+use crate::Error;
+
 pub fn parse(s: &str) -> Vec<LanguageIdentifier> {
+    tags(s).filter_map(|t| t.parse().ok()).collect()
+}
+
+/// Same as [`parse`], but fails on the first language tag which doesn't
+/// parse, instead of skipping it.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::accepted_languages::try_parse;
+/// use fluent_langneg::Error;
+///
+/// assert_eq!(try_parse("de-AT;q=0.9, en").unwrap().len(), 2);
+///
+/// let err = try_parse("de-AT;q=0.9, en--US").unwrap_err();
+/// assert!(matches!(err, Error::Header { offset: 16, .. }));
+/// ```
+pub fn try_parse(s: &str) -> Result<Vec<LanguageIdentifier>, Error> {
+    tags(s)
+        .map(|t| {
+            t.parse().map_err(|source| {
+                // The tag is a subslice of the header.
+                let start = t.as_ptr() as usize - s.as_ptr() as usize;
+                match Error::parse(t.as_bytes(), source) {
+                    Error::Parse { offset, .. } => Error::Header {
+                        header: s.to_string(),
+                        offset: start + offset,
+                        source,
+                    },
+                    err => err,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Same as [`parse`], keeping the quality of each language tag.
+///
+/// Tags without a valid `q` parameter have a quality of `1.0`, and the order
+/// of the header is kept, whatever the qualities.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::accepted_languages::parse_weighted;
+/// use icu_locid::langid;
+///
+/// assert_eq!(
+///     parse_weighted("de-AT, de;q=0.8, en;q=0.5"),
+///     &[(langid!("de-AT"), 1.0), (langid!("de"), 0.8), (langid!("en"), 0.5)]
+/// );
+/// ```
+pub fn parse_weighted(s: &str) -> Vec<(LanguageIdentifier, f32)> {
     s.split(',')
-        .map(|t| t.trim().split(';').next().unwrap())
-        .filter(|t| !t.is_empty())
-        .filter_map(|t| t.parse().ok())
+        .filter_map(|entry| {
+            let mut params = entry.split(';');
+            let langid = params.next()?.trim().parse().ok()?;
+            let quality = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .filter(|q| (0.0..=1.0).contains(q))
+                .unwrap_or(1.0);
+            Some((langid, quality))
+        })
         .collect()
 }
+
+/// Sorts weighted language tags by decreasing quality, keeping the order of
+/// the tags with equal qualities.
+///
+/// `-0.0` is equal to `0.0`, and a `NaN` quality is sorted last. Since
+/// [`parse_weighted`] keeps the order of the header, the tags of equal
+/// quality stay in the order of the header, so identical headers always
+/// produce identical results.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::accepted_languages::{parse_weighted, stable_sort_by_quality};
+/// use icu_locid::langid;
+///
+/// let mut requested = parse_weighted("fr;q=0.5, de, en;q=0.5, it");
+/// stable_sort_by_quality(&mut requested);
+/// assert_eq!(
+///     requested,
+///     &[
+///         (langid!("de"), 1.0),
+///         (langid!("it"), 1.0),
+///         (langid!("fr"), 0.5),
+///         (langid!("en"), 0.5),
+///     ]
+/// );
+/// ```
+pub fn stable_sort_by_quality<T>(entries: &mut [(T, f32)]) {
+    entries.sort_by(|(_, q1), (_, q2)| sort_key(*q2).total_cmp(&sort_key(*q1)));
+}
+
+fn sort_key(quality: f32) -> f32 {
+    if quality.is_nan() {
+        f32::NEG_INFINITY
+    } else {
+        // Adding zero turns `-0.0` into `0.0`, which `total_cmp` would
+        // otherwise order apart.
+        quality + 0.0
+    }
+}
+
+/// Serializes weighted language tags into an `Accept-Language` value, in
+/// their order.
+///
+/// The qualities are rounded to three decimals, as allowed by RFC 9110, and
+/// are left out when they are `1`, so that the value of a header parsed with
+/// [`parse_weighted`] round-trips if it is in this form. Qualities outside
+/// of `0` to `1` are clamped to that range, and `NaN` is written as `0`.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::accepted_languages::{parse_weighted, serialize_weighted};
+///
+/// let header = "de-AT,de;q=0.8,en;q=0.5";
+/// assert_eq!(serialize_weighted(&parse_weighted(header)), header);
+/// assert_eq!(serialize_weighted(&parse_weighted("de-at ; q=0.80,  en;q=1")), "de-AT;q=0.8,en");
+/// ```
+pub fn serialize_weighted<L: AsRef<LanguageIdentifier>>(entries: &[(L, f32)]) -> String {
+    let mut header = String::new();
+    for (i, (langid, quality)) in entries.iter().enumerate() {
+        if i > 0 {
+            header.push(',');
+        }
+        // Writing to a `String` can't fail.
+        let _ = write!(header, "{}", langid.as_ref());
+        let quality = if quality.is_nan() {
+            0.0
+        } else {
+            quality.clamp(0.0, 1.0)
+        };
+        let quality = format!("{:.3}", quality + 0.0);
+        let quality = quality.trim_end_matches('0').trim_end_matches('.');
+        if quality != "1" {
+            header.push_str(";q=");
+            header.push_str(quality);
+        }
+    }
+    header
+}
+
+/// Splits the header into language tags, without parsing them.
+pub(crate) fn tags(s: &str) -> impl Iterator<Item = &str> {
+    s.split(',')
+        .filter_map(|t| t.trim().split(';').next())
+        .filter(|t| !t.is_empty())
+}
@@ -0,0 +1,148 @@
+//! Implementations of `Arbitrary` for fuzzing and property testing.
+//!
+//! The language identifier types come from `icu_locid`, so they are wrapped
+//! in [`ArbitraryLangId`] and [`ArbitraryLocale`]. The generated identifiers
+//! are always valid, and are biased towards a small set of common subtags,
+//! so that negotiations over them actually find matches.
+//!
+//! The strategies and options of this crate implement `Arbitrary` directly.
+//!
+//! This module is only available with the `arbitrary` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use fluent_langneg::arbitrary::ArbitraryLangId;
+//! use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+//!
+//! let data = [7u8; 64];
+//! let mut u = Unstructured::new(&data);
+//! let requested = Vec::<ArbitraryLangId>::arbitrary(&mut u).unwrap();
+//! let available = Vec::<ArbitraryLangId>::arbitrary(&mut u).unwrap();
+//! let strategy = NegotiationStrategy::arbitrary(&mut u).unwrap();
+//!
+//! for supported in negotiate_languages(&requested, &available, None, strategy) {
+//!     assert!(available.iter().any(|a| std::ptr::eq(a, supported)));
+//! }
+//! ```
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use icu_locid::extensions::unicode::Unicode;
+use icu_locid::subtags::{Language, Region, Script, Variant, Variants};
+use icu_locid::{LanguageIdentifier, Locale};
+
+static LANGUAGES: &[&str] = &["und", "en", "fr", "de", "sr", "zh", "az", "ar", "pl"];
+static SCRIPTS: &[&str] = &["Latn", "Cyrl", "Arab", "Hant", "Hans"];
+static REGIONS: &[&str] = &["US", "GB", "FR", "DE", "RS", "CN", "TW", "419"];
+static VARIANTS: &[&str] = &["1996", "fonipa", "valencia", "rozaj"];
+static KEYWORDS: &[&str] = &["ca-buddhist", "hc-h23", "nu-arab", "co-phonebk"];
+
+/// A valid [`LanguageIdentifier`] implementing `Arbitrary`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArbitraryLangId(pub LanguageIdentifier);
+
+/// A valid [`Locale`] implementing `Arbitrary`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArbitraryLocale(pub Locale);
+
+impl AsRef<LanguageIdentifier> for ArbitraryLangId {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        &self.0
+    }
+}
+
+impl AsRef<LanguageIdentifier> for ArbitraryLocale {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        &self.0.id
+    }
+}
+
+impl From<ArbitraryLangId> for LanguageIdentifier {
+    fn from(langid: ArbitraryLangId) -> Self {
+        langid.0
+    }
+}
+
+impl From<ArbitraryLocale> for Locale {
+    fn from(locale: ArbitraryLocale) -> Self {
+        locale.0
+    }
+}
+
+/// Picks a subtag from the pool most of the time, and otherwise generates
+/// one from `len` characters of `alphabet`, falling back on the pool if it
+/// is not valid.
+fn subtag<T>(
+    u: &mut Unstructured<'_>,
+    pool: &[&str],
+    len: std::ops::RangeInclusive<usize>,
+    alphabet: &[u8],
+    parse: impl Fn(&[u8]) -> Option<T>,
+) -> Result<T> {
+    let pooled = u.choose(pool)?.as_bytes();
+    if u.ratio(3, 4)? {
+        return Ok(parse(pooled).expect("Pooled subtags must be valid."));
+    }
+    let len = u.int_in_range(len)?;
+    let mut bytes = Vec::with_capacity(len);
+    for _ in 0..len {
+        bytes.push(*u.choose(alphabet)?);
+    }
+    Ok(parse(&bytes).unwrap_or_else(|| parse(pooled).expect("Pooled subtags must be valid.")))
+}
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl<'a> Arbitrary<'a> for ArbitraryLangId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let language = subtag(u, LANGUAGES, 2..=3, LOWER, |b| {
+            Language::try_from_bytes(b).ok()
+        })?;
+        let script = if u.arbitrary()? {
+            Some(subtag(u, SCRIPTS, 4..=4, LOWER, |b| {
+                Script::try_from_bytes(b).ok()
+            })?)
+        } else {
+            None
+        };
+        let region = if u.arbitrary()? {
+            Some(subtag(u, REGIONS, 2..=2, UPPER, |b| {
+                Region::try_from_bytes(b).ok()
+            })?)
+        } else {
+            None
+        };
+        let mut variants = vec![];
+        for _ in 0..u.int_in_range(0..=2u8)? {
+            variants.push(subtag(u, VARIANTS, 5..=8, LOWER, |b| {
+                Variant::try_from_bytes(b).ok()
+            })?);
+        }
+        variants.sort_unstable();
+        variants.dedup();
+
+        let mut langid = LanguageIdentifier::from((language, script, region));
+        langid.variants = Variants::from_vec_unchecked(variants);
+        Ok(Self(langid))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ArbitraryLocale {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let ArbitraryLangId(langid) = u.arbitrary()?;
+        let mut locale = Locale::from(langid);
+        if u.arbitrary()? {
+            let keyword = u.choose(KEYWORDS)?;
+            let extension: Locale = format!("und-u-{}", keyword)
+                .parse()
+                .expect("Pooled keywords must be valid.");
+            locale.extensions.unicode = Unicode {
+                keywords: extension.extensions.unicode.keywords,
+                ..Default::default()
+            };
+        }
+        Ok(Self(locale))
+    }
+}
@@ -0,0 +1,83 @@
+use icu_locid::subtags::{language, region, script, Language, Region, Script};
+
+/// Deprecated language subtags, mapped to their replacement language,
+/// and the script implied by the deprecated subtag.
+///
+/// Sorted by the deprecated subtag.
+static LANGUAGE_KEYS: &[(Language, Language, Option<Script>)] = &[
+    (language!("in"), language!("id"), None),
+    (language!("iw"), language!("he"), None),
+    (language!("ji"), language!("yi"), None),
+    (language!("jw"), language!("jv"), None),
+    (language!("mo"), language!("ro"), None),
+    (language!("sh"), language!("sr"), Some(script!("Latn"))),
+];
+
+/// Deprecated region subtags, mapped to their replacement region.
+///
+/// Regions which have been split, such as `SU`, are mapped to the region
+/// most commonly associated with them.
+///
+/// Sorted by the deprecated subtag.
+static REGION_KEYS: &[(Region, Region)] = &[
+    (region!("BU"), region!("MM")),
+    (region!("CS"), region!("RS")),
+    (region!("DD"), region!("DE")),
+    (region!("DY"), region!("BJ")),
+    (region!("FX"), region!("FR")),
+    (region!("HV"), region!("BF")),
+    (region!("NH"), region!("VU")),
+    (region!("RH"), region!("ZW")),
+    (region!("SU"), region!("RU")),
+    (region!("TP"), region!("TL")),
+    (region!("YD"), region!("YE")),
+    (region!("YU"), region!("RS")),
+    (region!("ZR"), region!("CD")),
+];
+
+pub fn replace_language(input: Language) -> Option<(Language, Option<Script>)> {
+    LANGUAGE_KEYS
+        .binary_search_by(|(l, _, _)| l.cmp(&input))
+        .ok()
+        .and_then(|idx| LANGUAGE_KEYS.get(idx))
+        .map(|(_, language, script)| (*language, *script))
+}
+
+pub fn replace_region(input: Region) -> Option<Region> {
+    REGION_KEYS
+        .binary_search_by(|(r, _)| r.cmp(&input))
+        .ok()
+        .and_then(|idx| REGION_KEYS.get(idx))
+        .map(|(_, region)| *region)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_sort() {
+        for v in LANGUAGE_KEYS.windows(2) {
+            let (v1, v2) = (v[0], v[1]);
+            assert!(
+                v1.0 < v2.0,
+                "Language \"{}\" is placed after \"{}\"",
+                v1.0,
+                v2.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_region_sort() {
+        for v in REGION_KEYS.windows(2) {
+            let (v1, v2) = (v[0], v[1]);
+            assert!(
+                v1.0 < v2.0,
+                "Region \"{}\" is placed after \"{}\"",
+                v1.0,
+                v2.0
+            );
+        }
+    }
+}
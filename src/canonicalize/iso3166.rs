@@ -0,0 +1,561 @@
+use icu_locid::subtags::{region, Region};
+
+/// ISO 3166-1 alpha-3 codes of countries, mapped to their alpha-2 codes.
+///
+/// Generated from the ISO 3166-1 code table, sorted by the alpha-3 code.
+static ALPHA3_KEYS: &[(&str, Region)] = &[
+    ("ABW", region!("AW")),
+    ("AFG", region!("AF")),
+    ("AGO", region!("AO")),
+    ("AIA", region!("AI")),
+    ("ALA", region!("AX")),
+    ("ALB", region!("AL")),
+    ("AND", region!("AD")),
+    ("ARE", region!("AE")),
+    ("ARG", region!("AR")),
+    ("ARM", region!("AM")),
+    ("ASM", region!("AS")),
+    ("ATA", region!("AQ")),
+    ("ATF", region!("TF")),
+    ("ATG", region!("AG")),
+    ("AUS", region!("AU")),
+    ("AUT", region!("AT")),
+    ("AZE", region!("AZ")),
+    ("BDI", region!("BI")),
+    ("BEL", region!("BE")),
+    ("BEN", region!("BJ")),
+    ("BES", region!("BQ")),
+    ("BFA", region!("BF")),
+    ("BGD", region!("BD")),
+    ("BGR", region!("BG")),
+    ("BHR", region!("BH")),
+    ("BHS", region!("BS")),
+    ("BIH", region!("BA")),
+    ("BLM", region!("BL")),
+    ("BLR", region!("BY")),
+    ("BLZ", region!("BZ")),
+    ("BMU", region!("BM")),
+    ("BOL", region!("BO")),
+    ("BRA", region!("BR")),
+    ("BRB", region!("BB")),
+    ("BRN", region!("BN")),
+    ("BTN", region!("BT")),
+    ("BVT", region!("BV")),
+    ("BWA", region!("BW")),
+    ("CAF", region!("CF")),
+    ("CAN", region!("CA")),
+    ("CCK", region!("CC")),
+    ("CHE", region!("CH")),
+    ("CHL", region!("CL")),
+    ("CHN", region!("CN")),
+    ("CIV", region!("CI")),
+    ("CMR", region!("CM")),
+    ("COD", region!("CD")),
+    ("COG", region!("CG")),
+    ("COK", region!("CK")),
+    ("COL", region!("CO")),
+    ("COM", region!("KM")),
+    ("CPV", region!("CV")),
+    ("CRI", region!("CR")),
+    ("CUB", region!("CU")),
+    ("CUW", region!("CW")),
+    ("CXR", region!("CX")),
+    ("CYM", region!("KY")),
+    ("CYP", region!("CY")),
+    ("CZE", region!("CZ")),
+    ("DEU", region!("DE")),
+    ("DJI", region!("DJ")),
+    ("DMA", region!("DM")),
+    ("DNK", region!("DK")),
+    ("DOM", region!("DO")),
+    ("DZA", region!("DZ")),
+    ("ECU", region!("EC")),
+    ("EGY", region!("EG")),
+    ("ERI", region!("ER")),
+    ("ESH", region!("EH")),
+    ("ESP", region!("ES")),
+    ("EST", region!("EE")),
+    ("ETH", region!("ET")),
+    ("FIN", region!("FI")),
+    ("FJI", region!("FJ")),
+    ("FLK", region!("FK")),
+    ("FRA", region!("FR")),
+    ("FRO", region!("FO")),
+    ("FSM", region!("FM")),
+    ("GAB", region!("GA")),
+    ("GBR", region!("GB")),
+    ("GEO", region!("GE")),
+    ("GGY", region!("GG")),
+    ("GHA", region!("GH")),
+    ("GIB", region!("GI")),
+    ("GIN", region!("GN")),
+    ("GLP", region!("GP")),
+    ("GMB", region!("GM")),
+    ("GNB", region!("GW")),
+    ("GNQ", region!("GQ")),
+    ("GRC", region!("GR")),
+    ("GRD", region!("GD")),
+    ("GRL", region!("GL")),
+    ("GTM", region!("GT")),
+    ("GUF", region!("GF")),
+    ("GUM", region!("GU")),
+    ("GUY", region!("GY")),
+    ("HKG", region!("HK")),
+    ("HMD", region!("HM")),
+    ("HND", region!("HN")),
+    ("HRV", region!("HR")),
+    ("HTI", region!("HT")),
+    ("HUN", region!("HU")),
+    ("IDN", region!("ID")),
+    ("IMN", region!("IM")),
+    ("IND", region!("IN")),
+    ("IOT", region!("IO")),
+    ("IRL", region!("IE")),
+    ("IRN", region!("IR")),
+    ("IRQ", region!("IQ")),
+    ("ISL", region!("IS")),
+    ("ISR", region!("IL")),
+    ("ITA", region!("IT")),
+    ("JAM", region!("JM")),
+    ("JEY", region!("JE")),
+    ("JOR", region!("JO")),
+    ("JPN", region!("JP")),
+    ("KAZ", region!("KZ")),
+    ("KEN", region!("KE")),
+    ("KGZ", region!("KG")),
+    ("KHM", region!("KH")),
+    ("KIR", region!("KI")),
+    ("KNA", region!("KN")),
+    ("KOR", region!("KR")),
+    ("KWT", region!("KW")),
+    ("LAO", region!("LA")),
+    ("LBN", region!("LB")),
+    ("LBR", region!("LR")),
+    ("LBY", region!("LY")),
+    ("LCA", region!("LC")),
+    ("LIE", region!("LI")),
+    ("LKA", region!("LK")),
+    ("LSO", region!("LS")),
+    ("LTU", region!("LT")),
+    ("LUX", region!("LU")),
+    ("LVA", region!("LV")),
+    ("MAC", region!("MO")),
+    ("MAF", region!("MF")),
+    ("MAR", region!("MA")),
+    ("MCO", region!("MC")),
+    ("MDA", region!("MD")),
+    ("MDG", region!("MG")),
+    ("MDV", region!("MV")),
+    ("MEX", region!("MX")),
+    ("MHL", region!("MH")),
+    ("MKD", region!("MK")),
+    ("MLI", region!("ML")),
+    ("MLT", region!("MT")),
+    ("MMR", region!("MM")),
+    ("MNE", region!("ME")),
+    ("MNG", region!("MN")),
+    ("MNP", region!("MP")),
+    ("MOZ", region!("MZ")),
+    ("MRT", region!("MR")),
+    ("MSR", region!("MS")),
+    ("MTQ", region!("MQ")),
+    ("MUS", region!("MU")),
+    ("MWI", region!("MW")),
+    ("MYS", region!("MY")),
+    ("MYT", region!("YT")),
+    ("NAM", region!("NA")),
+    ("NCL", region!("NC")),
+    ("NER", region!("NE")),
+    ("NFK", region!("NF")),
+    ("NGA", region!("NG")),
+    ("NIC", region!("NI")),
+    ("NIU", region!("NU")),
+    ("NLD", region!("NL")),
+    ("NOR", region!("NO")),
+    ("NPL", region!("NP")),
+    ("NRU", region!("NR")),
+    ("NZL", region!("NZ")),
+    ("OMN", region!("OM")),
+    ("PAK", region!("PK")),
+    ("PAN", region!("PA")),
+    ("PCN", region!("PN")),
+    ("PER", region!("PE")),
+    ("PHL", region!("PH")),
+    ("PLW", region!("PW")),
+    ("PNG", region!("PG")),
+    ("POL", region!("PL")),
+    ("PRI", region!("PR")),
+    ("PRK", region!("KP")),
+    ("PRT", region!("PT")),
+    ("PRY", region!("PY")),
+    ("PSE", region!("PS")),
+    ("PYF", region!("PF")),
+    ("QAT", region!("QA")),
+    ("REU", region!("RE")),
+    ("ROU", region!("RO")),
+    ("RUS", region!("RU")),
+    ("RWA", region!("RW")),
+    ("SAU", region!("SA")),
+    ("SDN", region!("SD")),
+    ("SEN", region!("SN")),
+    ("SGP", region!("SG")),
+    ("SGS", region!("GS")),
+    ("SHN", region!("SH")),
+    ("SJM", region!("SJ")),
+    ("SLB", region!("SB")),
+    ("SLE", region!("SL")),
+    ("SLV", region!("SV")),
+    ("SMR", region!("SM")),
+    ("SOM", region!("SO")),
+    ("SPM", region!("PM")),
+    ("SRB", region!("RS")),
+    ("SSD", region!("SS")),
+    ("STP", region!("ST")),
+    ("SUR", region!("SR")),
+    ("SVK", region!("SK")),
+    ("SVN", region!("SI")),
+    ("SWE", region!("SE")),
+    ("SWZ", region!("SZ")),
+    ("SXM", region!("SX")),
+    ("SYC", region!("SC")),
+    ("SYR", region!("SY")),
+    ("TCA", region!("TC")),
+    ("TCD", region!("TD")),
+    ("TGO", region!("TG")),
+    ("THA", region!("TH")),
+    ("TJK", region!("TJ")),
+    ("TKL", region!("TK")),
+    ("TKM", region!("TM")),
+    ("TLS", region!("TL")),
+    ("TON", region!("TO")),
+    ("TTO", region!("TT")),
+    ("TUN", region!("TN")),
+    ("TUR", region!("TR")),
+    ("TUV", region!("TV")),
+    ("TWN", region!("TW")),
+    ("TZA", region!("TZ")),
+    ("UGA", region!("UG")),
+    ("UKR", region!("UA")),
+    ("UMI", region!("UM")),
+    ("URY", region!("UY")),
+    ("USA", region!("US")),
+    ("UZB", region!("UZ")),
+    ("VAT", region!("VA")),
+    ("VCT", region!("VC")),
+    ("VEN", region!("VE")),
+    ("VGB", region!("VG")),
+    ("VIR", region!("VI")),
+    ("VNM", region!("VN")),
+    ("VUT", region!("VU")),
+    ("WLF", region!("WF")),
+    ("WSM", region!("WS")),
+    ("YEM", region!("YE")),
+    ("ZAF", region!("ZA")),
+    ("ZMB", region!("ZM")),
+    ("ZWE", region!("ZW")),
+];
+
+/// ISO 3166-1 numeric codes of countries, mapped to their alpha-2 codes.
+///
+/// UN M.49 codes of macro regions, such as `419`, have no alpha-2
+/// equivalent and are not listed.
+///
+/// Generated from the ISO 3166-1 code table, sorted by the numeric code.
+static NUMERIC_KEYS: &[(Region, Region)] = &[
+    (region!("004"), region!("AF")),
+    (region!("008"), region!("AL")),
+    (region!("010"), region!("AQ")),
+    (region!("012"), region!("DZ")),
+    (region!("016"), region!("AS")),
+    (region!("020"), region!("AD")),
+    (region!("024"), region!("AO")),
+    (region!("028"), region!("AG")),
+    (region!("031"), region!("AZ")),
+    (region!("032"), region!("AR")),
+    (region!("036"), region!("AU")),
+    (region!("040"), region!("AT")),
+    (region!("044"), region!("BS")),
+    (region!("048"), region!("BH")),
+    (region!("050"), region!("BD")),
+    (region!("051"), region!("AM")),
+    (region!("052"), region!("BB")),
+    (region!("056"), region!("BE")),
+    (region!("060"), region!("BM")),
+    (region!("064"), region!("BT")),
+    (region!("068"), region!("BO")),
+    (region!("070"), region!("BA")),
+    (region!("072"), region!("BW")),
+    (region!("074"), region!("BV")),
+    (region!("076"), region!("BR")),
+    (region!("084"), region!("BZ")),
+    (region!("086"), region!("IO")),
+    (region!("090"), region!("SB")),
+    (region!("092"), region!("VG")),
+    (region!("096"), region!("BN")),
+    (region!("100"), region!("BG")),
+    (region!("104"), region!("MM")),
+    (region!("108"), region!("BI")),
+    (region!("112"), region!("BY")),
+    (region!("116"), region!("KH")),
+    (region!("120"), region!("CM")),
+    (region!("124"), region!("CA")),
+    (region!("132"), region!("CV")),
+    (region!("136"), region!("KY")),
+    (region!("140"), region!("CF")),
+    (region!("144"), region!("LK")),
+    (region!("148"), region!("TD")),
+    (region!("152"), region!("CL")),
+    (region!("156"), region!("CN")),
+    (region!("158"), region!("TW")),
+    (region!("162"), region!("CX")),
+    (region!("166"), region!("CC")),
+    (region!("170"), region!("CO")),
+    (region!("174"), region!("KM")),
+    (region!("175"), region!("YT")),
+    (region!("178"), region!("CG")),
+    (region!("180"), region!("CD")),
+    (region!("184"), region!("CK")),
+    (region!("188"), region!("CR")),
+    (region!("191"), region!("HR")),
+    (region!("192"), region!("CU")),
+    (region!("196"), region!("CY")),
+    (region!("203"), region!("CZ")),
+    (region!("204"), region!("BJ")),
+    (region!("208"), region!("DK")),
+    (region!("212"), region!("DM")),
+    (region!("214"), region!("DO")),
+    (region!("218"), region!("EC")),
+    (region!("222"), region!("SV")),
+    (region!("226"), region!("GQ")),
+    (region!("231"), region!("ET")),
+    (region!("232"), region!("ER")),
+    (region!("233"), region!("EE")),
+    (region!("234"), region!("FO")),
+    (region!("238"), region!("FK")),
+    (region!("239"), region!("GS")),
+    (region!("242"), region!("FJ")),
+    (region!("246"), region!("FI")),
+    (region!("248"), region!("AX")),
+    (region!("250"), region!("FR")),
+    (region!("254"), region!("GF")),
+    (region!("258"), region!("PF")),
+    (region!("260"), region!("TF")),
+    (region!("262"), region!("DJ")),
+    (region!("266"), region!("GA")),
+    (region!("268"), region!("GE")),
+    (region!("270"), region!("GM")),
+    (region!("275"), region!("PS")),
+    (region!("276"), region!("DE")),
+    (region!("288"), region!("GH")),
+    (region!("292"), region!("GI")),
+    (region!("296"), region!("KI")),
+    (region!("300"), region!("GR")),
+    (region!("304"), region!("GL")),
+    (region!("308"), region!("GD")),
+    (region!("312"), region!("GP")),
+    (region!("316"), region!("GU")),
+    (region!("320"), region!("GT")),
+    (region!("324"), region!("GN")),
+    (region!("328"), region!("GY")),
+    (region!("332"), region!("HT")),
+    (region!("334"), region!("HM")),
+    (region!("336"), region!("VA")),
+    (region!("340"), region!("HN")),
+    (region!("344"), region!("HK")),
+    (region!("348"), region!("HU")),
+    (region!("352"), region!("IS")),
+    (region!("356"), region!("IN")),
+    (region!("360"), region!("ID")),
+    (region!("364"), region!("IR")),
+    (region!("368"), region!("IQ")),
+    (region!("372"), region!("IE")),
+    (region!("376"), region!("IL")),
+    (region!("380"), region!("IT")),
+    (region!("384"), region!("CI")),
+    (region!("388"), region!("JM")),
+    (region!("392"), region!("JP")),
+    (region!("398"), region!("KZ")),
+    (region!("400"), region!("JO")),
+    (region!("404"), region!("KE")),
+    (region!("408"), region!("KP")),
+    (region!("410"), region!("KR")),
+    (region!("414"), region!("KW")),
+    (region!("417"), region!("KG")),
+    (region!("418"), region!("LA")),
+    (region!("422"), region!("LB")),
+    (region!("426"), region!("LS")),
+    (region!("428"), region!("LV")),
+    (region!("430"), region!("LR")),
+    (region!("434"), region!("LY")),
+    (region!("438"), region!("LI")),
+    (region!("440"), region!("LT")),
+    (region!("442"), region!("LU")),
+    (region!("446"), region!("MO")),
+    (region!("450"), region!("MG")),
+    (region!("454"), region!("MW")),
+    (region!("458"), region!("MY")),
+    (region!("462"), region!("MV")),
+    (region!("466"), region!("ML")),
+    (region!("470"), region!("MT")),
+    (region!("474"), region!("MQ")),
+    (region!("478"), region!("MR")),
+    (region!("480"), region!("MU")),
+    (region!("484"), region!("MX")),
+    (region!("492"), region!("MC")),
+    (region!("496"), region!("MN")),
+    (region!("498"), region!("MD")),
+    (region!("499"), region!("ME")),
+    (region!("500"), region!("MS")),
+    (region!("504"), region!("MA")),
+    (region!("508"), region!("MZ")),
+    (region!("512"), region!("OM")),
+    (region!("516"), region!("NA")),
+    (region!("520"), region!("NR")),
+    (region!("524"), region!("NP")),
+    (region!("528"), region!("NL")),
+    (region!("531"), region!("CW")),
+    (region!("533"), region!("AW")),
+    (region!("534"), region!("SX")),
+    (region!("535"), region!("BQ")),
+    (region!("540"), region!("NC")),
+    (region!("548"), region!("VU")),
+    (region!("554"), region!("NZ")),
+    (region!("558"), region!("NI")),
+    (region!("562"), region!("NE")),
+    (region!("566"), region!("NG")),
+    (region!("570"), region!("NU")),
+    (region!("574"), region!("NF")),
+    (region!("578"), region!("NO")),
+    (region!("580"), region!("MP")),
+    (region!("581"), region!("UM")),
+    (region!("583"), region!("FM")),
+    (region!("584"), region!("MH")),
+    (region!("585"), region!("PW")),
+    (region!("586"), region!("PK")),
+    (region!("591"), region!("PA")),
+    (region!("598"), region!("PG")),
+    (region!("600"), region!("PY")),
+    (region!("604"), region!("PE")),
+    (region!("608"), region!("PH")),
+    (region!("612"), region!("PN")),
+    (region!("616"), region!("PL")),
+    (region!("620"), region!("PT")),
+    (region!("624"), region!("GW")),
+    (region!("626"), region!("TL")),
+    (region!("630"), region!("PR")),
+    (region!("634"), region!("QA")),
+    (region!("638"), region!("RE")),
+    (region!("642"), region!("RO")),
+    (region!("643"), region!("RU")),
+    (region!("646"), region!("RW")),
+    (region!("652"), region!("BL")),
+    (region!("654"), region!("SH")),
+    (region!("659"), region!("KN")),
+    (region!("660"), region!("AI")),
+    (region!("662"), region!("LC")),
+    (region!("663"), region!("MF")),
+    (region!("666"), region!("PM")),
+    (region!("670"), region!("VC")),
+    (region!("674"), region!("SM")),
+    (region!("678"), region!("ST")),
+    (region!("682"), region!("SA")),
+    (region!("686"), region!("SN")),
+    (region!("688"), region!("RS")),
+    (region!("690"), region!("SC")),
+    (region!("694"), region!("SL")),
+    (region!("702"), region!("SG")),
+    (region!("703"), region!("SK")),
+    (region!("704"), region!("VN")),
+    (region!("705"), region!("SI")),
+    (region!("706"), region!("SO")),
+    (region!("710"), region!("ZA")),
+    (region!("716"), region!("ZW")),
+    (region!("724"), region!("ES")),
+    (region!("728"), region!("SS")),
+    (region!("729"), region!("SD")),
+    (region!("732"), region!("EH")),
+    (region!("740"), region!("SR")),
+    (region!("744"), region!("SJ")),
+    (region!("748"), region!("SZ")),
+    (region!("752"), region!("SE")),
+    (region!("756"), region!("CH")),
+    (region!("760"), region!("SY")),
+    (region!("762"), region!("TJ")),
+    (region!("764"), region!("TH")),
+    (region!("768"), region!("TG")),
+    (region!("772"), region!("TK")),
+    (region!("776"), region!("TO")),
+    (region!("780"), region!("TT")),
+    (region!("784"), region!("AE")),
+    (region!("788"), region!("TN")),
+    (region!("792"), region!("TR")),
+    (region!("795"), region!("TM")),
+    (region!("796"), region!("TC")),
+    (region!("798"), region!("TV")),
+    (region!("800"), region!("UG")),
+    (region!("804"), region!("UA")),
+    (region!("807"), region!("MK")),
+    (region!("818"), region!("EG")),
+    (region!("826"), region!("GB")),
+    (region!("831"), region!("GG")),
+    (region!("832"), region!("JE")),
+    (region!("833"), region!("IM")),
+    (region!("834"), region!("TZ")),
+    (region!("840"), region!("US")),
+    (region!("850"), region!("VI")),
+    (region!("854"), region!("BF")),
+    (region!("858"), region!("UY")),
+    (region!("860"), region!("UZ")),
+    (region!("862"), region!("VE")),
+    (region!("876"), region!("WF")),
+    (region!("882"), region!("WS")),
+    (region!("887"), region!("YE")),
+    (region!("894"), region!("ZM")),
+];
+
+pub fn region_from_alpha3(input: &str) -> Option<Region> {
+    ALPHA3_KEYS
+        .binary_search_by(|(r, _)| (*r).cmp(input))
+        .ok()
+        .and_then(|idx| ALPHA3_KEYS.get(idx))
+        .map(|(_, region)| *region)
+}
+
+pub fn region_from_numeric(input: Region) -> Option<Region> {
+    NUMERIC_KEYS
+        .binary_search_by(|(r, _)| r.cmp(&input))
+        .ok()
+        .and_then(|idx| NUMERIC_KEYS.get(idx))
+        .map(|(_, region)| *region)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alpha3_sort() {
+        for v in ALPHA3_KEYS.windows(2) {
+            let (v1, v2) = (v[0], v[1]);
+            assert!(
+                v1.0 < v2.0,
+                "Region \"{}\" is placed after \"{}\"",
+                v1.0,
+                v2.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_numeric_sort() {
+        for v in NUMERIC_KEYS.windows(2) {
+            let (v1, v2) = (v[0], v[1]);
+            assert!(
+                v1.0 < v2.0,
+                "Region \"{}\" is placed after \"{}\"",
+                v1.0,
+                v2.0
+            );
+        }
+    }
+}
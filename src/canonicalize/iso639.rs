@@ -0,0 +1,238 @@
+use icu_locid::subtags::{language, Language};
+
+/// Three-letter ISO 639-2/B, ISO 639-2/T and ISO 639-3 codes of languages
+/// which also have a two-letter ISO 639-1 code, which is the one BCP47
+/// requires to be used.
+///
+/// Generated from the ISO 639-3 code table, sorted by the three-letter code.
+static ISO639_KEYS: &[(Language, Language)] = &[
+    (language!("aar"), language!("aa")),
+    (language!("abk"), language!("ab")),
+    (language!("afr"), language!("af")),
+    (language!("aka"), language!("ak")),
+    (language!("alb"), language!("sq")),
+    (language!("amh"), language!("am")),
+    (language!("ara"), language!("ar")),
+    (language!("arg"), language!("an")),
+    (language!("arm"), language!("hy")),
+    (language!("asm"), language!("as")),
+    (language!("ava"), language!("av")),
+    (language!("ave"), language!("ae")),
+    (language!("aym"), language!("ay")),
+    (language!("aze"), language!("az")),
+    (language!("bak"), language!("ba")),
+    (language!("bam"), language!("bm")),
+    (language!("baq"), language!("eu")),
+    (language!("bel"), language!("be")),
+    (language!("ben"), language!("bn")),
+    (language!("bis"), language!("bi")),
+    (language!("bod"), language!("bo")),
+    (language!("bos"), language!("bs")),
+    (language!("bre"), language!("br")),
+    (language!("bul"), language!("bg")),
+    (language!("bur"), language!("my")),
+    (language!("cat"), language!("ca")),
+    (language!("ces"), language!("cs")),
+    (language!("cha"), language!("ch")),
+    (language!("che"), language!("ce")),
+    (language!("chi"), language!("zh")),
+    (language!("chu"), language!("cu")),
+    (language!("chv"), language!("cv")),
+    (language!("cor"), language!("kw")),
+    (language!("cos"), language!("co")),
+    (language!("cre"), language!("cr")),
+    (language!("cym"), language!("cy")),
+    (language!("cze"), language!("cs")),
+    (language!("dan"), language!("da")),
+    (language!("deu"), language!("de")),
+    (language!("div"), language!("dv")),
+    (language!("dut"), language!("nl")),
+    (language!("dzo"), language!("dz")),
+    (language!("ell"), language!("el")),
+    (language!("eng"), language!("en")),
+    (language!("epo"), language!("eo")),
+    (language!("est"), language!("et")),
+    (language!("eus"), language!("eu")),
+    (language!("ewe"), language!("ee")),
+    (language!("fao"), language!("fo")),
+    (language!("fas"), language!("fa")),
+    (language!("fij"), language!("fj")),
+    (language!("fin"), language!("fi")),
+    (language!("fra"), language!("fr")),
+    (language!("fre"), language!("fr")),
+    (language!("fry"), language!("fy")),
+    (language!("ful"), language!("ff")),
+    (language!("geo"), language!("ka")),
+    (language!("ger"), language!("de")),
+    (language!("gla"), language!("gd")),
+    (language!("gle"), language!("ga")),
+    (language!("glg"), language!("gl")),
+    (language!("glv"), language!("gv")),
+    (language!("gre"), language!("el")),
+    (language!("grn"), language!("gn")),
+    (language!("guj"), language!("gu")),
+    (language!("hat"), language!("ht")),
+    (language!("hau"), language!("ha")),
+    (language!("heb"), language!("he")),
+    (language!("her"), language!("hz")),
+    (language!("hin"), language!("hi")),
+    (language!("hmo"), language!("ho")),
+    (language!("hrv"), language!("hr")),
+    (language!("hun"), language!("hu")),
+    (language!("hye"), language!("hy")),
+    (language!("ibo"), language!("ig")),
+    (language!("ice"), language!("is")),
+    (language!("ido"), language!("io")),
+    (language!("iii"), language!("ii")),
+    (language!("iku"), language!("iu")),
+    (language!("ile"), language!("ie")),
+    (language!("ina"), language!("ia")),
+    (language!("ind"), language!("id")),
+    (language!("ipk"), language!("ik")),
+    (language!("isl"), language!("is")),
+    (language!("ita"), language!("it")),
+    (language!("jav"), language!("jv")),
+    (language!("jpn"), language!("ja")),
+    (language!("kal"), language!("kl")),
+    (language!("kan"), language!("kn")),
+    (language!("kas"), language!("ks")),
+    (language!("kat"), language!("ka")),
+    (language!("kau"), language!("kr")),
+    (language!("kaz"), language!("kk")),
+    (language!("khm"), language!("km")),
+    (language!("kik"), language!("ki")),
+    (language!("kin"), language!("rw")),
+    (language!("kir"), language!("ky")),
+    (language!("kom"), language!("kv")),
+    (language!("kon"), language!("kg")),
+    (language!("kor"), language!("ko")),
+    (language!("kua"), language!("kj")),
+    (language!("kur"), language!("ku")),
+    (language!("lao"), language!("lo")),
+    (language!("lat"), language!("la")),
+    (language!("lav"), language!("lv")),
+    (language!("lim"), language!("li")),
+    (language!("lin"), language!("ln")),
+    (language!("lit"), language!("lt")),
+    (language!("ltz"), language!("lb")),
+    (language!("lub"), language!("lu")),
+    (language!("lug"), language!("lg")),
+    (language!("mac"), language!("mk")),
+    (language!("mah"), language!("mh")),
+    (language!("mal"), language!("ml")),
+    (language!("mao"), language!("mi")),
+    (language!("mar"), language!("mr")),
+    (language!("may"), language!("ms")),
+    (language!("mkd"), language!("mk")),
+    (language!("mlg"), language!("mg")),
+    (language!("mlt"), language!("mt")),
+    (language!("mon"), language!("mn")),
+    (language!("mri"), language!("mi")),
+    (language!("msa"), language!("ms")),
+    (language!("mya"), language!("my")),
+    (language!("nau"), language!("na")),
+    (language!("nav"), language!("nv")),
+    (language!("nbl"), language!("nr")),
+    (language!("nde"), language!("nd")),
+    (language!("ndo"), language!("ng")),
+    (language!("nep"), language!("ne")),
+    (language!("nld"), language!("nl")),
+    (language!("nno"), language!("nn")),
+    (language!("nob"), language!("nb")),
+    (language!("nor"), language!("no")),
+    (language!("nya"), language!("ny")),
+    (language!("oci"), language!("oc")),
+    (language!("oji"), language!("oj")),
+    (language!("ori"), language!("or")),
+    (language!("orm"), language!("om")),
+    (language!("oss"), language!("os")),
+    (language!("pan"), language!("pa")),
+    (language!("per"), language!("fa")),
+    (language!("pli"), language!("pi")),
+    (language!("pol"), language!("pl")),
+    (language!("por"), language!("pt")),
+    (language!("pus"), language!("ps")),
+    (language!("que"), language!("qu")),
+    (language!("roh"), language!("rm")),
+    (language!("ron"), language!("ro")),
+    (language!("rum"), language!("ro")),
+    (language!("run"), language!("rn")),
+    (language!("rus"), language!("ru")),
+    (language!("sag"), language!("sg")),
+    (language!("san"), language!("sa")),
+    (language!("sin"), language!("si")),
+    (language!("slk"), language!("sk")),
+    (language!("slo"), language!("sk")),
+    (language!("slv"), language!("sl")),
+    (language!("sme"), language!("se")),
+    (language!("smo"), language!("sm")),
+    (language!("sna"), language!("sn")),
+    (language!("snd"), language!("sd")),
+    (language!("som"), language!("so")),
+    (language!("sot"), language!("st")),
+    (language!("spa"), language!("es")),
+    (language!("sqi"), language!("sq")),
+    (language!("srd"), language!("sc")),
+    (language!("srp"), language!("sr")),
+    (language!("ssw"), language!("ss")),
+    (language!("sun"), language!("su")),
+    (language!("swa"), language!("sw")),
+    (language!("swe"), language!("sv")),
+    (language!("tah"), language!("ty")),
+    (language!("tam"), language!("ta")),
+    (language!("tat"), language!("tt")),
+    (language!("tel"), language!("te")),
+    (language!("tgk"), language!("tg")),
+    (language!("tgl"), language!("tl")),
+    (language!("tha"), language!("th")),
+    (language!("tib"), language!("bo")),
+    (language!("tir"), language!("ti")),
+    (language!("ton"), language!("to")),
+    (language!("tsn"), language!("tn")),
+    (language!("tso"), language!("ts")),
+    (language!("tuk"), language!("tk")),
+    (language!("tur"), language!("tr")),
+    (language!("twi"), language!("tw")),
+    (language!("uig"), language!("ug")),
+    (language!("ukr"), language!("uk")),
+    (language!("urd"), language!("ur")),
+    (language!("uzb"), language!("uz")),
+    (language!("ven"), language!("ve")),
+    (language!("vie"), language!("vi")),
+    (language!("vol"), language!("vo")),
+    (language!("wel"), language!("cy")),
+    (language!("wln"), language!("wa")),
+    (language!("wol"), language!("wo")),
+    (language!("xho"), language!("xh")),
+    (language!("yid"), language!("yi")),
+    (language!("yor"), language!("yo")),
+    (language!("zha"), language!("za")),
+    (language!("zho"), language!("zh")),
+    (language!("zul"), language!("zu")),
+];
+
+pub fn language_from_iso639(input: Language) -> Option<Language> {
+    ISO639_KEYS
+        .binary_search_by(|(l, _)| l.cmp(&input))
+        .ok()
+        .and_then(|idx| ISO639_KEYS.get(idx))
+        .map(|(_, language)| *language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso639_sort() {
+        for v in ISO639_KEYS.windows(2) {
+            let (v1, v2) = (v[0], v[1]);
+            assert!(
+                v1.0 < v2.0,
+                "Language \"{}\" is placed after \"{}\"",
+                v1.0,
+                v2.0
+            );
+        }
+    }
+}
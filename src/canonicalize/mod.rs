@@ -0,0 +1,340 @@
+//! Canonicalization replaces codes coming from other standards with
+//! the subtags preferred by BCP47, so that they can take part in
+//! language negotiation.
+//!
+//! Library and metadata systems such as MARC or Dublin Core often
+//! use ISO 639-2/B (`ger`, `fre`) or ISO 639-3 (`deu`, `fra`) codes,
+//! which are valid language subtags, but will never match the two-letter
+//! ISO 639-1 codes (`de`, `fr`) used by most available locales.
+//!
+//! Similarly, e-commerce feeds often carry ISO 3166-1 alpha-3 (`USA`, `DEU`)
+//! or numeric (`840`, `276`) region codes, while BCP47 uses alpha-2
+//! codes (`US`, `DE`). Alpha-3 codes are not valid region subtags at all,
+//! so they have to be replaced before the tag is parsed, see [`parse`].
+//!
+//! Deprecated subtags, such as `iw` or `YU`, are replaced as well, and
+//! [`script`] can drop a script subtag which is implied by likely subtags.
+//!
+//! Casing, as well as the order of variants and extension keys, is
+//! already normalized when a [`LanguageIdentifier`] or a `Locale` is parsed.
+//! [`langid`] performs all the remaining replacements in one call, and can
+//! be applied to a `Locale` through its `id` field.
+//!
+//! Canonicalization is opt-in and should be applied to the requested
+//! locales before they are passed to negotiation.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::canonicalize;
+//! use fluent_langneg::convert_vec_str_to_langids_lossy;
+//! use fluent_langneg::negotiate_languages;
+//! use fluent_langneg::NegotiationStrategy;
+//! use icu_locid::LanguageIdentifier;
+//!
+//! let mut requested = convert_vec_str_to_langids_lossy(&["ger", "fre-CA"]);
+//! let available = convert_vec_str_to_langids_lossy(&["en", "fr", "de"]);
+//!
+//! for langid in requested.iter_mut() {
+//!     canonicalize::language(langid);
+//! }
+//!
+//! let supported = negotiate_languages(
+//!   &requested,
+//!   &available,
+//!   None,
+//!   NegotiationStrategy::Filtering
+//! );
+//!
+//! let expected = convert_vec_str_to_langids_lossy(&["de", "fr"]);
+//! assert_eq!(supported,
+//!            expected.iter().map(|t| t.as_ref()).collect::<Vec<&LanguageIdentifier>>());
+//! ```
+
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::string_slice,
+    clippy::panic
+)]
+
+mod deprecated;
+mod iso3166;
+mod iso639;
+
+use icu_locid::subtags::{Language, Region};
+use icu_locid::LanguageIdentifier;
+
+use crate::negotiate::LocaleExpander;
+use crate::Error;
+
+/// Returns the ISO 639-1 language for a three-letter ISO 639-2/B,
+/// ISO 639-2/T or ISO 639-3 code, if one exists.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize::language_from_iso639;
+/// use icu_locid::subtags::language;
+///
+/// assert_eq!(language_from_iso639("ger"), Some(language!("de")));
+/// assert_eq!(language_from_iso639("deu"), Some(language!("de")));
+/// assert_eq!(language_from_iso639("yue"), None);
+/// ```
+pub fn language_from_iso639<S: AsRef<[u8]>>(input: S) -> Option<Language> {
+    let input = Language::try_from_bytes(input.as_ref()).ok()?;
+    iso639::language_from_iso639(input)
+}
+
+/// Replaces a three-letter language subtag of the language identifier
+/// with its ISO 639-1 equivalent.
+///
+/// Returns `true` if the language identifier has been modified.
+pub fn language(langid: &mut LanguageIdentifier) -> bool {
+    match iso639::language_from_iso639(langid.language) {
+        Some(language) => {
+            langid.language = language;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns the ISO 3166-1 alpha-2 region for an alpha-3 country code.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize::region_from_alpha3;
+/// use icu_locid::subtags::region;
+///
+/// assert_eq!(region_from_alpha3("USA"), Some(region!("US")));
+/// assert_eq!(region_from_alpha3("deu"), Some(region!("DE")));
+/// assert_eq!(region_from_alpha3("XYZ"), None);
+/// ```
+pub fn region_from_alpha3<S: AsRef<[u8]>>(input: S) -> Option<Region> {
+    let input = input.as_ref();
+    if input.len() != 3 || !input.iter().all(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    let mut code = [0; 3];
+    for (c, b) in code.iter_mut().zip(input) {
+        *c = b.to_ascii_uppercase();
+    }
+    // All bytes are ASCII letters, so this is always valid UTF-8.
+    iso3166::region_from_alpha3(std::str::from_utf8(&code).ok()?)
+}
+
+/// Returns the ISO 3166-1 alpha-2 region for a numeric country code.
+///
+/// UN M.49 codes of macro regions, such as `419` (Latin America), are
+/// valid region subtags on their own and are not converted.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize::region_from_numeric;
+/// use icu_locid::subtags::region;
+///
+/// assert_eq!(region_from_numeric("840"), Some(region!("US")));
+/// assert_eq!(region_from_numeric("276"), Some(region!("DE")));
+/// assert_eq!(region_from_numeric("419"), None);
+/// ```
+pub fn region_from_numeric<S: AsRef<[u8]>>(input: S) -> Option<Region> {
+    let input = Region::try_from_bytes(input.as_ref()).ok()?;
+    iso3166::region_from_numeric(input)
+}
+
+/// Replaces a numeric country region subtag of the language identifier
+/// with its alpha-2 equivalent.
+///
+/// Returns `true` if the language identifier has been modified.
+pub fn region(langid: &mut LanguageIdentifier) -> bool {
+    match langid.region.and_then(iso3166::region_from_numeric) {
+        Some(region) => {
+            langid.region = Some(region);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Replaces deprecated language and region subtags of the language
+/// identifier with their current equivalents.
+///
+/// Returns `true` if the language identifier has been modified.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize;
+/// use icu_locid::langid;
+///
+/// let mut langid = langid!("sh-YU");
+/// assert!(canonicalize::deprecated(&mut langid));
+/// assert_eq!(langid, langid!("sr-Latn-RS"));
+/// ```
+pub fn deprecated(langid: &mut LanguageIdentifier) -> bool {
+    let mut modified = false;
+    if let Some((language, script)) = deprecated::replace_language(langid.language) {
+        langid.language = language;
+        if langid.script.is_none() {
+            langid.script = script;
+        }
+        modified = true;
+    }
+    if let Some(region) = langid.region.and_then(deprecated::replace_region) {
+        langid.region = Some(region);
+        modified = true;
+    }
+    modified
+}
+
+/// Removes the script subtag of the language identifier, if it is the
+/// likely script for its language and region.
+///
+/// Returns `true` if the language identifier has been modified.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize;
+/// use icu_locid::langid;
+///
+/// let mut langid = langid!("en-Latn");
+/// assert!(canonicalize::script(&mut langid));
+/// assert_eq!(langid, langid!("en"));
+///
+/// let mut langid = langid!("sr-Latn");
+/// assert!(!canonicalize::script(&mut langid));
+/// ```
+pub fn script(langid: &mut LanguageIdentifier) -> bool {
+    if langid.script.is_none() || langid.language.is_empty() {
+        return false;
+    }
+    let mut likely = langid.clone();
+    likely.script = None;
+    LocaleExpander::new().maximize(&mut likely);
+    if likely.script == langid.script {
+        langid.script = None;
+        true
+    } else {
+        false
+    }
+}
+
+/// Applies [`language`], [`deprecated`] and [`region`] to the language
+/// identifier.
+///
+/// Returns `true` if the language identifier has been modified.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize;
+/// use icu_locid::locale;
+///
+/// let mut loc = locale!("heb-IL-u-ca-hebrew");
+/// assert!(canonicalize::langid(&mut loc.id));
+/// assert_eq!(loc, locale!("he-IL-u-ca-hebrew"));
+/// ```
+pub fn langid(langid: &mut LanguageIdentifier) -> bool {
+    language(langid) | deprecated(langid) | region(langid)
+}
+
+/// Optional steps of [`langid_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct CanonicalizeOptions {
+    /// Whether to remove the script subtag when it is the likely one,
+    /// see [`script`], such as `sr-Cyrl` to `sr`.
+    pub collapse_script: bool,
+}
+
+impl CanonicalizeOptions {
+    pub const fn new() -> Self {
+        Self {
+            collapse_script: false,
+        }
+    }
+
+    pub const fn collapse_script(mut self, collapse: bool) -> Self {
+        self.collapse_script = collapse;
+        self
+    }
+}
+
+/// Same as [`langid`], followed by the optional steps enabled in the options.
+///
+/// Returns `true` if the language identifier has been modified.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize::{self, CanonicalizeOptions};
+/// use icu_locid::langid;
+///
+/// let options = CanonicalizeOptions::new().collapse_script(true);
+///
+/// let mut langid = langid!("srp-Cyrl");
+/// assert!(canonicalize::langid_with(&mut langid, options));
+/// assert_eq!(langid, langid!("sr"));
+///
+/// let mut langid = langid!("sr-Latn");
+/// assert!(!canonicalize::langid_with(&mut langid, options));
+/// ```
+pub fn langid_with(langid: &mut LanguageIdentifier, options: CanonicalizeOptions) -> bool {
+    let mut modified = self::langid(langid);
+    if options.collapse_script {
+        modified |= script(langid);
+    }
+    modified
+}
+
+/// Parses a language identifier, replacing codes from ISO 639 and ISO 3166,
+/// as well as deprecated subtags, with their BCP47 equivalents.
+///
+/// Unlike the other functions in this module, this one also accepts
+/// alpha-3 region codes, which cannot be represented in
+/// a [`LanguageIdentifier`].
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::canonicalize;
+/// use icu_locid::langid;
+///
+/// assert_eq!(canonicalize::parse("ger-DEU").ok(), Some(langid!("de-DE")));
+/// assert_eq!(canonicalize::parse("sr_Latn_SRB").ok(), Some(langid!("sr-Latn-RS")));
+/// assert_eq!(canonicalize::parse("es-419").ok(), Some(langid!("es-419")));
+/// assert_eq!(canonicalize::parse("fre-840").ok(), Some(langid!("fr-US")));
+/// assert_eq!(canonicalize::parse("iw-IL").ok(), Some(langid!("he-IL")));
+/// ```
+pub fn parse<S: AsRef<[u8]>>(input: S) -> Result<LanguageIdentifier, Error> {
+    let subtags: Vec<&[u8]> = input.as_ref().split(|b| *b == b'-' || *b == b'_').collect();
+
+    // An alpha-3 region can only follow the language, or the language and the script.
+    let region_idx = match subtags.get(1) {
+        Some(s) if s.len() == 4 && s.iter().all(u8::is_ascii_alphabetic) => 2,
+        _ => 1,
+    };
+
+    let mut normalized = Vec::with_capacity(input.as_ref().len());
+    for (idx, subtag) in subtags.into_iter().enumerate() {
+        if idx > 0 {
+            normalized.push(b'-');
+        }
+        match region_from_alpha3(subtag) {
+            Some(region) if idx == region_idx => {
+                normalized.extend_from_slice(region.as_str().as_bytes())
+            }
+            _ => normalized.extend_from_slice(subtag),
+        }
+    }
+
+    let mut result = LanguageIdentifier::try_from_bytes(&normalized)
+        .map_err(|source| Error::parse(input.as_ref(), source))?;
+    langid(&mut result);
+    Ok(result)
+}
@@ -0,0 +1,42 @@
+//! A hook for language detectors, inferring requested locales from text.
+//!
+//! When neither an `Accept-Language` header nor the locales of the
+//! operating system are known, a sample of text written by the user, such
+//! as the first message of a chat, is often the only hint of their language.
+//! The crate doesn't detect languages itself; [`LanguageDetector`] is
+//! implemented by the application on top of the detector it uses, and the
+//! detected languages seed the requested locales through
+//! [`RequestedLocales::text_sample`](crate::negotiate::RequestedLocales::text_sample).
+//!
+//! The trait is implemented for closures, so a detector can be plugged in
+//! without a wrapper type.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::detect::LanguageDetector;
+//! use icu_locid::langid;
+//!
+//! let detector = |text: &str| {
+//!     if text.contains("Hallo") {
+//!         vec![langid!("de")]
+//!     } else {
+//!         vec![]
+//!     }
+//! };
+//! assert_eq!(detector.detect("Hallo, ich brauche Hilfe"), &[langid!("de")]);
+//! ```
+
+use icu_locid::LanguageIdentifier;
+
+pub trait LanguageDetector {
+    /// Returns the languages the text is likely written in, from the most
+    /// likely one, or an empty list if the text is too short to tell.
+    fn detect(&self, text: &str) -> Vec<LanguageIdentifier>;
+}
+
+impl<F: Fn(&str) -> Vec<LanguageIdentifier>> LanguageDetector for F {
+    fn detect(&self, text: &str) -> Vec<LanguageIdentifier> {
+        self(text)
+    }
+}
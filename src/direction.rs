@@ -0,0 +1,129 @@
+//! Direction of the text written in a given locale.
+//!
+//! UI layers usually need to know whether to lay out the interface
+//! right-to-left right after the negotiation picked a locale.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::is_rtl;
+//! use icu_locid::langid;
+//!
+//! assert!(is_rtl(&langid!("ar")));
+//! assert!(is_rtl(&langid!("he-IL")));
+//! assert!(is_rtl(&langid!("az-IR")));
+//! assert!(!is_rtl(&langid!("az")));
+//! assert!(!is_rtl(&langid!("pl")));
+//! ```
+
+use icu_locid::subtags::{language, script, Language, Script};
+use icu_locid::LanguageIdentifier;
+
+use crate::negotiate::LocaleExpander;
+
+/// Scripts written right-to-left, sorted.
+static RTL_SCRIPTS: &[Script] = &[
+    script!("Adlm"),
+    script!("Arab"),
+    script!("Armi"),
+    script!("Avst"),
+    script!("Chrs"),
+    script!("Cprt"),
+    script!("Elym"),
+    script!("Hatr"),
+    script!("Hebr"),
+    script!("Hung"),
+    script!("Khar"),
+    script!("Lydi"),
+    script!("Mand"),
+    script!("Mani"),
+    script!("Mend"),
+    script!("Merc"),
+    script!("Mero"),
+    script!("Narb"),
+    script!("Nbat"),
+    script!("Nkoo"),
+    script!("Orkh"),
+    script!("Ougr"),
+    script!("Palm"),
+    script!("Phli"),
+    script!("Phlp"),
+    script!("Phnx"),
+    script!("Prti"),
+    script!("Rohg"),
+    script!("Samr"),
+    script!("Sarb"),
+    script!("Sogd"),
+    script!("Sogo"),
+    script!("Syrc"),
+    script!("Thaa"),
+    script!("Yezi"),
+];
+
+/// Languages whose likely script is written right-to-left, sorted.
+///
+/// Used when the likely subtags data available cannot provide the script.
+static RTL_LANGUAGES: &[Language] = &[
+    language!("ar"),
+    language!("arc"),
+    language!("ckb"),
+    language!("dv"),
+    language!("fa"),
+    language!("he"),
+    language!("ks"),
+    language!("lrc"),
+    language!("mzn"),
+    language!("nqo"),
+    language!("ps"),
+    language!("sd"),
+    language!("syr"),
+    language!("ug"),
+    language!("ur"),
+    language!("yi"),
+];
+
+/// Returns `true` if the text in the given locale is written right-to-left.
+///
+/// If the language identifier doesn't specify the script, it is
+/// maximized using likely subtags first.
+pub fn is_rtl(langid: &LanguageIdentifier) -> bool {
+    let script = langid.script.or_else(|| {
+        let mut langid = langid.clone();
+        LocaleExpander::new().maximize(&mut langid);
+        langid.script
+    });
+
+    match script {
+        Some(script) => RTL_SCRIPTS.binary_search(&script).is_ok(),
+        None => RTL_LANGUAGES.binary_search(&langid.language).is_ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtl_scripts_sort() {
+        for v in RTL_SCRIPTS.windows(2) {
+            assert!(
+                v[0] < v[1],
+                "Script \"{}\" is placed after \"{}\"",
+                v[0],
+                v[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_rtl_languages_sort() {
+        for v in RTL_LANGUAGES.windows(2) {
+            assert!(
+                v[0] < v[1],
+                "Language \"{}\" is placed after \"{}\"",
+                v[0],
+                v[1]
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Human readable names of locales, based on CLDR data.
+//!
+//! Language pickers built on top of the negotiation result need to present
+//! the supported locales to the user, either in English, or in the language
+//! of the locale itself.
+//!
+//! This module is only available with the `display-names` feature, which is
+//! experimental, as it is built on the unstable `icu_experimental` crate.
+//!
+//! The formatter of each display locale is created on first use, and kept
+//! for the following calls on the same thread.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::display_names::{display_name, native_display_name};
+//! use icu_locid::langid;
+//!
+//! assert_eq!(display_name(&langid!("de-AT")), "German (Austria)");
+//! assert_eq!(display_name(&langid!("sr-Latn")), "Serbian (Latin)");
+//! assert_eq!(native_display_name(&langid!("de-AT")), "Deutsch (Österreich)");
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use icu_experimental::displaynames::{
+    DisplayNamesOptions, LanguageDisplay, LocaleDisplayNamesFormatter,
+};
+use icu_locid::{locale, LanguageIdentifier, Locale};
+
+thread_local! {
+    /// The formatters by display locale, `None` if there is no data for it.
+    static FORMATTERS: RefCell<HashMap<Locale, Option<LocaleDisplayNamesFormatter>>> =
+        RefCell::default();
+}
+
+fn new_formatter(in_locale: &Locale) -> Option<LocaleDisplayNamesFormatter> {
+    let mut options = DisplayNamesOptions::default();
+    options.language_display = LanguageDisplay::Standard;
+    LocaleDisplayNamesFormatter::try_new(&in_locale.into(), options).ok()
+}
+
+fn format(in_locale: &Locale, langid: &LanguageIdentifier) -> Option<String> {
+    let name =
+        |formatter: &LocaleDisplayNamesFormatter| formatter.of(&langid.clone().into()).into_owned();
+    FORMATTERS
+        .try_with(|formatters| {
+            let mut formatters = formatters.borrow_mut();
+            let formatter = formatters
+                .entry(in_locale.clone())
+                .or_insert_with(|| new_formatter(in_locale));
+            formatter.as_ref().map(name)
+        })
+        // During the destruction of the thread, the formatter isn't kept.
+        .unwrap_or_else(|_| new_formatter(in_locale).as_ref().map(name))
+}
+
+/// Returns the English name of the locale, such as `German (Austria)`.
+///
+/// Subtags without a known name are displayed as their code.
+pub fn display_name(langid: &LanguageIdentifier) -> String {
+    format(&locale!("en"), langid).unwrap_or_else(|| langid.to_string())
+}
+
+/// Returns the name of the locale in its own language, such as
+/// `Deutsch (Österreich)`.
+///
+/// Falls back on the English name if there's no data for the locale.
+pub fn native_display_name(langid: &LanguageIdentifier) -> String {
+    format(&langid.clone().into(), langid).unwrap_or_else(|| display_name(langid))
+}
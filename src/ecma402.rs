@@ -0,0 +1,539 @@
+//! Locale matchers following the abstract operations of ECMA-402.
+//!
+//! Implementations of `Intl` APIs need the behavior defined by the
+//! [ECMA-402](https://tc39.es/ecma402/#sec-abstract-operations) specification
+//! rather than the custom algorithm of this crate. [`lookup_matcher`]
+//! implements `LookupMatcher` exactly, while [`best_fit_matcher`] implements
+//! the implementation-defined `BestFitMatcher` with the `Lookup` strategy
+//! of this crate.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::ecma402::lookup_matcher;
+//! use icu_locid::{langid, locale};
+//!
+//! let available = [langid!("en"), langid!("de-AT"), langid!("zh-Hant")];
+//! let requested = [locale!("zh-Hant-TW-u-nu-hanidec")];
+//!
+//! let result = lookup_matcher(&requested, &available, &available[0]);
+//! assert_eq!(result.available, &available[2]);
+//! assert_eq!(result.to_locale(), locale!("zh-Hant-u-nu-hanidec"));
+//! ```
+//!
+//! [`IntlLocale`] mirrors the `Intl.Locale` object, for engines and
+//! polyfills backing it with this crate.
+//!
+//! [`resolve_locale`] follows `ResolveLocale`, resolving the Unicode
+//! extension keywords along with the locale, against the keyword values
+//! each available locale declares with [`KeywordAvailability`].
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use icu_locid::extensions::unicode::{key, Key, Unicode, Value};
+use icu_locid::{LanguageIdentifier, Locale};
+
+use crate::negotiate::{filter_matches, LocaleExpander, NegotiationStrategy};
+use crate::Error;
+
+/// Result of a locale matcher, the `[[locale]]` and `[[extension]]` fields
+/// of the record returned by the specification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatcherResult<'a, A> {
+    /// The matched available locale, or the default one.
+    pub available: &'a A,
+    /// The Unicode extension of the matched requested locale, if it had one.
+    pub extension: Option<Unicode>,
+}
+
+impl<A: AsRef<LanguageIdentifier>> MatcherResult<'_, A> {
+    /// Returns the matched available locale with the extension of
+    /// the requested locale.
+    pub fn to_locale(&self) -> Locale {
+        let mut locale = Locale::from(self.available.as_ref().clone());
+        if let Some(extension) = &self.extension {
+            locale.extensions.unicode = extension.clone();
+        }
+        locale
+    }
+}
+
+/// The `BestAvailableLocale` abstract operation.
+///
+/// Returns the available locale equal to the longest prefix of `locale`,
+/// truncated at subtag boundaries, ignoring a single character subtag
+/// preceding the truncation point.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::ecma402::best_available_locale;
+/// use icu_locid::langid;
+///
+/// let available = [langid!("de"), langid!("de-AT")];
+/// assert_eq!(best_available_locale(&available, "de-AT-1996"), Some(&available[1]));
+/// assert_eq!(best_available_locale(&available, "de-x-private"), Some(&available[0]));
+/// assert_eq!(best_available_locale(&available, "fr"), None);
+/// ```
+pub fn best_available_locale<'a, A: AsRef<LanguageIdentifier>>(
+    available: &'a [A],
+    locale: &str,
+) -> Option<&'a A> {
+    let mut candidate = locale;
+    loop {
+        let found = available
+            .iter()
+            .find(|a| a.as_ref().strict_cmp(candidate.as_bytes()) == Ordering::Equal);
+        if found.is_some() {
+            return found;
+        }
+        let mut pos = candidate.rfind('-')?;
+        if pos >= 2 && candidate.as_bytes()[pos - 2] == b'-' {
+            pos -= 2;
+        }
+        candidate = &candidate[..pos];
+    }
+}
+
+/// The `LookupMatcher` abstract operation.
+///
+/// Requested locales are matched in order, with their Unicode extensions
+/// removed, using [`best_available_locale`]. If none matches, `default` is
+/// returned without an extension.
+pub fn lookup_matcher<'a, A: AsRef<LanguageIdentifier>>(
+    requested: &[Locale],
+    available: &'a [A],
+    default: &'a A,
+) -> MatcherResult<'a, A> {
+    for locale in requested {
+        let mut no_extensions = locale.clone();
+        no_extensions.extensions.unicode.clear();
+        if let Some(found) = best_available_locale(available, &no_extensions.to_string()) {
+            return MatcherResult {
+                available: found,
+                extension: unicode_extension(locale),
+            };
+        }
+    }
+    MatcherResult {
+        available: default,
+        extension: None,
+    }
+}
+
+/// The `BestFitMatcher` abstract operation.
+///
+/// Requested locales are matched in order using the `Lookup` strategy of
+/// [`negotiate_languages`](crate::negotiate_languages), so a requested `en-GB`
+/// can match an available `en-US`, unlike with [`lookup_matcher`].
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::ecma402::{best_fit_matcher, lookup_matcher};
+/// use icu_locid::{langid, locale};
+///
+/// let available = [langid!("fr"), langid!("en-US")];
+/// let requested = [locale!("en-GB-u-hc-h23")];
+///
+/// assert_eq!(lookup_matcher(&requested, &available, &available[0]).available, &available[0]);
+///
+/// let result = best_fit_matcher(&requested, &available, &available[0]);
+/// assert_eq!(result.to_locale(), locale!("en-US-u-hc-h23"));
+/// ```
+pub fn best_fit_matcher<'a, A: AsRef<LanguageIdentifier>>(
+    requested: &[Locale],
+    available: &'a [A],
+    default: &'a A,
+) -> MatcherResult<'a, A> {
+    for locale in requested {
+        let found = filter_matches(
+            std::slice::from_ref(&locale.id),
+            available,
+            NegotiationStrategy::Lookup,
+        );
+        if let Some(found) = found.first() {
+            return MatcherResult {
+                available: found,
+                extension: unicode_extension(locale),
+            };
+        }
+    }
+    MatcherResult {
+        available: default,
+        extension: None,
+    }
+}
+
+fn unicode_extension(locale: &Locale) -> Option<Unicode> {
+    (!locale.extensions.unicode.is_empty()).then(|| locale.extensions.unicode.clone())
+}
+
+/// The values of Unicode extension keywords an available locale supports,
+/// the `[[LocaleData]]` of the specification.
+///
+/// The first value declared for a key is the default one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordAvailability {
+    pub langid: LanguageIdentifier,
+    keywords: Vec<(Key, Vec<Value>)>,
+}
+
+impl KeywordAvailability {
+    pub fn new(langid: LanguageIdentifier) -> Self {
+        Self {
+            langid,
+            keywords: Vec::new(),
+        }
+    }
+
+    /// Declares the supported values of a key, starting with the default.
+    ///
+    /// A key without values is ignored.
+    pub fn with(mut self, key: Key, values: impl IntoIterator<Item = Value>) -> Self {
+        let values: Vec<Value> = values.into_iter().collect();
+        if !values.is_empty() {
+            self.keywords.retain(|(k, _)| *k != key);
+            self.keywords.push((key, values));
+        }
+        self
+    }
+
+    /// Returns the supported values of a key, starting with the default.
+    pub fn values(&self, key: Key) -> &[Value] {
+        self.keywords
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map_or(&[], |(_, values)| values)
+    }
+
+    pub fn supports(&self, key: Key, value: &Value) -> bool {
+        self.values(key).contains(value)
+    }
+
+    /// The number of keywords of the locale this locale supports.
+    fn supported_count(&self, locale: &Locale) -> usize {
+        self.keywords
+            .iter()
+            .filter(|(key, values)| {
+                locale
+                    .extensions
+                    .unicode
+                    .keywords
+                    .get(key)
+                    .is_some_and(|value| values.contains(value))
+            })
+            .count()
+    }
+}
+
+impl AsRef<LanguageIdentifier> for KeywordAvailability {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        &self.langid
+    }
+}
+
+/// A keyword resolved by [`resolve_locale`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedKeyword {
+    pub key: Key,
+    pub value: Value,
+    /// Whether the value comes from the requested locale, rather than being
+    /// the default value of the available locale.
+    pub requested: bool,
+}
+
+/// Result of [`resolve_locale`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLocale<'a> {
+    /// The matched available locale, or the default one.
+    pub available: &'a KeywordAvailability,
+    /// A value for every key the available locale declares, in the order
+    /// of the declarations.
+    pub keywords: Vec<ResolvedKeyword>,
+}
+
+impl ResolvedLocale<'_> {
+    /// Returns the resolved value of a key.
+    pub fn get(&self, key: Key) -> Option<&Value> {
+        self.keywords
+            .iter()
+            .find(|k| k.key == key)
+            .map(|k| &k.value)
+    }
+
+    /// Returns the matched available locale with the keywords taken from
+    /// the requested locale, the `[[locale]]` of the specification.
+    pub fn to_locale(&self) -> Locale {
+        let mut locale = Locale::from(self.available.langid.clone());
+        for keyword in self.keywords.iter().filter(|k| k.requested) {
+            locale
+                .extensions
+                .unicode
+                .keywords
+                .set(keyword.key, keyword.value.clone());
+        }
+        locale
+    }
+}
+
+/// The `ResolveLocale` abstract operation, resolving the locale and its
+/// Unicode extension keywords jointly.
+///
+/// Requested locales are matched in order like [`best_fit_matcher`]. Among
+/// the available locales matching the same requested locale, the one
+/// supporting the most of its keywords is picked, the order of negotiation
+/// deciding between equal ones. Every key declared by the picked locale is
+/// then resolved to the requested value if it is supported, or to the
+/// default value otherwise.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::ecma402::{resolve_locale, KeywordAvailability};
+/// use icu_locid::extensions::unicode::{key, value};
+/// use icu_locid::{langid, locale};
+///
+/// let available = [
+///     KeywordAvailability::new(langid!("en")).with(key!("nu"), [value!("latn")]),
+///     KeywordAvailability::new(langid!("ar-EG"))
+///         .with(key!("nu"), [value!("arab")])
+///         .with(key!("ca"), [value!("gregory"), value!("islamic")]),
+///     KeywordAvailability::new(langid!("ar-SA"))
+///         .with(key!("nu"), [value!("arab"), value!("latn")])
+///         .with(key!("ca"), [value!("islamic"), value!("gregory")]),
+/// ];
+///
+/// // ar-SA is the only Arabic locale supporting Latin digits.
+/// let result = resolve_locale(&[locale!("ar-u-nu-latn")], &available, &available[0]);
+/// assert_eq!(result.to_locale(), locale!("ar-SA-u-nu-latn"));
+/// assert_eq!(result.get(key!("ca")), Some(&value!("islamic")));
+///
+/// // The unsupported value is replaced with the default one.
+/// let result = resolve_locale(&[locale!("ar-EG-u-nu-thai")], &available, &available[0]);
+/// assert_eq!(result.to_locale(), locale!("ar-EG"));
+/// assert_eq!(result.get(key!("nu")), Some(&value!("arab")));
+/// ```
+pub fn resolve_locale<'a>(
+    requested: &[Locale],
+    available: &'a [KeywordAvailability],
+    default: &'a KeywordAvailability,
+) -> ResolvedLocale<'a> {
+    for locale in requested {
+        let found = filter_matches(
+            std::slice::from_ref(&locale.id),
+            available,
+            NegotiationStrategy::Filtering,
+        );
+        let mut best: Option<(&KeywordAvailability, usize)> = None;
+        for candidate in found {
+            let count = candidate.supported_count(locale);
+            if best.is_none_or(|(_, best_count)| count > best_count) {
+                best = Some((candidate, count));
+            }
+        }
+        if let Some((found, _)) = best {
+            return resolve_keywords(found, Some(locale));
+        }
+    }
+    resolve_keywords(default, None)
+}
+
+fn resolve_keywords<'a>(
+    available: &'a KeywordAvailability,
+    requested: Option<&Locale>,
+) -> ResolvedLocale<'a> {
+    let keywords = available
+        .keywords
+        .iter()
+        .map(|(key, values)| {
+            let value = requested
+                .and_then(|locale| locale.extensions.unicode.keywords.get(key))
+                .filter(|value| values.contains(value));
+            ResolvedKeyword {
+                key: *key,
+                value: value.unwrap_or(&values[0]).clone(),
+                requested: value.is_some(),
+            }
+        })
+        .collect();
+    ResolvedLocale {
+        available,
+        keywords,
+    }
+}
+
+/// The `hourCycle` of an [`IntlLocale`], from the `hc` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HourCycle {
+    H11,
+    H12,
+    H23,
+    H24,
+}
+
+/// The `caseFirst` of an [`IntlLocale`], from the `kf` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseFirst {
+    Upper,
+    Lower,
+    False,
+}
+
+/// A locale with the accessors of the `Intl.Locale` object.
+///
+/// Keyword getters return `None` when the keyword is missing, as well as
+/// when its value isn't one `Intl.Locale` reports.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::ecma402::{HourCycle, IntlLocale};
+///
+/// let locale: IntlLocale = "en-u-hc-h23-kn".parse().unwrap();
+/// assert_eq!(locale.base_name(), "en");
+/// assert_eq!(locale.hour_cycle(), Some(HourCycle::H23));
+/// assert!(locale.numeric());
+/// assert_eq!(locale.calendar(), None);
+///
+/// let maximized = locale.maximize();
+/// assert_eq!(maximized.to_string(), "en-Latn-US-u-hc-h23-kn");
+/// assert_eq!(maximized.minimize().to_string(), "en-u-hc-h23-kn");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntlLocale(Locale);
+
+impl IntlLocale {
+    pub fn new(locale: Locale) -> Self {
+        Self(locale)
+    }
+
+    pub fn as_locale(&self) -> &Locale {
+        &self.0
+    }
+
+    pub fn into_locale(self) -> Locale {
+        self.0
+    }
+
+    /// The `maximize()` method, adding likely subtags.
+    ///
+    /// Without the `cldr` feature, only the most used languages are
+    /// covered, see [`LocaleExpander`].
+    pub fn maximize(&self) -> Self {
+        let mut locale = self.0.clone();
+        LocaleExpander::new().maximize(&mut locale);
+        Self(locale)
+    }
+
+    /// The `minimize()` method, removing likely subtags.
+    ///
+    /// Without the `cldr` feature, only the most used languages are
+    /// covered, see [`LocaleExpander`].
+    pub fn minimize(&self) -> Self {
+        let mut locale = self.0.clone();
+        LocaleExpander::new().minimize(&mut locale);
+        Self(locale)
+    }
+
+    /// The `baseName` property, the locale without its extensions.
+    pub fn base_name(&self) -> String {
+        self.0.id.to_string()
+    }
+
+    pub fn language(&self) -> &str {
+        self.0.id.language.as_str()
+    }
+
+    pub fn script(&self) -> Option<&str> {
+        self.0.id.script.as_ref().map(|s| s.as_str())
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.0.id.region.as_ref().map(|r| r.as_str())
+    }
+
+    /// The `calendar` property, from the `ca` keyword.
+    pub fn calendar(&self) -> Option<String> {
+        self.keyword(key!("ca")).map(|v| v.to_string())
+    }
+
+    /// The `collation` property, from the `co` keyword.
+    pub fn collation(&self) -> Option<String> {
+        self.keyword(key!("co")).map(|v| v.to_string())
+    }
+
+    /// The `numberingSystem` property, from the `nu` keyword.
+    pub fn numbering_system(&self) -> Option<String> {
+        self.keyword(key!("nu")).map(|v| v.to_string())
+    }
+
+    /// The `hourCycle` property, from the `hc` keyword.
+    pub fn hour_cycle(&self) -> Option<HourCycle> {
+        match self.keyword(key!("hc"))?.to_string().as_str() {
+            "h11" => Some(HourCycle::H11),
+            "h12" => Some(HourCycle::H12),
+            "h23" => Some(HourCycle::H23),
+            "h24" => Some(HourCycle::H24),
+            _ => None,
+        }
+    }
+
+    /// The `caseFirst` property, from the `kf` keyword.
+    pub fn case_first(&self) -> Option<CaseFirst> {
+        match self.keyword(key!("kf"))?.to_string().as_str() {
+            "upper" => Some(CaseFirst::Upper),
+            "lower" => Some(CaseFirst::Lower),
+            "false" => Some(CaseFirst::False),
+            _ => None,
+        }
+    }
+
+    /// The `numeric` property, `true` if the `kn` keyword is present with
+    /// no value or with `true`.
+    pub fn numeric(&self) -> bool {
+        self.keyword(key!("kn"))
+            .is_some_and(|v| v.to_string() == "true" || *v == Value::default())
+    }
+
+    fn keyword(&self, key: Key) -> Option<&Value> {
+        self.0.extensions.unicode.keywords.get(&key)
+    }
+}
+
+impl From<Locale> for IntlLocale {
+    fn from(locale: Locale) -> Self {
+        Self(locale)
+    }
+}
+
+impl From<IntlLocale> for Locale {
+    fn from(locale: IntlLocale) -> Self {
+        locale.0
+    }
+}
+
+impl AsRef<LanguageIdentifier> for IntlLocale {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        &self.0.id
+    }
+}
+
+impl FromStr for IntlLocale {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(Self)
+            .map_err(|source| Error::parse(s.as_bytes(), source))
+    }
+}
+
+/// The `toString()` method.
+impl fmt::Display for IntlLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
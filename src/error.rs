@@ -0,0 +1,148 @@
+//! The error type of the crate.
+//!
+//! All the fallible functions of the crate return [`Error`], rather than the
+//! error of the library they wrap, such as [`ParserError`] or
+//! [`std::io::Error`], which lack the context needed to act on them. It keeps
+//! that context, such as the input and the byte offset of the subtag which
+//! failed to parse, with the wrapped error as its source, so that an
+//! application can use it with `?` throughout.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use icu_locid::{LanguageIdentifier, ParserError};
+
+use crate::ConvertError;
+
+/// An error of any of the fallible operations of the crate.
+///
+/// New variants may be added in the future, so matches over it need
+/// a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A language identifier or range failed to parse, see [`crate::parse_langid`].
+    Parse {
+        input: String,
+        /// Byte offset of the first invalid subtag in the input.
+        offset: usize,
+        source: ParserError,
+    },
+    /// A language tag of an `Accept-Language` header failed to parse,
+    /// see [`crate::accepted_languages::try_parse`].
+    #[cfg(feature = "accept-language")]
+    Header {
+        header: String,
+        /// Byte offset of the first invalid subtag in the header.
+        offset: usize,
+        source: ParserError,
+    },
+    /// An entry of a list failed to parse, see [`crate::convert_vec_str_to_langids`].
+    Convert(ConvertError),
+    /// The available locales failed to load from the filesystem.
+    Io { path: PathBuf, source: io::Error },
+    /// The available locales failed to be watched.
+    #[cfg(feature = "watch")]
+    Watch(notify::Error),
+    /// A `unic_langid` language identifier failed to parse.
+    #[cfg(feature = "unic-langid")]
+    Unic(unic_langid::LanguageIdentifierError),
+}
+
+impl Error {
+    pub(crate) fn parse(input: &[u8], source: ParserError) -> Self {
+        Self::Parse {
+            input: String::from_utf8_lossy(input).into_owned(),
+            offset: invalid_subtag_offset(input),
+            source,
+        }
+    }
+}
+
+/// Returns the byte offset of the first subtag which makes the input invalid.
+fn invalid_subtag_offset(input: &[u8]) -> usize {
+    let mut start = 0;
+    for (end, &byte) in input.iter().enumerate() {
+        if byte == b'-' || byte == b'_' {
+            if LanguageIdentifier::try_from_bytes(&input[..end]).is_err() {
+                return start;
+            }
+            start = end + 1;
+        }
+    }
+    start
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse {
+                input,
+                offset,
+                source,
+            } => write!(
+                f,
+                "Failed to parse language identifier \"{}\" at byte {}: {}",
+                input, offset, source
+            ),
+            #[cfg(feature = "accept-language")]
+            Self::Header {
+                header,
+                offset,
+                source,
+            } => write!(
+                f,
+                "Failed to parse Accept-Language header \"{}\" at byte {}: {}",
+                header, offset, source
+            ),
+            Self::Convert(err) => err.fmt(f),
+            Self::Io { path, source } => write!(
+                f,
+                "Failed to read available locales from \"{}\": {}",
+                path.display(),
+                source
+            ),
+            #[cfg(feature = "watch")]
+            Self::Watch(err) => write!(f, "Failed to watch available locales: {}", err),
+            #[cfg(feature = "unic-langid")]
+            Self::Unic(err) => write!(f, "Failed to parse language identifier: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse { source, .. } => Some(source),
+            #[cfg(feature = "accept-language")]
+            Self::Header { source, .. } => Some(source),
+            Self::Convert(err) => Some(err),
+            Self::Io { source, .. } => Some(source),
+            #[cfg(feature = "watch")]
+            Self::Watch(err) => Some(err),
+            #[cfg(feature = "unic-langid")]
+            Self::Unic(err) => Some(err),
+        }
+    }
+}
+
+impl From<ConvertError> for Error {
+    fn from(err: ConvertError) -> Self {
+        Self::Convert(err)
+    }
+}
+
+#[cfg(feature = "watch")]
+impl From<notify::Error> for Error {
+    fn from(err: notify::Error) -> Self {
+        Self::Watch(err)
+    }
+}
+
+#[cfg(feature = "unic-langid")]
+impl From<unic_langid::LanguageIdentifierError> for Error {
+    fn from(err: unic_langid::LanguageIdentifierError) -> Self {
+        Self::Unic(err)
+    }
+}
@@ -0,0 +1,80 @@
+//! Method-call helpers on [`LanguageIdentifier`].
+//!
+//! The language identifier type belongs to `icu_locid`, so the helpers of
+//! this crate operating on a single identifier are provided by the
+//! [`LangIdExt`] extension trait, also exported by the
+//! [`prelude`](crate::prelude).
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::ext::LangIdExt;
+//! use icu_locid::langid;
+//!
+//! let langid = langid!("sr-Cyrl-RS");
+//! assert!(langid.matches_range("sr-*-RS"));
+//! assert_eq!(langid.parent(), Some(langid!("sr-Cyrl")));
+//! assert_eq!(langid.minimized(), langid!("sr"));
+//! assert_eq!(
+//!     langid.fallbacks().collect::<Vec<_>>(),
+//!     &[langid!("sr-Cyrl-RS"), langid!("sr-Cyrl"), langid!("sr")]
+//! );
+//! ```
+
+use icu_locid::LanguageIdentifier;
+
+use crate::negotiate::{FallbackChain, LanguageRange, LocaleExpander};
+use crate::subtags;
+
+pub trait LangIdExt {
+    /// Returns `true` if the identifier is within the range, such as `es`
+    /// or `*-Cyrl`, see [`LanguageRange`]. A range which fails to parse
+    /// matches nothing.
+    fn matches_range(&self, range: &str) -> bool;
+
+    /// Returns the identifier with likely subtags added.
+    fn maximized(&self) -> LanguageIdentifier;
+
+    /// Returns the identifier with likely subtags removed.
+    ///
+    /// Identifiers with an `und` language are returned as is.
+    fn minimized(&self) -> LanguageIdentifier;
+
+    /// Returns the identifier with its least significant subtag removed,
+    /// see [`subtags::truncate`].
+    fn parent(&self) -> Option<LanguageIdentifier>;
+
+    /// Returns the fallback chain of the identifier, see [`FallbackChain`].
+    fn fallbacks(&self) -> FallbackChain;
+}
+
+impl LangIdExt for LanguageIdentifier {
+    fn matches_range(&self, range: &str) -> bool {
+        range
+            .parse::<LanguageRange>()
+            .is_ok_and(|range| range.matches(self))
+    }
+
+    fn maximized(&self) -> LanguageIdentifier {
+        let mut langid = self.clone();
+        LocaleExpander::new().maximize(&mut langid);
+        langid
+    }
+
+    fn minimized(&self) -> LanguageIdentifier {
+        let mut langid = self.clone();
+        // Likely subtags would turn an unknown language into `en`.
+        if !langid.language.is_empty() {
+            LocaleExpander::new().minimize(&mut langid);
+        }
+        langid
+    }
+
+    fn parent(&self) -> Option<LanguageIdentifier> {
+        subtags::truncate(self)
+    }
+
+    fn fallbacks(&self) -> FallbackChain {
+        FallbackChain::new(self.clone())
+    }
+}
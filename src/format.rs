@@ -0,0 +1,112 @@
+//! Spelling of language identifiers for other systems.
+//!
+//! [`LanguageIdentifier`] serializes into the canonical BCP47 form, such as
+//! `en-Latn-US`, but systems consuming the negotiated locales often expect
+//! another spelling: lowercase tags in URLs, or underscores in Java
+//! properties files and POSIX locale directories.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::format::TagFormat;
+//! use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+//! use icu_locid::langid;
+//!
+//! let available = [langid!("en-US"), langid!("sr-Latn")];
+//! let supported = negotiate_languages(
+//!     &[langid!("sr-Latn-RS"), langid!("en")],
+//!     &available,
+//!     None,
+//!     NegotiationStrategy::Filtering,
+//! );
+//!
+//! let dirs: Vec<String> = supported
+//!     .iter()
+//!     .map(|langid| TagFormat::Underscore.format(langid))
+//!     .collect();
+//! assert_eq!(dirs, &["sr_Latn", "en_US"]);
+//! ```
+
+use std::fmt::{self, Write};
+
+use icu_locid::LanguageIdentifier;
+
+/// Spelling of a language identifier.
+///
+/// New formats may be added in the future, so matches over it need
+/// a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum TagFormat {
+    /// The canonical BCP47 form, such as `en-Latn-US`.
+    #[default]
+    Canonical,
+    /// All lowercase, such as `en-latn-us`.
+    Lowercase,
+    /// The canonical case, separated by underscores, such as `en_Latn_US`.
+    Underscore,
+}
+
+impl TagFormat {
+    pub fn format(self, langid: &LanguageIdentifier) -> String {
+        self.display(langid).to_string()
+    }
+
+    /// Returns a value displaying the language identifier in this format,
+    /// without allocating.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use fluent_langneg::format::TagFormat;
+    /// use icu_locid::langid;
+    ///
+    /// let langid = langid!("de-Latn-AT-1996");
+    /// let url = format!("/{}/docs", TagFormat::Lowercase.display(&langid));
+    /// assert_eq!(url, "/de-latn-at-1996/docs");
+    /// ```
+    pub fn display(self, langid: &LanguageIdentifier) -> Formatted<'_> {
+        Formatted {
+            langid,
+            format: self,
+        }
+    }
+}
+
+/// A language identifier displayed in a [`TagFormat`].
+#[derive(Debug, Clone, Copy)]
+pub struct Formatted<'a> {
+    langid: &'a LanguageIdentifier,
+    format: TagFormat,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = match self.format {
+            TagFormat::Underscore => '_',
+            TagFormat::Canonical | TagFormat::Lowercase => '-',
+        };
+        let langid = self.langid;
+        let subtags = [langid.language.as_str()]
+            .into_iter()
+            .chain(langid.script.as_ref().map(|s| s.as_str()))
+            .chain(langid.region.as_ref().map(|r| r.as_str()))
+            .chain(langid.variants.iter().map(|v| v.as_str()));
+        for (i, subtag) in subtags.enumerate() {
+            if i > 0 {
+                f.write_char(separator)?;
+            }
+            if self.format == TagFormat::Lowercase {
+                for c in subtag.chars() {
+                    f.write_char(c.to_ascii_lowercase())?;
+                }
+            } else {
+                f.write_str(subtag)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Discovery of available locales on the filesystem.
+//!
+//! Localization resources are usually laid out either as one directory per
+//! locale, such as `locales/en-US/main.ftl`, or as one file per locale, such
+//! as `locales/en-US.ftl`. The functions of this module list such a directory
+//! and parse the names of its entries into the available locales.
+//!
+//! Entries whose names start with a `.` are skipped silently, and entries
+//! whose names don't parse are skipped and reported to the `rejected`
+//! callback, like in [`convert_vec_str_to_langids_lossy_with`](crate::convert_vec_str_to_langids_lossy_with).
+//!
+//! The locales are returned sorted by the name of their entry, so that
+//! the result doesn't depend on the order of the directory listing.
+//!
+//! With the `watch` feature, [`LocaleWatcher`] keeps the result up to date
+//! as locales are deployed or removed.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! use fluent_langneg::fs::discover_locale_dirs;
+//! use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+//! use icu_locid::langid;
+//!
+//! let available = discover_locale_dirs("./locales", |err| {
+//!     eprintln!("Skipping {}", err.input);
+//! })?;
+//!
+//! let supported = negotiate_languages(
+//!     &[langid!("de-AT")],
+//!     &available,
+//!     None,
+//!     NegotiationStrategy::Filtering,
+//! );
+//! # Ok::<(), fluent_langneg::Error>(())
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use icu_locid::LanguageIdentifier;
+
+use crate::{parse_entry, ConvertError, Error};
+
+#[cfg(feature = "watch")]
+mod watcher;
+#[cfg(feature = "watch")]
+pub use watcher::LocaleWatcher;
+
+/// Returns the locales named by the subdirectories of `path`.
+///
+/// The `index` of a rejected entry is its position among the sorted
+/// subdirectories.
+pub fn discover_locale_dirs<P, F>(path: P, rejected: F) -> Result<Vec<LanguageIdentifier>, Error>
+where
+    P: AsRef<Path>,
+    F: FnMut(ConvertError),
+{
+    discover(path.as_ref(), rejected, |entry, name| {
+        Ok(entry.file_type()?.is_dir().then_some(name))
+    })
+}
+
+/// Returns the locales named by the files of `path` with the `extension`,
+/// such as `ftl` for `en-US.ftl`.
+///
+/// The `index` of a rejected entry is its position among the sorted
+/// files with the extension.
+pub fn discover_locale_files<P, F>(
+    path: P,
+    extension: &str,
+    rejected: F,
+) -> Result<Vec<LanguageIdentifier>, Error>
+where
+    P: AsRef<Path>,
+    F: FnMut(ConvertError),
+{
+    discover(path.as_ref(), rejected, |entry, name| {
+        if !entry.file_type()?.is_file() {
+            return Ok(None);
+        }
+        let stem = name
+            .strip_suffix(extension)
+            .and_then(|stem| stem.strip_suffix('.'));
+        Ok(stem.map(str::to_string))
+    })
+}
+
+fn discover<F, N>(path: &Path, mut rejected: F, name: N) -> Result<Vec<LanguageIdentifier>, Error>
+where
+    F: FnMut(ConvertError),
+    N: Fn(&fs::DirEntry, String) -> io::Result<Option<String>>,
+{
+    let list = || -> io::Result<Vec<String>> {
+        let mut names = vec![];
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if file_name.starts_with('.') {
+                continue;
+            }
+            if let Some(name) = name(&entry, file_name)? {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    };
+    let mut names = list().map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    names.sort();
+
+    Ok(names
+        .iter()
+        .enumerate()
+        .filter_map(|(index, name)| {
+            parse_entry(index, name.as_bytes())
+                .map_err(&mut rejected)
+                .ok()
+        })
+        .collect())
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use icu_locid::LanguageIdentifier;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::{discover_locale_dirs, discover_locale_files};
+use crate::Error;
+
+type Scan = dyn Fn(&Path) -> Result<Vec<LanguageIdentifier>, Error> + Send + Sync;
+type Callback = dyn FnMut(&[LanguageIdentifier]) + Send;
+
+/// Keeps the available locales of a directory up to date.
+///
+/// The directory is scanned once when the watcher is created, and again
+/// whenever an entry in it is created, removed or renamed. If the available
+/// locales changed, the new list replaces the one returned by
+/// [`LocaleWatcher::available`], and is passed to the callback.
+///
+/// Since [`AvailableLocales`](crate::negotiate::AvailableLocales) borrows
+/// the available locales, a prepared set is rebuilt from the new list,
+/// for example in the callback or when the list returned by
+/// [`LocaleWatcher::available`] is a different one.
+///
+/// The directory is watched for as long as the watcher is alive.
+///
+/// # Example:
+///
+/// ```no_run
+/// use fluent_langneg::fs::LocaleWatcher;
+/// use fluent_langneg::negotiate::AvailableLocales;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let watcher = LocaleWatcher::watch_dirs("./locales", |available| {
+///     println!("Available locales changed: {:?}", available);
+/// })?;
+///
+/// // On each request, negotiate against the current list.
+/// let available = watcher.available();
+/// let prepared = AvailableLocales::new(&available);
+/// let supported = prepared.negotiate_languages(
+///     &[langid!("de-AT")],
+///     None,
+///     NegotiationStrategy::Filtering,
+/// );
+/// # Ok::<(), fluent_langneg::Error>(())
+/// ```
+pub struct LocaleWatcher {
+    available: Arc<RwLock<Arc<[LanguageIdentifier]>>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl LocaleWatcher {
+    /// Watches a directory with one subdirectory per locale,
+    /// see [`discover_locale_dirs`].
+    pub fn watch_dirs<P, F>(path: P, on_change: F) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&[LanguageIdentifier]) + Send + 'static,
+    {
+        Self::new(
+            path.as_ref(),
+            Box::new(|path| discover_locale_dirs(path, |_| {})),
+            Box::new(on_change),
+        )
+    }
+
+    /// Watches a directory with one file per locale,
+    /// see [`discover_locale_files`].
+    pub fn watch_files<P, F>(path: P, extension: &str, on_change: F) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&[LanguageIdentifier]) + Send + 'static,
+    {
+        let extension = extension.to_string();
+        Self::new(
+            path.as_ref(),
+            Box::new(move |path| discover_locale_files(path, &extension, |_| {})),
+            Box::new(on_change),
+        )
+    }
+
+    fn new(path: &Path, scan: Box<Scan>, on_change: Box<Callback>) -> Result<Self, Error> {
+        let path: PathBuf = path.to_path_buf();
+        let initial: Arc<[LanguageIdentifier]> = scan(&path)?.into();
+        let available = Arc::new(RwLock::new(initial));
+
+        let state = Arc::clone(&available);
+        let root = path.clone();
+        let on_change = Mutex::new(on_change);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let relevant = match event {
+                    Ok(event) => !matches!(event.kind, EventKind::Access(_)),
+                    Err(_) => true,
+                };
+                // A failed scan, for example of a removed directory,
+                // keeps the previous list.
+                let Some(locales) = relevant.then(|| scan(&root).ok()).flatten() else {
+                    return;
+                };
+                {
+                    let mut current = state.write().unwrap_or_else(|e| e.into_inner());
+                    if **current == *locales {
+                        return;
+                    }
+                    *current = locales.as_slice().into();
+                }
+                let mut on_change = on_change.lock().unwrap_or_else(|e| e.into_inner());
+                on_change(&locales);
+            })?;
+        watcher.watch(&path, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            available,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the current available locales.
+    pub fn available(&self) -> Arc<[LanguageIdentifier]> {
+        Arc::clone(&self.available.read().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl fmt::Debug for LocaleWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocaleWatcher")
+            .field("available", &self.available())
+            .finish()
+    }
+}
@@ -0,0 +1,104 @@
+//! Values of the HTML `lang` and `hreflang` attributes.
+//!
+//! Per the W3C guidance on language tags in HTML, the tag should be as short
+//! as possible, so subtags implied by the others, such as `Latn` in
+//! `en-Latn-US` or `DE` in `de-DE`, are left out. Likely subtags are
+//! removed with [`LocaleExpander::minimize`](crate::negotiate::LocaleExpander::minimize).
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::html::{hreflang_alternates, to_html_lang};
+//! use icu_locid::langid;
+//!
+//! assert_eq!(to_html_lang(&langid!("de-Latn-DE")), "de");
+//! assert_eq!(to_html_lang(&langid!("sr-Latn-RS")), "sr-Latn");
+//!
+//! let available = [langid!("en-US"), langid!("en-GB"), langid!("fr-FR")];
+//! let links: Vec<_> = hreflang_alternates(&available, Some(&available[0]))
+//!     .into_iter()
+//!     .map(|alternate| alternate.hreflang)
+//!     .collect();
+//! assert_eq!(links, &["en", "en-GB", "fr", "x-default"]);
+//! ```
+
+use icu_locid::{LanguageIdentifier, ParserError};
+
+use crate::ext::LangIdExt;
+use crate::Error;
+
+/// Returns the shortest tag of the language identifier, for the `lang`
+/// attribute.
+///
+/// Language identifiers with an `und` language are left as is.
+pub fn to_html_lang(langid: &LanguageIdentifier) -> String {
+    langid.minimized().to_string()
+}
+
+/// Parses the value of a `lang` or `hreflang` attribute.
+///
+/// Unlike [`crate::parse_langid`], underscores are rejected, since only
+/// hyphens separate the subtags of a BCP47 language tag.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::html::parse_html_lang;
+/// use fluent_langneg::Error;
+///
+/// assert!(parse_html_lang("pt-BR").is_ok());
+/// assert!(matches!(parse_html_lang("pt_BR"), Err(Error::Parse { offset: 2, .. })));
+/// assert!(parse_html_lang("").is_err());
+/// ```
+pub fn parse_html_lang(value: &str) -> Result<LanguageIdentifier, Error> {
+    if let Some(offset) = value.find('_') {
+        return Err(Error::Parse {
+            input: value.to_string(),
+            offset,
+            source: ParserError::InvalidSubtag,
+        });
+    }
+    crate::parse_langid(value)
+}
+
+/// An alternate version of a page, for a `<link rel="alternate">` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternate<'a, A> {
+    /// The value of the `hreflang` attribute.
+    pub hreflang: String,
+    /// The available locale of the page.
+    pub locale: &'a A,
+}
+
+/// Returns the alternates of a page available in each of the `available`
+/// locales, followed by an `x-default` one for the `default` locale.
+///
+/// The `hreflang` values are minimized like [`to_html_lang`], unless that
+/// would make two of them equal, such as for `en` and `en-US`.
+pub fn hreflang_alternates<'a, A: AsRef<LanguageIdentifier>>(
+    available: &'a [A],
+    default: Option<&'a A>,
+) -> Vec<Alternate<'a, A>> {
+    let minimized: Vec<LanguageIdentifier> =
+        available.iter().map(|a| a.as_ref().minimized()).collect();
+    let mut alternates: Vec<Alternate<'a, A>> = available
+        .iter()
+        .zip(&minimized)
+        .map(|(locale, short)| {
+            let ambiguous = minimized.iter().filter(|other| *other == short).count() > 1;
+            let hreflang = if ambiguous {
+                locale.as_ref().to_string()
+            } else {
+                short.to_string()
+            };
+            Alternate { hreflang, locale }
+        })
+        .collect();
+    if let Some(locale) = default {
+        alternates.push(Alternate {
+            hreflang: "x-default".to_string(),
+            locale,
+        });
+    }
+    alternates
+}
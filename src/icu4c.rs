@@ -0,0 +1,97 @@
+//! Compatibility with the language negotiation of ICU4C.
+//!
+//! Services migrating from ICU4C can use [`accept_language`] to check that
+//! their results stay the same, before switching to the negotiation
+//! strategies of this crate.
+//!
+//! The function follows the classic `uloc_acceptLanguage` algorithm, which
+//! doesn't use likely subtags, and only falls back from a requested locale
+//! to its parents.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::convert_vec_str_to_langids_lossy;
+//! use fluent_langneg::icu4c::{accept_language, AcceptResult};
+//! use icu_locid::langid;
+//!
+//! let requested = convert_vec_str_to_langids_lossy(["de-AT", "en-GB"]);
+//! let available = [langid!("en"), langid!("de"), langid!("en-GB")];
+//!
+//! assert_eq!(
+//!     accept_language(&requested, &available),
+//!     (Some(&available[2]), AcceptResult::Valid)
+//! );
+//! assert_eq!(
+//!     accept_language(&requested[..1], &available),
+//!     (Some(&available[1]), AcceptResult::Fallback)
+//! );
+//! ```
+
+use icu_locid::LanguageIdentifier;
+
+use crate::subtags::truncate;
+
+/// Disposition of the result, the `UAcceptResult` of ICU4C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AcceptResult {
+    /// No requested locale, nor any of its parents, is available.
+    /// `ULOC_ACCEPT_FAILED`.
+    Failed,
+    /// A requested locale is available. `ULOC_ACCEPT_VALID`.
+    Valid,
+    /// A parent of a requested locale is available. `ULOC_ACCEPT_FALLBACK`.
+    Fallback,
+}
+
+/// Selects an available locale for the requested ones, following
+/// the semantics of `uloc_acceptLanguage`.
+///
+/// The requested locales are first searched for an available one in their
+/// order. If there's none, the requested locales are truncated towards
+/// their parents, longest serialization first, until one of them matches.
+pub fn accept_language<'a, R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &'a [A],
+) -> (Option<&'a A>, AcceptResult) {
+    let find = |langid: &LanguageIdentifier| available.iter().find(|a| a.as_ref() == langid);
+
+    for req in requested {
+        if let Some(found) = find(req.as_ref()) {
+            return (Some(found), AcceptResult::Valid);
+        }
+    }
+
+    let mut fallbacks: Vec<Option<(usize, LanguageIdentifier)>> = requested
+        .iter()
+        .map(|req| {
+            let req = req.as_ref();
+            Some((req.to_string().len(), req.clone()))
+        })
+        .collect();
+    let max_len = fallbacks
+        .iter()
+        .flatten()
+        .map(|(len, _)| *len)
+        .max()
+        .unwrap_or(0);
+
+    for len in (1..=max_len).rev() {
+        for fallback in fallbacks.iter_mut() {
+            let Some((fallback_len, langid)) = fallback else {
+                continue;
+            };
+            if *fallback_len != len {
+                continue;
+            }
+            if let Some(found) = find(langid) {
+                return (Some(found), AcceptResult::Fallback);
+            }
+            // Like `uloc_getParent`, which stops at the root locale.
+            *fallback = truncate(langid)
+                .filter(|parent| *parent != LanguageIdentifier::UND)
+                .map(|parent| (parent.to_string().len(), parent));
+        }
+    }
+    (None, AcceptResult::Failed)
+}
@@ -0,0 +1,200 @@
+//! Interning of language identifiers.
+//!
+//! Workloads holding many parsed tags, such as the `Accept-Language`
+//! headers of an analytics pipeline, mostly hold the same few hundred
+//! language identifiers over and over. [`LocaleInterner`] stores each
+//! distinct one once, and hands out [`LocaleId`]s, four bytes handles which
+//! are `Copy`, and compare and hash as integers.
+//!
+//! Negotiation runs over the handles directly, see
+//! [`LocaleInterner::negotiate_languages`].
+//!
+//! This module is only available with the `interner` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::interner::LocaleInterner;
+//! use fluent_langneg::NegotiationStrategy;
+//! use icu_locid::langid;
+//!
+//! let mut interner = LocaleInterner::new();
+//! let en_us = interner.intern(langid!("en-US"));
+//! let de = interner.intern(langid!("de"));
+//!
+//! let requested: Vec<_> = ["de-AT", "de-AT", "en"]
+//!     .iter()
+//!     .map(|tag| interner.intern_str(tag).unwrap())
+//!     .collect();
+//! assert_eq!(requested[0], requested[1]);
+//! assert_eq!(interner.len(), 4);
+//!
+//! let supported = interner.negotiate_languages(
+//!     &requested,
+//!     &[en_us, de],
+//!     Some(en_us),
+//!     NegotiationStrategy::Filtering,
+//! );
+//! assert_eq!(supported, &[de, en_us]);
+//! assert_eq!(interner.resolve(de), Some(&langid!("de")));
+//! ```
+
+use std::collections::HashMap;
+
+use icu_locid::LanguageIdentifier;
+
+use crate::negotiate::filter_matches;
+use crate::{negotiate_languages, Error, NegotiationStrategy};
+
+/// A handle of a language identifier interned in a [`LocaleInterner`].
+///
+/// Handles are only meaningful for the interner which created them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocaleId(u32);
+
+impl LocaleId {
+    /// Returns the position of the language identifier in the interner, in
+    /// the order of interning.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// An arena of distinct language identifiers, see the
+/// [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct LocaleInterner {
+    langids: Vec<LanguageIdentifier>,
+    ids: HashMap<LanguageIdentifier, LocaleId>,
+}
+
+impl LocaleInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct language identifiers.
+    pub fn len(&self) -> usize {
+        self.langids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.langids.is_empty()
+    }
+
+    /// Returns the handle of the language identifier, interning it if it
+    /// hasn't been before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` distinct language identifiers are
+    /// interned.
+    pub fn intern(&mut self, langid: LanguageIdentifier) -> LocaleId {
+        if let Some(id) = self.ids.get(&langid) {
+            return *id;
+        }
+        let id = LocaleId(u32::try_from(self.langids.len()).expect("Too many interned locales"));
+        self.langids.push(langid.clone());
+        self.ids.insert(langid, id);
+        id
+    }
+
+    /// Parses and interns a language identifier.
+    pub fn intern_str(&mut self, tag: &str) -> Result<LocaleId, Error> {
+        crate::parse_langid(tag).map(|langid| self.intern(langid))
+    }
+
+    /// Returns the handle of the language identifier, if it has been
+    /// interned.
+    pub fn get(&self, langid: &LanguageIdentifier) -> Option<LocaleId> {
+        self.ids.get(langid).copied()
+    }
+
+    /// Returns the language identifier of the handle, or `None` for
+    /// a handle of another interner.
+    pub fn resolve(&self, id: LocaleId) -> Option<&LanguageIdentifier> {
+        self.langids.get(id.index())
+    }
+
+    /// Returns the interned language identifiers with their handles, in the
+    /// order of interning.
+    pub fn iter(&self) -> impl Iterator<Item = (LocaleId, &LanguageIdentifier)> {
+        self.langids
+            .iter()
+            .enumerate()
+            .map(|(i, langid)| (LocaleId(i as u32), langid))
+    }
+
+    /// Same as [`filter_matches`] over handles.
+    ///
+    /// Handles of another interner are ignored.
+    pub fn filter_matches(
+        &self,
+        requested: &[LocaleId],
+        available: &[LocaleId],
+        strategy: NegotiationStrategy,
+    ) -> Vec<LocaleId> {
+        let requested = self.resolve_all(requested);
+        let available = self.resolve_all(available);
+        filter_matches(&requested, &available, strategy)
+            .into_iter()
+            .map(|interned| interned.id)
+            .collect()
+    }
+
+    /// Same as [`negotiate_languages`] over handles.
+    ///
+    /// Handles of another interner are ignored.
+    pub fn negotiate_languages(
+        &self,
+        requested: &[LocaleId],
+        available: &[LocaleId],
+        default: Option<LocaleId>,
+        strategy: NegotiationStrategy,
+    ) -> Vec<LocaleId> {
+        let requested = self.resolve_all(requested);
+        let available = self.resolve_all(available);
+        let default = default.and_then(|id| self.interned(id));
+        negotiate_languages(&requested, &available, default.as_ref(), strategy)
+            .into_iter()
+            .map(|interned| interned.id)
+            .collect()
+    }
+
+    fn interned(&self, id: LocaleId) -> Option<Interned<'_>> {
+        self.resolve(id).map(|langid| Interned { id, langid })
+    }
+
+    fn resolve_all(&self, ids: &[LocaleId]) -> Vec<Interned<'_>> {
+        ids.iter().filter_map(|id| self.interned(*id)).collect()
+    }
+}
+
+impl Extend<LanguageIdentifier> for LocaleInterner {
+    fn extend<T: IntoIterator<Item = LanguageIdentifier>>(&mut self, iter: T) {
+        for langid in iter {
+            self.intern(langid);
+        }
+    }
+}
+
+impl FromIterator<LanguageIdentifier> for LocaleInterner {
+    fn from_iter<T: IntoIterator<Item = LanguageIdentifier>>(iter: T) -> Self {
+        let mut interner = Self::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+/// A resolved handle, negotiated like its language identifier.
+#[derive(Debug, PartialEq)]
+struct Interned<'a> {
+    id: LocaleId,
+    langid: &'a LanguageIdentifier,
+}
+
+impl AsRef<LanguageIdentifier> for Interned<'_> {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        self.langid
+    }
+}
@@ -0,0 +1,201 @@
+//! Language tags of keyboard layouts.
+//!
+//! Some applications have no locale preference to negotiate with other than
+//! the keyboard layouts the user has installed. The layout names of Windows
+//! and XKB are mapped to a plausible language tag, which can extend the
+//! requested locales, for example through
+//! [`RequestedLocales::os`](crate::negotiate::RequestedLocales::os).
+//!
+//! Only common layouts are mapped, and a layout shared by several languages,
+//! such as the Belgian one, maps to one of them.
+//!
+//! This module is only available with the `keyboard` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::keyboard::{from_windows_klid, from_xkb_layouts};
+//! use icu_locid::langid;
+//!
+//! assert_eq!(from_windows_klid("00000407"), Some(langid!("de-DE")));
+//! // US Dvorak.
+//! assert_eq!(from_windows_klid("00010409"), Some(langid!("en-US")));
+//! assert_eq!(
+//!     from_xkb_layouts("us,de(nodeadkeys),zz"),
+//!     &[langid!("en-US"), langid!("de-DE")]
+//! );
+//! ```
+
+use icu_locid::LanguageIdentifier;
+
+/// Windows language identifiers, the low word of a KLID, sorted.
+static WINDOWS_LANGIDS: &[(u16, &str)] = &[
+    (0x0401, "ar-SA"),
+    (0x0402, "bg-BG"),
+    (0x0404, "zh-TW"),
+    (0x0405, "cs-CZ"),
+    (0x0406, "da-DK"),
+    (0x0407, "de-DE"),
+    (0x0408, "el-GR"),
+    (0x0409, "en-US"),
+    (0x040b, "fi-FI"),
+    (0x040c, "fr-FR"),
+    (0x040d, "he-IL"),
+    (0x040e, "hu-HU"),
+    (0x040f, "is-IS"),
+    (0x0410, "it-IT"),
+    (0x0411, "ja-JP"),
+    (0x0412, "ko-KR"),
+    (0x0413, "nl-NL"),
+    (0x0414, "nb-NO"),
+    (0x0415, "pl-PL"),
+    (0x0416, "pt-BR"),
+    (0x0418, "ro-RO"),
+    (0x0419, "ru-RU"),
+    (0x041a, "hr-HR"),
+    (0x041b, "sk-SK"),
+    (0x041d, "sv-SE"),
+    (0x041e, "th-TH"),
+    (0x041f, "tr-TR"),
+    (0x0421, "id-ID"),
+    (0x0422, "uk-UA"),
+    (0x0423, "be-BY"),
+    (0x0424, "sl-SI"),
+    (0x0425, "et-EE"),
+    (0x0426, "lv-LV"),
+    (0x0427, "lt-LT"),
+    (0x0429, "fa-IR"),
+    (0x042a, "vi-VN"),
+    (0x042b, "hy-AM"),
+    (0x0437, "ka-GE"),
+    (0x0439, "hi-IN"),
+    (0x043e, "ms-MY"),
+    (0x043f, "kk-KZ"),
+    (0x0804, "zh-CN"),
+    (0x0807, "de-CH"),
+    (0x0809, "en-GB"),
+    (0x080a, "es-MX"),
+    (0x080c, "fr-BE"),
+    (0x0813, "nl-BE"),
+    (0x0816, "pt-PT"),
+    (0x0c04, "zh-HK"),
+    (0x0c07, "de-AT"),
+    (0x0c09, "en-AU"),
+    (0x0c0a, "es-ES"),
+    (0x0c0c, "fr-CA"),
+    (0x1009, "en-CA"),
+    (0x100c, "fr-CH"),
+    (0x241a, "sr-Latn-RS"),
+    (0x281a, "sr-Cyrl-RS"),
+];
+
+/// XKB layout names, sorted.
+static XKB_LAYOUTS: &[(&str, &str)] = &[
+    ("am", "hy-AM"),
+    ("ara", "ar"),
+    ("at", "de-AT"),
+    ("be", "nl-BE"),
+    ("bg", "bg-BG"),
+    ("br", "pt-BR"),
+    ("by", "be-BY"),
+    ("ca", "fr-CA"),
+    ("ch", "de-CH"),
+    ("cn", "zh-CN"),
+    ("cz", "cs-CZ"),
+    ("de", "de-DE"),
+    ("dk", "da-DK"),
+    ("ee", "et-EE"),
+    ("es", "es-ES"),
+    ("fi", "fi-FI"),
+    ("fr", "fr-FR"),
+    ("gb", "en-GB"),
+    ("ge", "ka-GE"),
+    ("gr", "el-GR"),
+    ("hr", "hr-HR"),
+    ("hu", "hu-HU"),
+    ("ie", "en-IE"),
+    ("il", "he-IL"),
+    ("in", "hi-IN"),
+    ("ir", "fa-IR"),
+    ("is", "is-IS"),
+    ("it", "it-IT"),
+    ("jp", "ja-JP"),
+    ("kr", "ko-KR"),
+    ("kz", "kk-KZ"),
+    ("latam", "es-419"),
+    ("lt", "lt-LT"),
+    ("lv", "lv-LV"),
+    ("nl", "nl-NL"),
+    ("no", "nb-NO"),
+    ("pl", "pl-PL"),
+    ("pt", "pt-PT"),
+    ("ro", "ro-RO"),
+    ("rs", "sr-RS"),
+    ("ru", "ru-RU"),
+    ("se", "sv-SE"),
+    ("si", "sl-SI"),
+    ("sk", "sk-SK"),
+    ("th", "th-TH"),
+    ("tr", "tr-TR"),
+    ("tw", "zh-TW"),
+    ("ua", "uk-UA"),
+    ("us", "en-US"),
+    ("vn", "vi-VN"),
+];
+
+/// Returns the language tag of a Windows keyboard layout identifier, such
+/// as `00000409`, or of an input locale identifier, such as `04090409`.
+///
+/// The language is given by the low word of the identifier, so layout
+/// variants map to the language of their base layout.
+pub fn from_windows_klid(klid: &str) -> Option<LanguageIdentifier> {
+    let klid = klid.trim();
+    if !matches!(klid.len(), 4 | 8) || !klid.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let langid = u16::from_str_radix(&klid[klid.len() - 4..], 16).ok()?;
+    let idx = WINDOWS_LANGIDS
+        .binary_search_by_key(&langid, |(id, _)| *id)
+        .ok()?;
+    WINDOWS_LANGIDS[idx].1.parse().ok()
+}
+
+/// Returns the language tag of an XKB layout, such as `de` or
+/// `us(dvorak)`.
+pub fn from_xkb_layout(layout: &str) -> Option<LanguageIdentifier> {
+    let name = layout.split('(').next()?.trim().to_ascii_lowercase();
+    let idx = XKB_LAYOUTS
+        .binary_search_by_key(&name.as_str(), |(name, _)| name)
+        .ok()?;
+    XKB_LAYOUTS[idx].1.parse().ok()
+}
+
+/// Returns the language tags of a comma separated list of XKB layouts, as
+/// reported by `setxkbmap -query`, skipping unknown layouts and duplicates.
+pub fn from_xkb_layouts(layouts: &str) -> Vec<LanguageIdentifier> {
+    let mut result: Vec<LanguageIdentifier> = Vec::new();
+    for langid in layouts.split(',').filter_map(from_xkb_layout) {
+        if !result.contains(&langid) {
+            result.push(langid);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_sorted_and_valid() {
+        assert!(WINDOWS_LANGIDS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(XKB_LAYOUTS.windows(2).all(|w| w[0].0 < w[1].0));
+        for tag in WINDOWS_LANGIDS
+            .iter()
+            .map(|(_, tag)| tag)
+            .chain(XKB_LAYOUTS.iter().map(|(_, tag)| tag))
+        {
+            assert!(tag.parse::<LanguageIdentifier>().is_ok(), "{tag}");
+        }
+    }
+}
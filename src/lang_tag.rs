@@ -0,0 +1,233 @@
+//! Abstraction over language tag types from different crates.
+//!
+//! The ecosystem uses several types to represent language tags. The
+//! [`LangTag`] trait exposes the subtags negotiation operates on, so
+//! [`negotiate_tags`](crate::negotiate::negotiate_tags) can negotiate
+//! over them without writing the conversion by hand. The tags are still
+//! converted into [`LanguageIdentifier`]s on each call.
+//!
+//! The trait is implemented for `icu_locid` identifiers, and, behind
+//! features of the same name, for `unic_langid::LanguageIdentifier`,
+//! `language_tags::LanguageTag` and `oxilangtag::LanguageTag`.
+//!
+//! Since neither side of the conversion belongs to this crate, conversions
+//! into a [`LanguageIdentifier`] are provided by [`LangTag::to_langid`]
+//! instead of `TryFrom`, and the reverse ones by functions such
+//! as `to_oxilangtag`.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::lang_tag::LangTag;
+//! use icu_locid::langid;
+//!
+//! let langid = langid!("sr-Cyrl-RS");
+//! assert_eq!(langid.language(), Some("sr"));
+//! assert_eq!(langid.script(), Some("Cyrl"));
+//! assert_eq!(langid.region(), Some("RS"));
+//! assert_eq!(langid.variants().count(), 0);
+//! ```
+
+use icu_locid::subtags::{Language, Region, Script, Variant, Variants};
+use icu_locid::{LanguageIdentifier, Locale};
+
+pub trait LangTag {
+    /// The language subtag, or `None` if it is undefined.
+    fn language(&self) -> Option<&str>;
+
+    fn script(&self) -> Option<&str>;
+
+    fn region(&self) -> Option<&str>;
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_>;
+
+    /// Converts the tag into a [`LanguageIdentifier`], or returns `None`
+    /// if any of its subtags is not valid in a Unicode Language Identifier.
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        from_subtags(self)
+    }
+}
+
+fn from_subtags<T: LangTag + ?Sized>(tag: &T) -> Option<LanguageIdentifier> {
+    let language = match tag.language() {
+        Some(language) => Language::try_from_bytes(language.as_bytes()).ok()?,
+        None => Language::UND,
+    };
+    let script = match tag.script() {
+        Some(script) => Some(Script::try_from_bytes(script.as_bytes()).ok()?),
+        None => None,
+    };
+    let region = match tag.region() {
+        Some(region) => Some(Region::try_from_bytes(region.as_bytes()).ok()?),
+        None => None,
+    };
+    let mut variants = tag
+        .variants()
+        .map(|v| Variant::try_from_bytes(v.as_bytes()).ok())
+        .collect::<Option<Vec<_>>>()?;
+    variants.sort_unstable();
+    variants.dedup();
+
+    let mut langid = LanguageIdentifier::from((language, script, region));
+    langid.variants = Variants::from_vec_unchecked(variants);
+    Some(langid)
+}
+
+impl LangTag for LanguageIdentifier {
+    fn language(&self) -> Option<&str> {
+        (!self.language.is_empty()).then(|| self.language.as_str())
+    }
+
+    fn script(&self) -> Option<&str> {
+        self.script.as_ref().map(|s| s.as_str())
+    }
+
+    fn region(&self) -> Option<&str> {
+        self.region.as_ref().map(|r| r.as_str())
+    }
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.variants.iter().map(|v| v.as_str()))
+    }
+
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        Some(self.clone())
+    }
+}
+
+impl LangTag for Locale {
+    fn language(&self) -> Option<&str> {
+        self.id.language()
+    }
+
+    fn script(&self) -> Option<&str> {
+        self.id.script()
+    }
+
+    fn region(&self) -> Option<&str> {
+        self.id.region()
+    }
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        self.id.variants()
+    }
+
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        Some(self.id.clone())
+    }
+}
+
+impl<T: LangTag + ?Sized> LangTag for &T {
+    fn language(&self) -> Option<&str> {
+        (**self).language()
+    }
+
+    fn script(&self) -> Option<&str> {
+        (**self).script()
+    }
+
+    fn region(&self) -> Option<&str> {
+        (**self).region()
+    }
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        (**self).variants()
+    }
+
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        (**self).to_langid()
+    }
+}
+
+#[cfg(feature = "unic-langid")]
+impl LangTag for unic_langid::LanguageIdentifier {
+    fn language(&self) -> Option<&str> {
+        (!self.language.is_empty()).then(|| self.language.as_str())
+    }
+
+    fn script(&self) -> Option<&str> {
+        self.script.as_ref().map(|s| s.as_str())
+    }
+
+    fn region(&self) -> Option<&str> {
+        self.region.as_ref().map(|r| r.as_str())
+    }
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(unic_langid::LanguageIdentifier::variants(self).map(|v| v.as_str()))
+    }
+}
+
+#[cfg(feature = "language-tags")]
+impl LangTag for language_tags::LanguageTag {
+    fn language(&self) -> Option<&str> {
+        Some(self.primary_language()).filter(|l| !l.eq_ignore_ascii_case("und"))
+    }
+
+    fn script(&self) -> Option<&str> {
+        language_tags::LanguageTag::script(self)
+    }
+
+    fn region(&self) -> Option<&str> {
+        language_tags::LanguageTag::region(self)
+    }
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.variant_subtags())
+    }
+
+    /// Tags with extended language subtags are not representable as
+    /// a [`LanguageIdentifier`], and convert to `None`.
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        if self.extended_language().is_some() {
+            return None;
+        }
+        from_subtags(self)
+    }
+}
+
+#[cfg(feature = "oxilangtag")]
+impl<T: std::ops::Deref<Target = str>> LangTag for oxilangtag::LanguageTag<T> {
+    fn language(&self) -> Option<&str> {
+        Some(self.primary_language()).filter(|l| !l.eq_ignore_ascii_case("und"))
+    }
+
+    fn script(&self) -> Option<&str> {
+        oxilangtag::LanguageTag::script(self)
+    }
+
+    fn region(&self) -> Option<&str> {
+        oxilangtag::LanguageTag::region(self)
+    }
+
+    fn variants(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.variant_subtags())
+    }
+
+    /// Tags with extended language subtags are not representable as
+    /// a [`LanguageIdentifier`], and convert to `None`.
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        if self.extended_language().is_some() {
+            return None;
+        }
+        from_subtags(self)
+    }
+}
+
+/// Converts a [`LanguageIdentifier`] into an `oxilangtag::LanguageTag`.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::lang_tag::to_oxilangtag;
+/// use icu_locid::langid;
+///
+/// let tag = to_oxilangtag(&langid!("sr-Latn-RS")).unwrap();
+/// assert_eq!(tag.script(), Some("Latn"));
+/// ```
+#[cfg(feature = "oxilangtag")]
+pub fn to_oxilangtag(
+    langid: &LanguageIdentifier,
+) -> Result<oxilangtag::LanguageTag<String>, oxilangtag::LanguageTagParseError> {
+    oxilangtag::LanguageTag::parse(langid.to_string())
+}
@@ -15,25 +15,182 @@
 //! but is arguably a better option for use cases involving operations on
 //! language tags and for language negotiation.
 
+use std::fmt;
+
+#[cfg(feature = "accept-language")]
 pub mod accepted_languages;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "utils")]
+pub mod canonicalize;
+#[cfg(feature = "accept-language")]
+pub mod detect;
+#[cfg(feature = "utils")]
+pub mod direction;
+#[cfg(feature = "display-names")]
+pub mod display_names;
+#[cfg(feature = "locale")]
+pub mod ecma402;
+mod error;
+#[cfg(feature = "utils")]
+pub mod ext;
+#[cfg(feature = "utils")]
+pub mod format;
+#[cfg(feature = "fs")]
+pub mod fs;
+#[cfg(feature = "utils")]
+pub mod html;
+#[cfg(feature = "utils")]
+pub mod icu4c;
+#[cfg(feature = "interner")]
+pub mod interner;
+#[cfg(feature = "keyboard")]
+pub mod keyboard;
+pub mod lang_tag;
 pub mod negotiate;
+#[cfg(feature = "locale")]
+pub mod plain;
+#[cfg(feature = "plurals")]
+pub mod plurals;
+#[cfg(feature = "region-preferences")]
+pub mod preferences;
+pub mod prelude;
+#[cfg(feature = "utils")]
+pub mod registry;
+#[cfg(feature = "utils")]
+pub mod rfc3066;
+#[cfg(feature = "accept-language")]
+pub mod routing;
+pub mod subtags;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "timezones")]
+pub mod timezone;
+#[cfg(feature = "unic-langid")]
+pub mod unic;
 
+#[cfg(feature = "accept-language")]
 pub use accepted_languages::parse as parse_accepted_languages;
+#[cfg(feature = "utils")]
+pub use direction::is_rtl;
+pub use error::Error;
+pub use negotiate::negotiate;
 pub use negotiate::negotiate_languages;
+pub use negotiate::NegotiationOptions;
 pub use negotiate::NegotiationStrategy;
+pub use negotiate::{LocaleExpander, TransformResult};
 
+pub use icu_locid;
+#[cfg(feature = "locale")]
+pub use icu_locid::Locale;
 pub use icu_locid::{LanguageIdentifier, ParserError as LangugeIdentifierParserError};
 
-pub fn convert_vec_str_to_langids<'a, I, J>(
-    input: I,
-) -> Result<Vec<LanguageIdentifier>, LangugeIdentifierParserError>
+/// Parses a list of available locales at compile time.
+///
+/// The macro expands to an array of [`LanguageIdentifier`]s which can be
+/// used to initialize a `static` or a `const`, so an invalid tag is a build
+/// error instead of an entry silently dropped at runtime.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::{negotiate_languages, static_available};
+/// use fluent_langneg::{LanguageIdentifier, NegotiationStrategy};
+///
+/// static AVAILABLE: [LanguageIdentifier; 3] = static_available!["en-US", "fr", "de_DE"];
+///
+/// let requested: LanguageIdentifier = "de".parse().unwrap();
+/// let supported = negotiate_languages(&[requested], &AVAILABLE, None, NegotiationStrategy::Lookup);
+/// assert_eq!(supported, &[&AVAILABLE[2]]);
+/// ```
+///
+/// Invalid tags fail to compile:
+///
+/// ```compile_fail
+/// static AVAILABLE: [fluent_langneg::LanguageIdentifier; 1] = fluent_langneg::static_available!["en-"];
+/// ```
+///
+/// *Note*: Like [`icu_locid::langid!`], the macro only supports tags with
+/// up to a single variant.
+#[macro_export]
+macro_rules! static_available {
+    ($($tag:literal),* $(,)?) => {
+        [$($crate::icu_locid::langid!($tag)),*]
+    };
+}
+
+/// Returns the CLDR release of the likely subtags data used by negotiation.
+///
+/// Without the `cldr` feature, this is the release the built-in tables were
+/// generated from. With it, this is the release of the data compiled into
+/// `icu_locid_transform_data` 1.5, and data replaced at build time, for
+/// example with the `ICU4X_DATA_DIR` environment variable of ICU4X, is
+/// not reflected here.
+///
+/// To make sure the data is from a given CLDR release, set the
+/// `FLUENT_LANGNEG_CLDR_VERSION` environment variable to it when building,
+/// for example `FLUENT_LANGNEG_CLDR_VERSION=46.0.0-BETA2 cargo build`.
+/// The build then fails if the data is from any other release.
+///
+/// # Example:
+///
+/// ```
+/// let version = fluent_langneg::data_version();
+/// assert!(version.starts_with("46."));
+/// ```
+pub fn data_version() -> &'static str {
+    negotiate::DATA_VERSION
+}
+
+/// An entry of a list which failed to parse, reported by
+/// [`convert_vec_str_to_langids`] within an [`Error::Convert`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvertError {
+    /// Position of the entry in the input.
+    pub index: usize,
+    /// The entry, lossily converted to a string.
+    pub input: String,
+    pub source: LangugeIdentifierParserError,
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to parse language identifier \"{}\" at index {}: {}",
+            self.input, self.index, self.source
+        )
+    }
+}
+
+impl std::error::Error for ConvertError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Converts all the entries, failing with an [`Error::Convert`] pointing
+/// at the first one which doesn't parse.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::{convert_vec_str_to_langids, Error};
+///
+/// assert_eq!(convert_vec_str_to_langids(["en-US", "fr"]).unwrap().len(), 2);
+///
+/// let err = convert_vec_str_to_langids(["en-US", "x"]).unwrap_err();
+/// assert!(matches!(err, Error::Convert(err) if err.index == 1));
+/// ```
+pub fn convert_vec_str_to_langids<'a, I, J>(input: I) -> Result<Vec<LanguageIdentifier>, Error>
 where
     I: IntoIterator<Item = J>,
     J: AsRef<[u8]> + 'a,
 {
     input
         .into_iter()
-        .map(|s| LanguageIdentifier::try_from_bytes(s.as_ref()))
+        .enumerate()
+        .map(|(index, s)| parse_entry(index, s.as_ref()).map_err(Error::Convert))
         .collect()
 }
 
@@ -41,9 +198,64 @@ pub fn convert_vec_str_to_langids_lossy<'a, I, J>(input: I) -> Vec<LanguageIdent
 where
     I: IntoIterator<Item = J>,
     J: AsRef<[u8]> + 'a,
+{
+    convert_vec_str_to_langids_lossy_with(input, |_| {})
+}
+
+/// Converts the entries which parse correctly, and passes the error
+/// for each rejected entry to the callback.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::convert_vec_str_to_langids_lossy_with;
+///
+/// let mut rejected = vec![];
+/// let langids = convert_vec_str_to_langids_lossy_with(["en-US", "x", "fr"], |err| {
+///     rejected.push(err.input);
+/// });
+///
+/// assert_eq!(langids.len(), 2);
+/// assert_eq!(rejected, &["x"]);
+/// ```
+pub fn convert_vec_str_to_langids_lossy_with<'a, I, J, F>(
+    input: I,
+    mut rejected: F,
+) -> Vec<LanguageIdentifier>
+where
+    I: IntoIterator<Item = J>,
+    J: AsRef<[u8]> + 'a,
+    F: FnMut(ConvertError),
 {
     input
         .into_iter()
-        .filter_map(|t| LanguageIdentifier::try_from_bytes(t.as_ref()).ok())
+        .enumerate()
+        .filter_map(|(index, s)| parse_entry(index, s.as_ref()).map_err(&mut rejected).ok())
         .collect()
 }
+
+/// Parses a language identifier, with an [`Error`] pointing at the first
+/// invalid subtag.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::{parse_langid, Error};
+///
+/// assert_eq!(parse_langid("en-US").unwrap().to_string(), "en-US");
+///
+/// let err = parse_langid("en--US").unwrap_err();
+/// assert!(matches!(err, Error::Parse { offset: 3, .. }));
+/// ```
+pub fn parse_langid<S: AsRef<[u8]>>(input: S) -> Result<LanguageIdentifier, Error> {
+    let input = input.as_ref();
+    LanguageIdentifier::try_from_bytes(input).map_err(|source| Error::parse(input, source))
+}
+
+fn parse_entry(index: usize, input: &[u8]) -> Result<LanguageIdentifier, ConvertError> {
+    LanguageIdentifier::try_from_bytes(input).map_err(|source| ConvertError {
+        index,
+        input: String::from_utf8_lossy(input).into_owned(),
+        source,
+    })
+}
@@ -0,0 +1,186 @@
+use icu_locid::LanguageIdentifier;
+
+#[cfg(feature = "cache")]
+use super::cache::{CacheStats, ResultCache};
+use super::index::AvailableIndex;
+use super::{add_default, filter_indices, filter_mask, supported_in, NegotiationStrategy};
+
+/// A set of available locales prepared for repeated negotiation.
+///
+/// The available locales are indexed once, and the maximized forms of
+/// likely subtags computed during one negotiation are reused by the
+/// following ones.
+///
+/// With the `cache` feature, the prepared set can also keep a bounded number
+/// of negotiation results, see [`AvailableLocales::with_cache`].
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::AvailableLocales;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let available = [langid!("en-US"), langid!("de"), langid!("en-GB")];
+/// let prepared = AvailableLocales::new(&available);
+///
+/// assert_eq!(
+///     prepared.negotiate_languages(&[langid!("de-AT")], None, NegotiationStrategy::Filtering),
+///     &[&available[1]]
+/// );
+/// assert_eq!(
+///     prepared.filter_matches(&[langid!("en")], NegotiationStrategy::Lookup),
+///     &[&available[0]]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct AvailableLocales<'a, A> {
+    available: &'a [A],
+    index: AvailableIndex,
+    #[cfg(feature = "cache")]
+    cache: Option<ResultCache>,
+}
+
+impl<'a, A: AsRef<LanguageIdentifier>> AvailableLocales<'a, A> {
+    pub fn new(available: &'a [A]) -> Self {
+        Self {
+            available,
+            index: AvailableIndex::new(available),
+            #[cfg(feature = "cache")]
+            cache: None,
+        }
+    }
+
+    /// Creates a prepared set which remembers the results of up to `capacity`
+    /// distinct negotiations, evicting the least recently used ones.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use fluent_langneg::negotiate::AvailableLocales;
+    /// use fluent_langneg::NegotiationStrategy;
+    /// use icu_locid::langid;
+    ///
+    /// let available = [langid!("en-US"), langid!("de")];
+    /// let prepared = AvailableLocales::with_cache(&available, 100);
+    ///
+    /// for _ in 0..4 {
+    ///     prepared.filter_matches(&[langid!("de-AT")], NegotiationStrategy::Filtering);
+    /// }
+    ///
+    /// let stats = prepared.cache_stats().unwrap();
+    /// assert_eq!((stats.hits, stats.misses), (3, 1));
+    /// assert_eq!(stats.hit_rate(), 0.75);
+    /// ```
+    #[cfg(feature = "cache")]
+    pub fn with_cache(available: &'a [A], capacity: usize) -> Self {
+        Self {
+            cache: Some(ResultCache::new(capacity)),
+            ..Self::new(available)
+        }
+    }
+
+    /// Returns the statistics of the result cache, or `None` if the prepared
+    /// set has been created without one.
+    #[cfg(feature = "cache")]
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(ResultCache::stats)
+    }
+
+    /// Same as [`super::is_supported`], using the prepared set.
+    pub fn is_supported(&self, requested: &LanguageIdentifier) -> bool {
+        supported_in(requested, self.available, Some(&self.index))
+    }
+
+    pub fn as_slice(&self) -> &'a [A] {
+        self.available
+    }
+
+    /// Same as [`filter_matches`](super::filter_matches) over the prepared
+    /// available locales.
+    pub fn filter_matches<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> Vec<&'a A> {
+        let negotiate = || filter_indices(requested, self.available, &self.index, strategy);
+        #[cfg(feature = "cache")]
+        let supported = match &self.cache {
+            Some(cache) => cache.get_or_insert_with(requested, strategy, negotiate),
+            None => negotiate(),
+        };
+        #[cfg(not(feature = "cache"))]
+        let supported = negotiate();
+        supported
+            .into_iter()
+            .filter_map(|i| self.available.get(i))
+            .collect()
+    }
+
+    /// Same as [`filter_matches_mask`](super::filter_matches_mask) over the
+    /// prepared available locales.
+    pub fn filter_matches_mask<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> Vec<bool> {
+        filter_mask(requested, self.available, &self.index, strategy)
+    }
+
+    /// Same as [`negotiate_languages`](super::negotiate_languages) over the
+    /// prepared available locales.
+    pub fn negotiate_languages<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        default: Option<&'a A>,
+        strategy: NegotiationStrategy,
+    ) -> Vec<&'a A>
+    where
+        A: PartialEq,
+    {
+        let mut supported = self.filter_matches(requested, strategy);
+        add_default(&mut supported, default, strategy);
+        supported
+    }
+
+    /// Negotiates each of the lists of requested locales against the prepared
+    /// available locales, in parallel.
+    ///
+    /// The results are in the order of the requests.
+    ///
+    /// This method is only available with the `rayon` feature.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use fluent_langneg::negotiate::AvailableLocales;
+    /// use fluent_langneg::NegotiationStrategy;
+    /// use icu_locid::langid;
+    ///
+    /// let available = [langid!("en-US"), langid!("de"), langid!("fr")];
+    /// let prepared = AvailableLocales::new(&available);
+    ///
+    /// let requests = vec![vec![langid!("de-AT")], vec![langid!("fr-CA"), langid!("en")]];
+    /// let results = prepared.negotiate_many(&requests, None, NegotiationStrategy::Lookup);
+    /// assert_eq!(results, &[vec![&available[1]], vec![&available[2]]]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn negotiate_many<Q, R>(
+        &self,
+        requests: &[Q],
+        default: Option<&'a A>,
+        strategy: NegotiationStrategy,
+    ) -> Vec<Vec<&'a A>>
+    where
+        Q: AsRef<[R]> + Sync,
+        R: AsRef<LanguageIdentifier>,
+        A: PartialEq + Sync,
+    {
+        use rayon::prelude::*;
+
+        requests
+            .par_iter()
+            .map(|requested| self.negotiate_languages(requested.as_ref(), default, strategy))
+            .collect()
+    }
+}
@@ -0,0 +1,214 @@
+//! Bounded cache of negotiation results.
+//!
+//! Real traffic tends to contain a small number of distinct lists of
+//! requested locales, such as the `Accept-Language` headers of the most
+//! common browser configurations, so the results of negotiating them against
+//! the same available locales are worth keeping around.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use icu_locid::LanguageIdentifier;
+
+use super::NegotiationStrategy;
+
+/// Statistics of the negotiation result cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Number of results currently stored.
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Returns the ratio of lookups answered from the cache, or `0.0`
+    /// if there were no lookups yet.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    hash: u64,
+    requested: Vec<LanguageIdentifier>,
+    strategy: NegotiationStrategy,
+    supported: Vec<usize>,
+    last_used: u64,
+}
+
+impl Entry {
+    fn is_for<R: AsRef<LanguageIdentifier>>(
+        &self,
+        hash: u64,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> bool {
+        self.hash == hash
+            && self.strategy == strategy
+            && self.requested.len() == requested.len()
+            && self
+                .requested
+                .iter()
+                .zip(requested)
+                .all(|(e, r)| e == r.as_ref())
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// The entries, which keep their slot until evicted.
+    entries: Vec<Entry>,
+    /// Slots of the entries by the hash of their key.
+    by_hash: HashMap<u64, Vec<usize>>,
+    /// Slots of the entries by the time they were last used, the least
+    /// recently used one first.
+    by_use: BTreeMap<u64, usize>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl State {
+    fn find<R: AsRef<LanguageIdentifier>>(
+        &self,
+        hash: u64,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> Option<usize> {
+        self.by_hash.get(&hash)?.iter().copied().find(|slot| {
+            self.entries
+                .get(*slot)
+                .is_some_and(|entry| entry.is_for(hash, requested, strategy))
+        })
+    }
+
+    /// Returns a new time, distinct from the ones of all the entries.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Removes the slot from the hash index, before it is reused.
+    fn evict(&mut self, slot: usize) {
+        let Some(hash) = self.entries.get(slot).map(|entry| entry.hash) else {
+            return;
+        };
+        if let Some(slots) = self.by_hash.get_mut(&hash) {
+            slots.retain(|s| *s != slot);
+            if slots.is_empty() {
+                self.by_hash.remove(&hash);
+            }
+        }
+    }
+}
+
+/// A least recently used cache of the indices of supported locales, keyed
+/// by the requested locales and the strategy.
+#[derive(Debug)]
+pub(crate) struct ResultCache {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl ResultCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn get_or_insert_with<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+        negotiate: impl FnOnce() -> Vec<usize>,
+    ) -> Vec<usize> {
+        let mut hasher = DefaultHasher::new();
+        for langid in requested {
+            langid.as_ref().hash(&mut hasher);
+        }
+        strategy.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        {
+            let mut state = self.lock();
+            if let Some(slot) = state.find(hash, requested, strategy) {
+                let clock = state.tick();
+                if let Some(entry) = state.entries.get_mut(slot) {
+                    let last_used = std::mem::replace(&mut entry.last_used, clock);
+                    let supported = entry.supported.clone();
+                    state.by_use.remove(&last_used);
+                    state.by_use.insert(clock, slot);
+                    state.hits += 1;
+                    return supported;
+                }
+            }
+            state.misses += 1;
+        }
+
+        // The lock isn't held during the negotiation, so concurrent misses
+        // on the same key may both negotiate, and only one result is kept.
+        let supported = negotiate();
+        if self.capacity == 0 {
+            return supported;
+        }
+
+        let mut state = self.lock();
+        if state.find(hash, requested, strategy).is_some() {
+            return supported;
+        }
+        let last_used = state.tick();
+        let entry = Entry {
+            hash,
+            requested: requested.iter().map(|r| r.as_ref().clone()).collect(),
+            strategy,
+            supported: supported.clone(),
+            last_used,
+        };
+        let slot = if state.entries.len() < self.capacity {
+            state.entries.push(entry);
+            state.entries.len() - 1
+        } else {
+            match state.by_use.pop_first() {
+                Some((_, lru)) => {
+                    state.evict(lru);
+                    if let Some(evicted) = state.entries.get_mut(lru) {
+                        *evicted = entry;
+                    }
+                    lru
+                }
+                None => return supported,
+            }
+        };
+        state.by_hash.entry(hash).or_default().push(slot);
+        state.by_use.insert(last_used, slot);
+        supported
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            len: state.entries.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // The state is consistent between statements, so a panic in another
+        // thread doesn't invalidate it.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,97 @@
+use icu_locid::extensions::unicode::{key, Value};
+use icu_locid::Locale;
+
+use super::{negotiate_languages, NegotiationStrategy};
+
+/// An available locale picked by [`negotiate_collation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollationMatch<'a> {
+    pub locale: &'a Locale,
+    /// The `co` keyword of the available locale, or `None` for the standard
+    /// collation.
+    pub collation: Option<Value>,
+}
+
+/// Negotiates locales carrying `-u-co-` collation keywords.
+///
+/// Locales are negotiated like [`negotiate_languages`], which ignores
+/// extensions. Available locales which only differ by their extensions,
+/// such as `de` and `de-u-co-phonebk`, are then ordered so that the one
+/// with the collation requested for their language comes first. That
+/// collation is the `co` keyword of the first requested locale of the
+/// language, and a requested locale without one prefers the standard
+/// collation.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::negotiate_collation;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::extensions::unicode::value;
+/// use icu_locid::locale;
+///
+/// let available = [locale!("de"), locale!("de-u-co-phonebk"), locale!("en")];
+///
+/// let supported = negotiate_collation(
+///     &[locale!("de-AT-u-co-phonebk")],
+///     &available,
+///     None,
+///     NegotiationStrategy::Lookup,
+/// );
+/// assert_eq!(supported[0].locale, &available[1]);
+/// assert_eq!(supported[0].collation, Some(value!("phonebk")));
+///
+/// let supported = negotiate_collation(
+///     &[locale!("de-AT")],
+///     &available,
+///     None,
+///     NegotiationStrategy::Filtering,
+/// );
+/// assert_eq!(supported[0].locale, &available[0]);
+/// assert_eq!(supported[0].collation, None);
+/// ```
+pub fn negotiate_collation<'a>(
+    requested: &[Locale],
+    available: &'a [Locale],
+    default: Option<&'a Locale>,
+    strategy: NegotiationStrategy,
+) -> Vec<CollationMatch<'a>> {
+    let supported = negotiate_languages(requested, available, default, strategy);
+
+    let mut result: Vec<&'a Locale> = Vec::with_capacity(supported.len());
+    for locale in supported {
+        let wanted = requested
+            .iter()
+            .find(|r| r.id.language == locale.id.language)
+            .and_then(collation);
+        let compatible = available
+            .iter()
+            .filter(|a| a.id == locale.id && !result.contains(a))
+            .find(|a| collation(a) == wanted);
+        match compatible {
+            Some(compatible) => result.push(compatible),
+            None if !result.contains(&locale) => result.push(locale),
+            None => {
+                // The locale was moved forward in place of a sibling.
+                if let Some(sibling) = available
+                    .iter()
+                    .find(|a| a.id == locale.id && !result.contains(a))
+                {
+                    result.push(sibling);
+                }
+            }
+        }
+    }
+
+    result
+        .into_iter()
+        .map(|locale| CollationMatch {
+            locale,
+            collation: collation(locale),
+        })
+        .collect()
+}
+
+fn collation(locale: &Locale) -> Option<Value> {
+    locale.extensions.unicode.keywords.get(&key!("co")).cloned()
+}
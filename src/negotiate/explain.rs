@@ -0,0 +1,172 @@
+//! Machine-readable explanation of a negotiation.
+//!
+//! An [`Explanation`] lists, for each requested locale, the available
+//! locales it matches and at which step of the algorithm, see the
+//! [module documentation](super), and which of them were picked. With the
+//! `serde` feature, it serializes to JSON for support and QA tooling.
+
+use icu_locid::LanguageIdentifier;
+
+use super::index::AvailableIndex;
+use super::score::{self, Query, Score};
+use super::{add_default, find_all_matches, LocaleExpander, NegotiationStrategy, TransformResult};
+
+/// The step of the algorithm at which an available locale matches
+/// a requested one, from the closest match to the loosest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
+pub enum MatchStep {
+    /// The available locale is equal to the requested one.
+    Exact,
+    /// The available locale, treated as a range, covers the requested one.
+    Range,
+    /// The available locale, treated as a range, covers the maximized
+    /// requested one.
+    Maximization,
+    /// The available locale matches the requested one with another variant.
+    Variant,
+    /// The available locale covers the likely script of the requested
+    /// language.
+    Script,
+    /// The available locale matches the requested language with another
+    /// region.
+    Region,
+}
+
+impl MatchStep {
+    fn from_score(score: Score) -> Self {
+        match score {
+            score::EXACT => Self::Exact,
+            score::RANGE => Self::Range,
+            score::MAXIMIZATION => Self::Maximization,
+            score::VARIANT => Self::Variant,
+            score::SCRIPT => Self::Script,
+            _ => Self::Region,
+        }
+    }
+}
+
+/// An available locale matching a requested one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candidate {
+    pub available: LanguageIdentifier,
+    pub step: MatchStep,
+    /// Whether the negotiation picked it for this requested locale. It isn't
+    /// when a closer candidate wins, or when a previous requested locale
+    /// already picked it.
+    pub picked: bool,
+}
+
+/// The candidates of a requested locale, from the closest one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestedExplanation {
+    pub requested: LanguageIdentifier,
+    pub candidates: Vec<Candidate>,
+}
+
+/// Explanation of a negotiation, see [`explain_negotiation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation {
+    pub strategy: NegotiationStrategy,
+    /// The requested locales considered by the negotiation. With the
+    /// `Lookup` strategy, the ones after the first match are left out.
+    pub requested: Vec<RequestedExplanation>,
+    /// The result of the negotiation, as returned by
+    /// [`negotiate_languages`](super::negotiate_languages).
+    pub supported: Vec<LanguageIdentifier>,
+    /// Whether the default locale was added to the result.
+    pub default_added: bool,
+}
+
+/// Negotiates the locales like [`negotiate_languages`](super::negotiate_languages),
+/// and explains the result.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::{explain_negotiation, MatchStep};
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let requested = [langid!("de-AT"), langid!("en")];
+/// let available = [langid!("en-US"), langid!("de"), langid!("de-DE")];
+///
+/// let explanation =
+///     explain_negotiation(&requested, &available, None, NegotiationStrategy::Matching);
+///
+/// let de_at = &explanation.requested[0];
+/// assert_eq!(de_at.candidates[0].available, langid!("de"));
+/// assert_eq!(de_at.candidates[0].step, MatchStep::Range);
+/// assert!(de_at.candidates[0].picked);
+/// assert_eq!(de_at.candidates[1].available, langid!("de-DE"));
+/// assert!(!de_at.candidates[1].picked);
+///
+/// assert_eq!(explanation.supported, &[langid!("de"), langid!("en-US")]);
+/// ```
+pub fn explain_negotiation<
+    'a,
+    R: AsRef<LanguageIdentifier>,
+    A: AsRef<LanguageIdentifier> + PartialEq,
+>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Explanation {
+    let index = AvailableIndex::new(available);
+    let matches = find_all_matches(requested, available, &index, strategy);
+
+    let considered = match (strategy, matches.first()) {
+        (NegotiationStrategy::Lookup, Some(m)) => m.requested + 1,
+        _ => requested.len(),
+    };
+    let lc = LocaleExpander::new();
+    let mut maximize =
+        |langid: &mut LanguageIdentifier| lc.maximize(langid) == TransformResult::Modified;
+    let explained = requested
+        .iter()
+        .take(considered)
+        .enumerate()
+        .map(|(req_idx, req)| {
+            let query = Query::new(req.as_ref(), &mut maximize);
+            let mut candidates: Vec<(Score, Candidate)> = available
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, langid)| {
+                    let score = query.score(langid.as_ref(), Score::MAX)?;
+                    let candidate = Candidate {
+                        available: langid.as_ref().clone(),
+                        step: MatchStep::from_score(score::step(score)),
+                        picked: matches
+                            .iter()
+                            .any(|m| m.requested == req_idx && m.available == idx),
+                    };
+                    Some((score, candidate))
+                })
+                .collect();
+            candidates.sort_by_key(|(score, _)| *score);
+            RequestedExplanation {
+                requested: req.as_ref().clone(),
+                candidates: candidates.into_iter().map(|(_, c)| c).collect(),
+            }
+        })
+        .collect();
+
+    let mut supported: Vec<&A> = matches
+        .iter()
+        .filter_map(|m| available.get(m.available))
+        .collect();
+    let len = supported.len();
+    add_default(&mut supported, default, strategy);
+    Explanation {
+        strategy,
+        requested: explained,
+        default_added: supported.len() > len,
+        supported: supported.into_iter().map(|a| a.as_ref().clone()).collect(),
+    }
+}
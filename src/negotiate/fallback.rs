@@ -0,0 +1,132 @@
+use icu_locid::subtags::Variants;
+use icu_locid::LanguageIdentifier;
+
+use super::{LocaleExpander, TransformResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Exact,
+    Maximized,
+    WithoutVariants,
+    WithoutRegion,
+    Parent,
+    Default,
+    Done,
+}
+
+/// The fallback chain of a locale, computed one locale at a time.
+///
+/// The chain starts with the locale itself, followed by its maximized
+/// version, the maximized version without variants, then without region,
+/// the bare language, and finally the default locale, if any. A locale
+/// which is already in the chain is not repeated.
+///
+/// Likely subtags are only looked up when the second locale is requested,
+/// so a resource loader stopping at the first locale whose files exist
+/// doesn't pay for the rest of the chain.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::FallbackChain;
+/// use icu_locid::langid;
+///
+/// let chain = FallbackChain::new(langid!("sr-Cyrl-RS")).with_default(langid!("en-US"));
+/// assert_eq!(
+///     chain.collect::<Vec<_>>(),
+///     &[langid!("sr-Cyrl-RS"), langid!("sr-Cyrl"), langid!("sr"), langid!("en-US")]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct FallbackChain {
+    requested: LanguageIdentifier,
+    maximized: Option<LanguageIdentifier>,
+    default: Option<LanguageIdentifier>,
+    stage: Stage,
+    yielded: Vec<LanguageIdentifier>,
+}
+
+impl FallbackChain {
+    pub fn new(requested: LanguageIdentifier) -> Self {
+        Self {
+            requested,
+            maximized: None,
+            default: None,
+            stage: Stage::Exact,
+            yielded: Vec::new(),
+        }
+    }
+
+    /// Sets the locale ending the chain.
+    pub fn with_default(mut self, default: LanguageIdentifier) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    /// The maximized requested locale, which keeps its own script and region.
+    fn maximized(&mut self) -> &LanguageIdentifier {
+        let requested = &self.requested;
+        self.maximized.get_or_insert_with(|| {
+            let mut maximized = requested.clone();
+            if requested.language.is_empty()
+                || LocaleExpander::new().maximize(&mut maximized) == TransformResult::Unmodified
+                || requested
+                    .script
+                    .is_some_and(|s| maximized.script != Some(s))
+                || requested
+                    .region
+                    .is_some_and(|r| maximized.region != Some(r))
+            {
+                return requested.clone();
+            }
+            maximized
+        })
+    }
+
+    fn candidate(&mut self, stage: Stage) -> Option<LanguageIdentifier> {
+        match stage {
+            Stage::Exact => Some(self.requested.clone()),
+            Stage::Maximized => Some(self.maximized().clone()),
+            Stage::WithoutVariants => {
+                let mut langid = self.maximized().clone();
+                langid.variants = Variants::new();
+                Some(langid)
+            }
+            Stage::WithoutRegion => {
+                let mut langid = self.maximized().clone();
+                langid.variants = Variants::new();
+                langid.region = None;
+                Some(langid)
+            }
+            Stage::Parent => (!self.requested.language.is_empty())
+                .then(|| LanguageIdentifier::from(self.requested.language)),
+            Stage::Default => self.default.clone(),
+            Stage::Done => None,
+        }
+    }
+}
+
+impl Iterator for FallbackChain {
+    type Item = LanguageIdentifier;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.stage != Stage::Done {
+            let stage = self.stage;
+            self.stage = match stage {
+                Stage::Exact => Stage::Maximized,
+                Stage::Maximized => Stage::WithoutVariants,
+                Stage::WithoutVariants => Stage::WithoutRegion,
+                Stage::WithoutRegion => Stage::Parent,
+                Stage::Parent => Stage::Default,
+                Stage::Default | Stage::Done => Stage::Done,
+            };
+            if let Some(langid) = self.candidate(stage) {
+                if !self.yielded.contains(&langid) {
+                    self.yielded.push(langid.clone());
+                    return Some(langid);
+                }
+            }
+        }
+        None
+    }
+}
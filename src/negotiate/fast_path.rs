@@ -0,0 +1,171 @@
+//! Likely subtags of the most commonly requested languages.
+//!
+//! Most negotiations maximize one of a handful of languages, so these are
+//! looked up in a perfect hash table built at compile time, which takes a
+//! multiplication and a comparison instead of a search of the likely
+//! subtags data. Other language identifiers, and the ones whose likely
+//! script depends on their region, fall back to the [`LocaleExpander`](super::LocaleExpander).
+//!
+//! The table mirrors the CLDR data, so it is only used with the `cldr`
+//! feature. The built-in data of the expander is small enough to not need it.
+
+use icu_locid::subtags::{language, region, script, Language, Region, Script};
+use icu_locid::LanguageIdentifier;
+
+/// Languages with their likely script and region, from CLDR.
+const LIKELY: [(Language, Script, Region); 20] = [
+    (language!("en"), script!("Latn"), region!("US")),
+    (language!("zh"), script!("Hans"), region!("CN")),
+    (language!("es"), script!("Latn"), region!("ES")),
+    (language!("fr"), script!("Latn"), region!("FR")),
+    (language!("de"), script!("Latn"), region!("DE")),
+    (language!("ja"), script!("Jpan"), region!("JP")),
+    (language!("pt"), script!("Latn"), region!("BR")),
+    (language!("ru"), script!("Cyrl"), region!("RU")),
+    (language!("ar"), script!("Arab"), region!("EG")),
+    (language!("ko"), script!("Kore"), region!("KR")),
+    (language!("it"), script!("Latn"), region!("IT")),
+    (language!("nl"), script!("Latn"), region!("NL")),
+    (language!("pl"), script!("Latn"), region!("PL")),
+    (language!("tr"), script!("Latn"), region!("TR")),
+    (language!("hi"), script!("Deva"), region!("IN")),
+    (language!("id"), script!("Latn"), region!("ID")),
+    (language!("vi"), script!("Latn"), region!("VN")),
+    (language!("th"), script!("Thai"), region!("TH")),
+    (language!("sv"), script!("Latn"), region!("SE")),
+    (language!("uk"), script!("Cyrl"), region!("UA")),
+];
+
+/// Languages of [`LIKELY`] with a likely script depending on the region,
+/// such as `Hant` for `zh-TW`.
+const REGIONAL_SCRIPTS: [Language; 1] = [language!("zh")];
+
+const SLOT_BITS: u32 = 6;
+const MULTIPLIER: u32 = find_multiplier();
+const SLOTS: [u8; 1 << SLOT_BITS] = build_slots(MULTIPLIER);
+const EMPTY: u8 = u8::MAX;
+
+const fn key(language: Language) -> u32 {
+    let raw = language.into_raw();
+    raw[0] as u32 | (raw[1] as u32) << 8 | (raw[2] as u32) << 16
+}
+
+const fn slot(key: u32, multiplier: u32) -> usize {
+    (key.wrapping_mul(multiplier) >> (32 - SLOT_BITS)) as usize
+}
+
+// The table is built in const fns, evaluated at compile time, where an out
+// of bounds index is a build error.
+
+/// Returns the first odd multiplier, from the golden ratio one, for which
+/// the languages of [`LIKELY`] land in distinct slots.
+#[allow(clippy::indexing_slicing)]
+const fn find_multiplier() -> u32 {
+    let mut multiplier: u32 = 0x9E37_79B1;
+    loop {
+        let mut used = [false; 1 << SLOT_BITS];
+        let mut i = 0;
+        while i < LIKELY.len() {
+            let slot = slot(key(LIKELY[i].0), multiplier);
+            if used[slot] {
+                break;
+            }
+            used[slot] = true;
+            i += 1;
+        }
+        if i == LIKELY.len() {
+            return multiplier;
+        }
+        multiplier = multiplier.wrapping_add(2);
+    }
+}
+
+#[allow(clippy::indexing_slicing)]
+const fn build_slots(multiplier: u32) -> [u8; 1 << SLOT_BITS] {
+    let mut slots = [EMPTY; 1 << SLOT_BITS];
+    let mut i = 0;
+    while i < LIKELY.len() {
+        slots[slot(key(LIKELY[i].0), multiplier)] = i as u8;
+        i += 1;
+    }
+    slots
+}
+
+fn lookup(language: Language) -> Option<(Script, Region)> {
+    let entry = *SLOTS.get(slot(key(language), MULTIPLIER))?;
+    let (found, script, region) = *LIKELY.get(entry as usize)?;
+    (found == language).then_some((script, region))
+}
+
+/// Maximizes the language identifier if its language is in the table.
+///
+/// Returns whether the language identifier has been modified, or `None` if
+/// it has to be maximized by the general path.
+pub(crate) fn maximize(langid: &mut LanguageIdentifier) -> Option<bool> {
+    let (script, region) = lookup(langid.language)?;
+    match (langid.script, langid.region) {
+        (Some(_), Some(_)) => Some(false),
+        (None, None) => {
+            langid.script = Some(script);
+            langid.region = Some(region);
+            Some(true)
+        }
+        (Some(s), None) if s == script => {
+            langid.region = Some(region);
+            Some(true)
+        }
+        (None, Some(_)) if !REGIONAL_SCRIPTS.contains(&langid.language) => {
+            langid.script = Some(script);
+            Some(true)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::negotiate::{LocaleExpander, TransformResult};
+
+    #[test]
+    fn test_perfect_hash() {
+        for (language, script, region) in LIKELY {
+            assert_eq!(lookup(language), Some((script, region)));
+        }
+        assert_eq!(lookup(language!("sr")), None);
+        assert_eq!(lookup(Language::UND), None);
+    }
+
+    #[test]
+    fn test_same_as_expander() {
+        let lc = LocaleExpander::new();
+        let regions = [
+            "", "US", "GB", "CA", "AU", "IN", "CN", "TW", "HK", "SG", "ES", "MX", "AR", "FR", "BE",
+            "CH", "DE", "AT", "JP", "BR", "PT", "RU", "UA", "EG", "SA", "MA", "KR", "IT", "NL",
+            "PL", "TR", "CY", "ID", "VN", "TH", "SE", "FI", "KZ", "IL", "PK", "AF", "IR", "001",
+        ];
+        for (language, script, _) in LIKELY {
+            for region in regions {
+                for script in [None, Some(script), Some(script!("Latn"))] {
+                    let mut tag = language.to_string();
+                    if let Some(script) = script {
+                        tag = format!("{tag}-{script}");
+                    }
+                    if !region.is_empty() {
+                        tag = format!("{tag}-{region}");
+                    }
+                    let langid: LanguageIdentifier = tag.parse().unwrap();
+
+                    let mut fast = langid.clone();
+                    let Some(modified) = maximize(&mut fast) else {
+                        continue;
+                    };
+                    let mut expected = langid.clone();
+                    let expected_modified = lc.maximize(&mut expected) == TransformResult::Modified;
+                    assert_eq!(fast, expected, "{langid}");
+                    assert_eq!(modified, expected_modified, "{langid}");
+                }
+            }
+        }
+    }
+}
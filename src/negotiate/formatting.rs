@@ -0,0 +1,50 @@
+use icu_locid::subtags::Region;
+use icu_locid::LanguageIdentifier;
+
+use super::{LocaleExpander, TransformResult};
+
+/// Returns the locale to format numbers and dates with, combining the
+/// negotiated UI locale with the region of the operating system.
+///
+/// The UI follows the available translations, while formatting follows the
+/// regional conventions of the user, so a German UI on a system set to the
+/// United States formats with `de-US`. Without a region, the UI locale is
+/// returned as is.
+///
+/// If the region would change the likely script of the UI language, such
+/// as Chinese in Taiwan, the script of the UI locale is kept explicitly.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::formatting_locale;
+/// use icu_locid::langid;
+/// use icu_locid::subtags::region;
+///
+/// assert_eq!(formatting_locale(&langid!("de"), Some(region!("US"))), langid!("de-US"));
+/// assert_eq!(formatting_locale(&langid!("en-GB"), Some(region!("CH"))), langid!("en-CH"));
+/// assert_eq!(formatting_locale(&langid!("fr-CA"), None), langid!("fr-CA"));
+/// ```
+pub fn formatting_locale(
+    ui_locale: &LanguageIdentifier,
+    os_region: Option<Region>,
+) -> LanguageIdentifier {
+    let mut result = ui_locale.clone();
+    let Some(region) = os_region else {
+        return result;
+    };
+    result.region = Some(region);
+
+    if result.script.is_none() && !result.language.is_empty() {
+        let lc = LocaleExpander::new();
+        let mut ui = ui_locale.clone();
+        let mut combined = result.clone();
+        if lc.maximize(&mut ui) == TransformResult::Modified
+            && lc.maximize(&mut combined) == TransformResult::Modified
+            && ui.script != combined.script
+        {
+            result.script = ui.script;
+        }
+    }
+    result
+}
@@ -0,0 +1,195 @@
+//! Index of available locales.
+//!
+//! Apart from `und` ranges, an available locale can only match a requested
+//! one with the same language, so each requested locale needs to scan
+//! only the available locales in its bucket.
+//!
+//! The index also caches the maximized subtags of available locales, which
+//! are reused when a requested locale is maximized into the same subtags.
+
+use std::iter::Peekable;
+use std::ops::Range;
+use std::slice::Iter;
+use std::sync::OnceLock;
+
+use icu_locid::subtags::{Language, Region, Script};
+use icu_locid::LanguageIdentifier;
+
+use super::{LocaleExpander, TransformResult};
+
+type Subtags = (Language, Option<Script>, Option<Region>);
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AvailableIndex {
+    /// Language of each available locale, with its index, sorted.
+    entries: Vec<(Language, usize)>,
+    /// Maximized subtags of each available locale, computed on first use.
+    /// `None` means the locale can't be maximized.
+    maximized: Vec<OnceLock<Option<Subtags>>>,
+}
+
+impl AvailableIndex {
+    pub fn new<A: AsRef<LanguageIdentifier>>(available: &[A]) -> Self {
+        let mut index = Self::default();
+        index.rebuild(available);
+        index
+    }
+
+    /// Indexes other available locales, reusing the allocations.
+    pub fn rebuild<A: AsRef<LanguageIdentifier>>(&mut self, available: &[A]) {
+        self.entries.clear();
+        self.entries.extend(
+            available
+                .iter()
+                .enumerate()
+                .map(|(idx, langid)| (langid.as_ref().language, idx)),
+        );
+        self.entries.sort_unstable();
+        self.maximized.clear();
+        self.maximized.resize_with(available.len(), OnceLock::new);
+    }
+
+    fn bucket(&self, language: Language) -> &[(Language, usize)] {
+        let start = self.entries.partition_point(|(l, _)| *l < language);
+        let rest = self.entries.get(start..).unwrap_or_default();
+        let len = rest.partition_point(|(l, _)| *l == language);
+        rest.get(..len).unwrap_or_default()
+    }
+
+    /// Returns the indices of the available locales with the language,
+    /// or an `und` one, in the order of the available locales.
+    pub fn candidates(&self, language: Language) -> Candidates<'_> {
+        let und = if language.is_empty() {
+            &[]
+        } else {
+            self.bucket(Language::UND)
+        };
+        Candidates::Buckets(
+            self.bucket(language).iter().peekable(),
+            und.iter().peekable(),
+        )
+    }
+
+    /// Maximizes the language identifier, reusing the cached result of an
+    /// available locale with the same subtags if there is one.
+    ///
+    /// Returns `true` if the language identifier has been modified.
+    pub fn maximize<A: AsRef<LanguageIdentifier>>(
+        &self,
+        available: &[A],
+        lc: &LocaleExpander,
+        langid: &mut LanguageIdentifier,
+    ) -> bool {
+        let found = self.bucket(langid.language).iter().find(|(_, idx)| {
+            available.get(*idx).is_some_and(|locale| {
+                let locale = locale.as_ref();
+                locale.script == langid.script && locale.region == langid.region
+            })
+        });
+        let Some(cached) = found.and_then(|(_, idx)| self.maximized.get(*idx)) else {
+            return lc.maximize(langid) == TransformResult::Modified;
+        };
+
+        let maximized = cached.get_or_init(|| {
+            let mut lid = LanguageIdentifier::from((langid.language, langid.script, langid.region));
+            (lc.maximize(&mut lid) == TransformResult::Modified).then_some((
+                lid.language,
+                lid.script,
+                lid.region,
+            ))
+        });
+        match maximized {
+            Some((language, script, region)) => {
+                langid.language = *language;
+                langid.script = *script;
+                langid.region = *region;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum Candidates<'a> {
+    All(Range<usize>),
+    Buckets(
+        Peekable<Iter<'a, (Language, usize)>>,
+        Peekable<Iter<'a, (Language, usize)>>,
+    ),
+}
+
+impl Iterator for Candidates<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match self {
+            Self::All(range) => range.next(),
+            Self::Buckets(lang, und) => match (lang.peek(), und.peek()) {
+                (Some((_, l)), Some((_, u))) if u < l => und.next(),
+                (Some(_), _) => lang.next(),
+                (None, _) => und.next(),
+            }
+            .map(|(_, idx)| *idx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icu_locid::langid;
+
+    #[test]
+    fn test_candidates_order() {
+        let available = [
+            langid!("en-US"),
+            langid!("und"),
+            langid!("fr"),
+            langid!("en"),
+            langid!("und-Latn"),
+        ];
+        let index = AvailableIndex::new(&available);
+        let en = available[0].language;
+        assert_eq!(index.candidates(en).collect::<Vec<_>>(), &[0, 1, 3, 4]);
+        assert_eq!(index.candidates(Language::UND).collect::<Vec<_>>(), &[1, 4]);
+    }
+
+    #[test]
+    fn test_cached_maximize() {
+        let available = [langid!("en-GB"), langid!("en")];
+        let index = AvailableIndex::new(&available);
+        let lc = LocaleExpander::new();
+
+        let mut langid = langid!("en");
+        assert!(index.maximize(&available, &lc, &mut langid));
+        assert_eq!(langid, langid!("en-Latn-US"));
+        assert!(index.maximized[0].get().is_none());
+        assert!(index.maximized[1].get().is_some());
+
+        let mut langid = langid!("en");
+        assert!(index.maximize(&available, &lc, &mut langid));
+        assert_eq!(langid, langid!("en-Latn-US"));
+    }
+
+    #[test]
+    fn test_rebuild() {
+        let available = [langid!("en"), langid!("fr")];
+        let mut index = AvailableIndex::new(&available);
+        let lc = LocaleExpander::new();
+        assert!(index.maximize(&available, &lc, &mut langid!("en")));
+        assert!(index.maximized[0].get().is_some());
+
+        let available = [langid!("fr"), langid!("de"), langid!("en")];
+        index.rebuild(&available);
+        assert!(index.maximized.iter().all(|m| m.get().is_none()));
+        assert_eq!(
+            index.candidates(available[2].language).collect::<Vec<_>>(),
+            &[2]
+        );
+        assert_eq!(
+            index.candidates(available[1].language).collect::<Vec<_>>(),
+            &[1]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Verification of the negotiation result.
+//!
+//! With the `invariants` feature, each negotiation checks its result and
+//! panics with a trace of the matches if any of these doesn't hold:
+//!
+//! - every supported locale is one of the available ones,
+//! - no available locale is supported twice,
+//! - the matches of each requested locale are ordered from the best
+//!   to the worst score,
+//! - the `Matching` strategy matches each requested locale at most once,
+//!   and `Lookup` returns at most one locale.
+//!
+//! The checks are meant for tests of applications and of this crate,
+//! and are too slow for production use.
+
+use std::fmt::Write;
+
+use icu_locid::LanguageIdentifier;
+
+use super::{Match, NegotiationStrategy};
+
+// Panicking on a violation is the purpose of the feature.
+#[allow(clippy::panic)]
+pub(crate) fn check<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    strategy: NegotiationStrategy,
+    trace: &[Match],
+) {
+    if let Err(violation) = find_violation(requested.len(), available.len(), strategy, trace) {
+        panic!(
+            "Negotiation invariant violated: {}\n{}",
+            violation,
+            format_trace(requested, available, strategy, trace)
+        );
+    }
+}
+
+fn find_violation(
+    requested: usize,
+    available: usize,
+    strategy: NegotiationStrategy,
+    trace: &[Match],
+) -> Result<(), String> {
+    for (idx, m) in trace.iter().enumerate() {
+        if m.available >= available || m.requested >= requested {
+            return Err(format!("match {} is out of bounds", idx));
+        }
+        if trace.iter().take(idx).any(|p| p.available == m.available) {
+            return Err(format!(
+                "available locale {} is supported twice",
+                m.available
+            ));
+        }
+        let outranked = trace
+            .iter()
+            .take(idx)
+            .any(|p| p.requested == m.requested && p.score > m.score);
+        if outranked {
+            return Err(format!(
+                "match {} of score {} is placed after a worse match",
+                idx, m.score
+            ));
+        }
+        if strategy == NegotiationStrategy::Matching
+            && trace.iter().take(idx).any(|p| p.requested == m.requested)
+        {
+            return Err(format!("requested locale {} is matched twice", m.requested));
+        }
+    }
+    if strategy == NegotiationStrategy::Lookup && trace.len() > 1 {
+        return Err("lookup returned more than one locale".to_string());
+    }
+    Ok(())
+}
+
+fn format_trace<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    strategy: NegotiationStrategy,
+    trace: &[Match],
+) -> String {
+    let join = |langids: &mut dyn Iterator<Item = &LanguageIdentifier>| {
+        langids
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut result = String::new();
+    let _ = writeln!(result, "  strategy: {:?}", strategy);
+    let _ = writeln!(
+        result,
+        "  requested: [{}]",
+        join(&mut requested.iter().map(|r| r.as_ref()))
+    );
+    let _ = writeln!(
+        result,
+        "  available: [{}]",
+        join(&mut available.iter().map(|a| a.as_ref()))
+    );
+    for m in trace {
+        let show = |langid: Option<&LanguageIdentifier>| {
+            langid.map_or_else(|| "?".to_string(), |l| l.to_string())
+        };
+        let _ = writeln!(
+            result,
+            "  score {}: {} (#{}) for {} (#{})",
+            m.score,
+            show(available.get(m.available).map(|a| a.as_ref())),
+            m.available,
+            show(requested.get(m.requested).map(|r| r.as_ref())),
+            m.requested,
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(available: usize, requested: usize, score: u8) -> Match {
+        Match {
+            available,
+            requested,
+            score,
+        }
+    }
+
+    #[test]
+    fn test_find_violation() {
+        let filtering = NegotiationStrategy::Filtering;
+        assert!(find_violation(2, 3, filtering, &[m(0, 0, 1), m(2, 0, 3), m(1, 1, 1)]).is_ok());
+        assert!(find_violation(2, 3, filtering, &[m(3, 0, 1)]).is_err());
+        assert!(find_violation(2, 3, filtering, &[m(0, 0, 1), m(0, 1, 1)]).is_err());
+        assert!(find_violation(2, 3, filtering, &[m(0, 0, 3), m(1, 0, 1)]).is_err());
+        assert!(find_violation(
+            2,
+            3,
+            NegotiationStrategy::Matching,
+            &[m(0, 0, 1), m(1, 0, 2)]
+        )
+        .is_err());
+        assert!(
+            find_violation(2, 3, NegotiationStrategy::Lookup, &[m(0, 0, 1), m(1, 1, 1)]).is_err()
+        );
+    }
+}
@@ -1,82 +1,731 @@
 use icu_locid::{
-    langid,
-    subtags::{language, region, Language, Region},
+    subtags::{language, region, script, Language, Region, Script},
     LanguageIdentifier,
 };
 
-static REGION_MATCHING_KEYS: &[(Language, Region)] = &[
-    (language!("az"), region!("AZ")),
-    (language!("bg"), region!("BG")),
-    (language!("cs"), region!("CS")),
-    (language!("de"), region!("DE")),
-    (language!("es"), region!("ES")),
-    (language!("fi"), region!("FI")),
-    (language!("fr"), region!("FR")),
-    (language!("it"), region!("IT")),
-    (language!("lt"), region!("LT")),
-    (language!("lv"), region!("LV")),
-    (language!("nl"), region!("NL")),
-    (language!("nu"), region!("NU")),
-    (language!("pl"), region!("PL")),
-    (language!("ro"), region!("RO")),
-    (language!("ru"), region!("RU")),
+/// CLDR release the tables below are generated from.
+pub(crate) const CLDR_VERSION: &str = "46.0.0-BETA2";
+
+/// Likely script and region of the most used languages, applied to the
+/// subtags missing from a language identifier of the language.
+///
+/// Generated from CLDR by `cargo run --example likely_subtags --features cldr`,
+/// sorted by the language.
+static LIKELY_SUBTAGS: &[(Language, Script, Region)] = &[
+    (language!("af"), script!("Latn"), region!("ZA")),
+    (language!("ak"), script!("Latn"), region!("GH")),
+    (language!("am"), script!("Ethi"), region!("ET")),
+    (language!("ar"), script!("Arab"), region!("EG")),
+    (language!("as"), script!("Beng"), region!("IN")),
+    (language!("az"), script!("Latn"), region!("AZ")),
+    (language!("be"), script!("Cyrl"), region!("BY")),
+    (language!("bg"), script!("Cyrl"), region!("BG")),
+    (language!("bn"), script!("Beng"), region!("BD")),
+    (language!("bo"), script!("Tibt"), region!("CN")),
+    (language!("bs"), script!("Latn"), region!("BA")),
+    (language!("ca"), script!("Latn"), region!("ES")),
+    (language!("ceb"), script!("Latn"), region!("PH")),
+    (language!("ckb"), script!("Arab"), region!("IQ")),
+    (language!("cs"), script!("Latn"), region!("CZ")),
+    (language!("cy"), script!("Latn"), region!("GB")),
+    (language!("da"), script!("Latn"), region!("DK")),
+    (language!("de"), script!("Latn"), region!("DE")),
+    (language!("dv"), script!("Thaa"), region!("MV")),
+    (language!("el"), script!("Grek"), region!("GR")),
+    (language!("en"), script!("Latn"), region!("US")),
+    (language!("es"), script!("Latn"), region!("ES")),
+    (language!("et"), script!("Latn"), region!("EE")),
+    (language!("eu"), script!("Latn"), region!("ES")),
+    (language!("fa"), script!("Arab"), region!("IR")),
+    (language!("ff"), script!("Latn"), region!("SN")),
+    (language!("fi"), script!("Latn"), region!("FI")),
+    (language!("fil"), script!("Latn"), region!("PH")),
+    (language!("fr"), script!("Latn"), region!("FR")),
+    (language!("ga"), script!("Latn"), region!("IE")),
+    (language!("gl"), script!("Latn"), region!("ES")),
+    (language!("gu"), script!("Gujr"), region!("IN")),
+    (language!("ha"), script!("Latn"), region!("NG")),
+    (language!("he"), script!("Hebr"), region!("IL")),
+    (language!("hi"), script!("Deva"), region!("IN")),
+    (language!("hr"), script!("Latn"), region!("HR")),
+    (language!("ht"), script!("Latn"), region!("HT")),
+    (language!("hu"), script!("Latn"), region!("HU")),
+    (language!("hy"), script!("Armn"), region!("AM")),
+    (language!("id"), script!("Latn"), region!("ID")),
+    (language!("ig"), script!("Latn"), region!("NG")),
+    (language!("is"), script!("Latn"), region!("IS")),
+    (language!("it"), script!("Latn"), region!("IT")),
+    (language!("ja"), script!("Jpan"), region!("JP")),
+    (language!("jv"), script!("Latn"), region!("ID")),
+    (language!("ka"), script!("Geor"), region!("GE")),
+    (language!("kk"), script!("Cyrl"), region!("KZ")),
+    (language!("km"), script!("Khmr"), region!("KH")),
+    (language!("kn"), script!("Knda"), region!("IN")),
+    (language!("ko"), script!("Kore"), region!("KR")),
+    (language!("ku"), script!("Latn"), region!("TR")),
+    (language!("ky"), script!("Cyrl"), region!("KG")),
+    (language!("lb"), script!("Latn"), region!("LU")),
+    (language!("ln"), script!("Latn"), region!("CD")),
+    (language!("lo"), script!("Laoo"), region!("LA")),
+    (language!("lt"), script!("Latn"), region!("LT")),
+    (language!("lv"), script!("Latn"), region!("LV")),
+    (language!("mg"), script!("Latn"), region!("MG")),
+    (language!("mk"), script!("Cyrl"), region!("MK")),
+    (language!("ml"), script!("Mlym"), region!("IN")),
+    (language!("mn"), script!("Cyrl"), region!("MN")),
+    (language!("mr"), script!("Deva"), region!("IN")),
+    (language!("ms"), script!("Latn"), region!("MY")),
+    (language!("mt"), script!("Latn"), region!("MT")),
+    (language!("my"), script!("Mymr"), region!("MM")),
+    (language!("nb"), script!("Latn"), region!("NO")),
+    (language!("ne"), script!("Deva"), region!("NP")),
+    (language!("nl"), script!("Latn"), region!("NL")),
+    (language!("nn"), script!("Latn"), region!("NO")),
+    (language!("no"), script!("Latn"), region!("NO")),
+    (language!("ny"), script!("Latn"), region!("MW")),
+    (language!("om"), script!("Latn"), region!("ET")),
+    (language!("or"), script!("Orya"), region!("IN")),
+    (language!("pa"), script!("Guru"), region!("IN")),
+    (language!("pl"), script!("Latn"), region!("PL")),
+    (language!("ps"), script!("Arab"), region!("AF")),
+    (language!("pt"), script!("Latn"), region!("BR")),
+    (language!("qu"), script!("Latn"), region!("PE")),
+    (language!("ro"), script!("Latn"), region!("RO")),
+    (language!("ru"), script!("Cyrl"), region!("RU")),
+    (language!("rw"), script!("Latn"), region!("RW")),
+    (language!("sa"), script!("Deva"), region!("IN")),
+    (language!("sd"), script!("Arab"), region!("PK")),
+    (language!("si"), script!("Sinh"), region!("LK")),
+    (language!("sk"), script!("Latn"), region!("SK")),
+    (language!("sl"), script!("Latn"), region!("SI")),
+    (language!("sn"), script!("Latn"), region!("ZW")),
+    (language!("so"), script!("Latn"), region!("SO")),
+    (language!("sq"), script!("Latn"), region!("AL")),
+    (language!("sr"), script!("Cyrl"), region!("RS")),
+    (language!("st"), script!("Latn"), region!("ZA")),
+    (language!("su"), script!("Latn"), region!("ID")),
+    (language!("sv"), script!("Latn"), region!("SE")),
+    (language!("sw"), script!("Latn"), region!("TZ")),
+    (language!("ta"), script!("Taml"), region!("IN")),
+    (language!("te"), script!("Telu"), region!("IN")),
+    (language!("tg"), script!("Cyrl"), region!("TJ")),
+    (language!("th"), script!("Thai"), region!("TH")),
+    (language!("ti"), script!("Ethi"), region!("ET")),
+    (language!("tk"), script!("Latn"), region!("TM")),
+    (language!("tr"), script!("Latn"), region!("TR")),
+    (language!("tt"), script!("Cyrl"), region!("RU")),
+    (language!("ug"), script!("Arab"), region!("CN")),
+    (language!("uk"), script!("Cyrl"), region!("UA")),
+    (language!("ur"), script!("Arab"), region!("PK")),
+    (language!("uz"), script!("Latn"), region!("UZ")),
+    (language!("vi"), script!("Latn"), region!("VN")),
+    (language!("wo"), script!("Latn"), region!("SN")),
+    (language!("xh"), script!("Latn"), region!("ZA")),
+    (language!("yo"), script!("Latn"), region!("NG")),
+    (language!("yue"), script!("Hant"), region!("HK")),
+    (language!("zh"), script!("Hans"), region!("CN")),
+    (language!("zu"), script!("Latn"), region!("ZA")),
+];
+
+/// Likely script of a language in a region, for the regions where it
+/// differs from the script of [`LIKELY_SUBTAGS`].
+///
+/// Generated together with [`LIKELY_SUBTAGS`], sorted by the language and
+/// the region.
+static LANGUAGE_REGION_SCRIPTS: &[(Language, Region, Script)] = &[
+    (language!("az"), region!("IQ"), script!("Arab")),
+    (language!("az"), region!("IR"), script!("Arab")),
+    (language!("az"), region!("RU"), script!("Cyrl")),
+    (language!("ha"), region!("CM"), script!("Arab")),
+    (language!("ha"), region!("SD"), script!("Arab")),
+    (language!("kk"), region!("AF"), script!("Arab")),
+    (language!("kk"), region!("CN"), script!("Arab")),
+    (language!("kk"), region!("IR"), script!("Arab")),
+    (language!("kk"), region!("MN"), script!("Arab")),
+    (language!("ku"), region!("LB"), script!("Arab")),
+    (language!("ky"), region!("CN"), script!("Arab")),
+    (language!("ky"), region!("TR"), script!("Latn")),
+    (language!("mn"), region!("CN"), script!("Mong")),
+    (language!("ms"), region!("CC"), script!("Arab")),
+    (language!("pa"), region!("PK"), script!("Arab")),
+    (language!("sd"), region!("IN"), script!("Deva")),
+    (language!("sr"), region!("ME"), script!("Latn")),
+    (language!("sr"), region!("RO"), script!("Latn")),
+    (language!("sr"), region!("RU"), script!("Latn")),
+    (language!("sr"), region!("TR"), script!("Latn")),
+    (language!("tg"), region!("PK"), script!("Arab")),
+    (language!("ug"), region!("KZ"), script!("Cyrl")),
+    (language!("ug"), region!("MN"), script!("Cyrl")),
+    (language!("uz"), region!("AF"), script!("Arab")),
+    (language!("uz"), region!("CN"), script!("Cyrl")),
+    (language!("yue"), region!("CN"), script!("Hans")),
+    (language!("zh"), region!("AU"), script!("Hant")),
+    (language!("zh"), region!("BN"), script!("Hant")),
+    (language!("zh"), region!("GB"), script!("Hant")),
+    (language!("zh"), region!("GF"), script!("Hant")),
+    (language!("zh"), region!("HK"), script!("Hant")),
+    (language!("zh"), region!("ID"), script!("Hant")),
+    (language!("zh"), region!("MO"), script!("Hant")),
+    (language!("zh"), region!("PA"), script!("Hant")),
+    (language!("zh"), region!("PF"), script!("Hant")),
+    (language!("zh"), region!("PH"), script!("Hant")),
+    (language!("zh"), region!("SR"), script!("Hant")),
+    (language!("zh"), region!("TH"), script!("Hant")),
+    (language!("zh"), region!("TW"), script!("Hant")),
+    (language!("zh"), region!("US"), script!("Hant")),
+    (language!("zh"), region!("VN"), script!("Hant")),
+];
+
+/// Likely region of a language in a script, for the scripts where it
+/// differs from the region of [`LIKELY_SUBTAGS`].
+///
+/// Generated together with [`LIKELY_SUBTAGS`], sorted by the language and
+/// the script.
+static LANGUAGE_SCRIPT_REGIONS: &[(Language, Script, Region)] = &[
+    (language!("az"), script!("Arab"), region!("IR")),
+    (language!("kk"), script!("Arab"), region!("CN")),
+    (language!("ku"), script!("Arab"), region!("IQ")),
+    (language!("ky"), script!("Arab"), region!("CN")),
+    (language!("ky"), script!("Latn"), region!("TR")),
+    (language!("mn"), script!("Mong"), region!("CN")),
+    (language!("pa"), script!("Arab"), region!("PK")),
+    (language!("sd"), script!("Deva"), region!("IN")),
+    (language!("tg"), script!("Arab"), region!("PK")),
+    (language!("ug"), script!("Cyrl"), region!("KZ")),
+    (language!("uz"), script!("Arab"), region!("AF")),
+    (language!("yue"), script!("Hans"), region!("CN")),
+    (language!("zh"), script!("Hant"), region!("TW")),
+];
+
+/// Likely language and region of a script, applied to an `und` language
+/// with the script.
+///
+/// Generated together with [`LIKELY_SUBTAGS`], sorted by the script.
+static SCRIPT_SUBTAGS: &[(Script, Language, Region)] = &[
+    (script!("Arab"), language!("ar"), region!("EG")),
+    (script!("Armn"), language!("hy"), region!("AM")),
+    (script!("Beng"), language!("bn"), region!("BD")),
+    (script!("Cyrl"), language!("ru"), region!("RU")),
+    (script!("Deva"), language!("hi"), region!("IN")),
+    (script!("Ethi"), language!("am"), region!("ET")),
+    (script!("Geor"), language!("ka"), region!("GE")),
+    (script!("Grek"), language!("el"), region!("GR")),
+    (script!("Gujr"), language!("gu"), region!("IN")),
+    (script!("Guru"), language!("pa"), region!("IN")),
+    (script!("Hans"), language!("zh"), region!("CN")),
+    (script!("Hant"), language!("zh"), region!("TW")),
+    (script!("Hebr"), language!("he"), region!("IL")),
+    (script!("Jpan"), language!("ja"), region!("JP")),
+    (script!("Khmr"), language!("km"), region!("KH")),
+    (script!("Knda"), language!("kn"), region!("IN")),
+    (script!("Kore"), language!("ko"), region!("KR")),
+    (script!("Laoo"), language!("lo"), region!("LA")),
+    (script!("Latn"), language!("en"), region!("US")),
+    (script!("Mlym"), language!("ml"), region!("IN")),
+    (script!("Mymr"), language!("my"), region!("MM")),
+    (script!("Orya"), language!("or"), region!("IN")),
+    (script!("Sinh"), language!("si"), region!("LK")),
+    (script!("Taml"), language!("ta"), region!("IN")),
+    (script!("Telu"), language!("te"), region!("IN")),
+    (script!("Thaa"), language!("dv"), region!("MV")),
+    (script!("Thai"), language!("th"), region!("TH")),
+    (script!("Tibt"), language!("bo"), region!("CN")),
+];
+
+/// Likely language of a region in a script, applied to an `und` language
+/// with both, for the scripts other than the one of [`REGION_SUBTAGS`]
+/// where it isn't the language of [`SCRIPT_SUBTAGS`].
+///
+/// Generated together with [`LIKELY_SUBTAGS`], sorted by the region and
+/// the script.
+static REGION_SCRIPT_LANGUAGES: &[(Region, Script, Language)] = &[
+    (region!("AF"), script!("Cyrl"), language!("kaa")),
+    (region!("AF"), script!("Latn"), language!("tk")),
+    (region!("AL"), script!("Cyrl"), language!("mk")),
+    (region!("AM"), script!("Latn"), language!("ku")),
+    (region!("AZ"), script!("Cyrl"), language!("az")),
+    (region!("BA"), script!("Cyrl"), language!("sr")),
+    (region!("BY"), script!("Latn"), language!("be")),
+    (region!("CN"), script!("Arab"), language!("ug")),
+    (region!("CN"), script!("Hant"), language!("yue")),
+    (region!("CN"), script!("Latn"), language!("za")),
+    (region!("CN"), script!("Thai"), language!("lcp")),
+    (region!("GB"), script!("Arab"), language!("ur")),
+    (region!("GE"), script!("Cyrl"), language!("ab")),
+    (region!("GE"), script!("Latn"), language!("ku")),
+    (region!("GR"), script!("Cyrl"), language!("mk")),
+    (region!("ID"), script!("Arab"), language!("ms")),
+    (region!("IN"), script!("Arab"), language!("ur")),
+    (region!("IN"), script!("Mymr"), language!("kht")),
+    (region!("IR"), script!("Cyrl"), language!("kaa")),
+    (region!("IR"), script!("Latn"), language!("tk")),
+    (region!("JP"), script!("Latn"), language!("ja")),
+    (region!("KG"), script!("Latn"), language!("ky")),
+    (region!("KH"), script!("Arab"), language!("cja")),
+    (region!("KH"), script!("Latn"), language!("km")),
+    (region!("KH"), script!("Thai"), language!("kdt")),
+    (region!("KR"), script!("Latn"), language!("ko")),
+    (region!("LA"), script!("Latn"), language!("lo")),
+    (region!("LA"), script!("Thai"), language!("kdt")),
+    (region!("MK"), script!("Latn"), language!("sq")),
+    (region!("MM"), script!("Arab"), language!("rhg")),
+    (region!("MM"), script!("Latn"), language!("kac")),
+    (region!("MN"), script!("Arab"), language!("kk")),
+    (region!("MN"), script!("Latn"), language!("mn")),
+    (region!("NG"), script!("Arab"), language!("ha")),
+    (region!("PK"), script!("Deva"), language!("btv")),
+    (region!("RO"), script!("Cyrl"), language!("bg")),
+    (region!("RS"), script!("Latn"), language!("sr")),
+    (region!("RU"), script!("Latn"), language!("krl")),
+    (region!("SE"), script!("Hebr"), language!("yi")),
+    (region!("SK"), script!("Cyrl"), language!("uk")),
+    (region!("TH"), script!("Arab"), language!("mfa")),
+    (region!("TH"), script!("Mymr"), language!("mnw")),
+    (region!("TJ"), script!("Arab"), language!("fa")),
+    (region!("TJ"), script!("Latn"), language!("tg")),
+    (region!("TR"), script!("Arab"), language!("apc")),
+    (region!("TR"), script!("Cyrl"), language!("kbd")),
+    (region!("TR"), script!("Grek"), language!("bgx")),
+    (region!("UA"), script!("Hebr"), language!("yi")),
+    (region!("UA"), script!("Latn"), language!("pl")),
+    (region!("US"), script!("Hebr"), language!("yi")),
+    (region!("UZ"), script!("Cyrl"), language!("uz")),
+];
+
+/// Likely language and script of a region, applied to an `und` language
+/// with the region, unless it has a different script.
+///
+/// Generated together with [`LIKELY_SUBTAGS`], sorted by the region.
+static REGION_SUBTAGS: &[(Region, Language, Script)] = &[
+    (region!("AF"), language!("fa"), script!("Arab")),
+    (region!("AL"), language!("sq"), script!("Latn")),
+    (region!("AM"), language!("hy"), script!("Armn")),
+    (region!("AZ"), language!("az"), script!("Latn")),
+    (region!("BA"), language!("bs"), script!("Latn")),
+    (region!("BD"), language!("bn"), script!("Beng")),
+    (region!("BG"), language!("bg"), script!("Cyrl")),
+    (region!("BR"), language!("pt"), script!("Latn")),
+    (region!("BY"), language!("be"), script!("Cyrl")),
+    (region!("CD"), language!("sw"), script!("Latn")),
+    (region!("CN"), language!("zh"), script!("Hans")),
+    (region!("CZ"), language!("cs"), script!("Latn")),
+    (region!("DE"), language!("de"), script!("Latn")),
+    (region!("DK"), language!("da"), script!("Latn")),
+    (region!("EE"), language!("et"), script!("Latn")),
+    (region!("EG"), language!("ar"), script!("Arab")),
+    (region!("ES"), language!("es"), script!("Latn")),
+    (region!("ET"), language!("am"), script!("Ethi")),
+    (region!("FI"), language!("fi"), script!("Latn")),
+    (region!("FR"), language!("fr"), script!("Latn")),
+    (region!("GB"), language!("en"), script!("Latn")),
+    (region!("GE"), language!("ka"), script!("Geor")),
+    (region!("GH"), language!("ak"), script!("Latn")),
+    (region!("GR"), language!("el"), script!("Grek")),
+    (region!("HK"), language!("zh"), script!("Hant")),
+    (region!("HR"), language!("hr"), script!("Latn")),
+    (region!("HT"), language!("ht"), script!("Latn")),
+    (region!("HU"), language!("hu"), script!("Latn")),
+    (region!("ID"), language!("id"), script!("Latn")),
+    (region!("IE"), language!("en"), script!("Latn")),
+    (region!("IL"), language!("he"), script!("Hebr")),
+    (region!("IN"), language!("hi"), script!("Deva")),
+    (region!("IQ"), language!("ar"), script!("Arab")),
+    (region!("IR"), language!("fa"), script!("Arab")),
+    (region!("IS"), language!("is"), script!("Latn")),
+    (region!("IT"), language!("it"), script!("Latn")),
+    (region!("JP"), language!("ja"), script!("Jpan")),
+    (region!("KG"), language!("ky"), script!("Cyrl")),
+    (region!("KH"), language!("km"), script!("Khmr")),
+    (region!("KR"), language!("ko"), script!("Kore")),
+    (region!("KZ"), language!("ru"), script!("Cyrl")),
+    (region!("LA"), language!("lo"), script!("Laoo")),
+    (region!("LK"), language!("si"), script!("Sinh")),
+    (region!("LT"), language!("lt"), script!("Latn")),
+    (region!("LU"), language!("fr"), script!("Latn")),
+    (region!("LV"), language!("lv"), script!("Latn")),
+    (region!("MG"), language!("mg"), script!("Latn")),
+    (region!("MK"), language!("mk"), script!("Cyrl")),
+    (region!("MM"), language!("my"), script!("Mymr")),
+    (region!("MN"), language!("mn"), script!("Cyrl")),
+    (region!("MT"), language!("mt"), script!("Latn")),
+    (region!("MV"), language!("dv"), script!("Thaa")),
+    (region!("MW"), language!("en"), script!("Latn")),
+    (region!("MY"), language!("ms"), script!("Latn")),
+    (region!("NG"), language!("en"), script!("Latn")),
+    (region!("NL"), language!("nl"), script!("Latn")),
+    (region!("NO"), language!("nb"), script!("Latn")),
+    (region!("NP"), language!("ne"), script!("Deva")),
+    (region!("PE"), language!("es"), script!("Latn")),
+    (region!("PH"), language!("fil"), script!("Latn")),
+    (region!("PK"), language!("ur"), script!("Arab")),
+    (region!("PL"), language!("pl"), script!("Latn")),
+    (region!("RO"), language!("ro"), script!("Latn")),
+    (region!("RS"), language!("sr"), script!("Cyrl")),
+    (region!("RU"), language!("ru"), script!("Cyrl")),
+    (region!("RW"), language!("rw"), script!("Latn")),
+    (region!("SE"), language!("sv"), script!("Latn")),
+    (region!("SI"), language!("sl"), script!("Latn")),
+    (region!("SK"), language!("sk"), script!("Latn")),
+    (region!("SN"), language!("fr"), script!("Latn")),
+    (region!("SO"), language!("so"), script!("Latn")),
+    (region!("TH"), language!("th"), script!("Thai")),
+    (region!("TJ"), language!("tg"), script!("Cyrl")),
+    (region!("TM"), language!("tk"), script!("Latn")),
+    (region!("TR"), language!("tr"), script!("Latn")),
+    (region!("TZ"), language!("sw"), script!("Latn")),
+    (region!("UA"), language!("uk"), script!("Cyrl")),
+    (region!("US"), language!("en"), script!("Latn")),
+    (region!("UZ"), language!("uz"), script!("Latn")),
+    (region!("VN"), language!("vi"), script!("Latn")),
+    (region!("ZA"), language!("en"), script!("Latn")),
+    (region!("ZW"), language!("sn"), script!("Latn")),
 ];
 
-#[derive(PartialEq, Eq, Debug)]
+/// Whether [`LocaleExpander::maximize`] or [`LocaleExpander::minimize`]
+/// modified the language identifier.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[non_exhaustive]
 pub enum TransformResult {
     Modified,
     Unmodified,
 }
 
+/// A lightweight likely subtags expander, used by negotiation unless the
+/// `cldr` feature replaces it with the one from `icu_locid_transform`.
+///
+/// Its data is a subset of CLDR: tables generated from the release reported
+/// by [`data_version`](crate::data_version), covering the most used
+/// languages and their scripts and regions. For these, the results are the
+/// ones of CLDR, while other languages, as well as a bare `und`, are left as
+/// is. Applications which need likely subtags of any language, for example
+/// to select fonts, should enable the `cldr` feature.
+///
+/// Its API is the subset of `icu_locid_transform::LocaleExpander` used by
+/// this crate.
+#[derive(Debug, Clone, Copy, Default)]
 pub struct LocaleExpander;
 
 impl LocaleExpander {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self
     }
 
-    pub fn maximize(&self, input: &mut LanguageIdentifier) -> TransformResult {
-        let extended = match &input {
-            b if *b == &langid!("en") => langid!("en-Latn-US"),
-            b if *b == &langid!("fr") => langid!("fr-Latn-FR"),
-            b if *b == &langid!("sr") => langid!("sr-Cyrl-SR"),
-            b if *b == &langid!("sr-RU") => langid!("sr-Latn-SR"),
-            b if *b == &langid!("az-IR") => langid!("az-Arab-IR"),
-            b if *b == &langid!("zh-GB") => langid!("zh-Hant-GB"),
-            b if *b == &langid!("zh-US") => langid!("zh-Hant-US"),
-            _ => {
-                let lang = &input.language;
-
-                if let Ok(idx) = REGION_MATCHING_KEYS.binary_search_by(|(l, _)| l.cmp(lang)) {
-                    let subtag = REGION_MATCHING_KEYS[idx].1;
-                    input.region = Some(subtag);
-                    return TransformResult::Modified;
-                }
-                return TransformResult::Unmodified;
-            }
+    /// Adds the likely script and region to the language identifier.
+    ///
+    /// The subtags which are already present are kept, and only inform the
+    /// choice of the missing ones, as with the CLDR algorithm.
+    pub fn maximize<T: AsMut<LanguageIdentifier>>(&self, mut input: T) -> TransformResult {
+        let input = input.as_mut();
+        if input.language.is_empty() {
+            return maximize_und(input);
+        }
+        let language = input.language;
+        let idx = LIKELY_SUBTAGS.binary_search_by(|(l, _, _)| l.cmp(&language));
+        let Some(&(_, likely_script, likely_region)) =
+            idx.ok().and_then(|idx| LIKELY_SUBTAGS.get(idx))
+        else {
+            return TransformResult::Unmodified;
         };
-        let (language, script, region) = (extended.language, extended.script, extended.region);
-        input.language = language;
-        input.script = script;
-        input.region = region;
+
+        let script = input.script.unwrap_or_else(|| {
+            input
+                .region
+                .and_then(|region| region_script(language, region))
+                .unwrap_or(likely_script)
+        });
+        let region = input.region.unwrap_or_else(|| {
+            input
+                .script
+                .and_then(|script| script_region(language, script))
+                .unwrap_or(likely_region)
+        });
+        if (input.script, input.region) == (Some(script), Some(region)) {
+            return TransformResult::Unmodified;
+        }
+        input.script = Some(script);
+        input.region = Some(region);
         TransformResult::Modified
     }
+
+    /// Removes the script and region which are likely for the language,
+    /// as long as they are implied by the remaining subtags.
+    ///
+    /// As with the CLDR algorithm, the region is kept rather than the script
+    /// when either would do.
+    pub fn minimize<T: AsMut<LanguageIdentifier>>(&self, mut input: T) -> TransformResult {
+        let input = input.as_mut();
+        let mut maximized = input.clone();
+        self.maximize(&mut maximized);
+        let (Some(script), Some(region)) = (maximized.script, maximized.region) else {
+            return TransformResult::Unmodified;
+        };
+
+        let minimized = [(None, None), (None, Some(region)), (Some(script), None)]
+            .into_iter()
+            .find(|&(script, region)| {
+                let mut trial = LanguageIdentifier::from((maximized.language, script, region));
+                self.maximize(&mut trial);
+                (trial.language, trial.script, trial.region)
+                    == (maximized.language, maximized.script, maximized.region)
+            })
+            .unwrap_or((Some(script), Some(region)));
+        let before = (input.language, input.script, input.region);
+        (input.script, input.region) = minimized;
+        input.language = maximized.language;
+        if (input.language, input.script, input.region) == before {
+            TransformResult::Unmodified
+        } else {
+            TransformResult::Modified
+        }
+    }
+}
+
+fn region_script(language: Language, region: Region) -> Option<Script> {
+    let idx = LANGUAGE_REGION_SCRIPTS
+        .binary_search_by(|(l, r, _)| (l, r).cmp(&(&language, &region)))
+        .ok()?;
+    LANGUAGE_REGION_SCRIPTS
+        .get(idx)
+        .map(|(_, _, script)| *script)
+}
+
+fn script_region(language: Language, script: Script) -> Option<Region> {
+    let idx = LANGUAGE_SCRIPT_REGIONS
+        .binary_search_by(|(l, s, _)| (l, s).cmp(&(&language, &script)))
+        .ok()?;
+    LANGUAGE_SCRIPT_REGIONS
+        .get(idx)
+        .map(|(_, _, region)| *region)
+}
+
+/// Adds the likely language of the region, in the script if there is one,
+/// or of the script if the region is unknown. A bare `und` is left as is.
+fn maximize_und(input: &mut LanguageIdentifier) -> TransformResult {
+    let by_script = |script: Script| {
+        let idx = SCRIPT_SUBTAGS
+            .binary_search_by(|(s, _, _)| s.cmp(&script))
+            .ok()?;
+        SCRIPT_SUBTAGS
+            .get(idx)
+            .map(|&(_, language, region)| (language, region))
+    };
+    let by_region = |region: Region| {
+        let idx = REGION_SUBTAGS
+            .binary_search_by(|(r, _, _)| r.cmp(&region))
+            .ok()?;
+        REGION_SUBTAGS
+            .get(idx)
+            .map(|&(_, language, script)| (language, script))
+    };
+    let by_region_script = |region: Region, script: Script| {
+        let idx = REGION_SCRIPT_LANGUAGES
+            .binary_search_by(|(r, s, _)| (r, s).cmp(&(&region, &script)))
+            .ok()?;
+        REGION_SCRIPT_LANGUAGES
+            .get(idx)
+            .map(|&(_, _, language)| language)
+    };
+
+    let maximized = match (input.script, input.region) {
+        (None, None) => None,
+        (None, Some(region)) => {
+            by_region(region).map(|(language, script)| (language, script, region))
+        }
+        (Some(script), None) => {
+            by_script(script).map(|(language, region)| (language, script, region))
+        }
+        (Some(script), Some(region)) => match by_region(region) {
+            Some((language, region_script)) if region_script == script => Some(language),
+            _ => by_region_script(region, script)
+                .or_else(|| by_script(script).map(|(language, _)| language)),
+        }
+        .map(|language| (language, script, region)),
+    };
+    let Some((language, script, region)) = maximized else {
+        return TransformResult::Unmodified;
+    };
+    input.language = language;
+    input.script = Some(script);
+    input.region = Some(region);
+    TransformResult::Modified
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use icu_locid::langid;
 
     #[test]
-    fn test_region_matching_sort() {
-        for v in REGION_MATCHING_KEYS.windows(2) {
-            let (v1, v2) = (v[0], v[1]);
+    fn test_likely_subtags_sort() {
+        for v in LIKELY_SUBTAGS.windows(2) {
             assert!(
-                v1.0 < v2.0,
+                v[0].0 < v[1].0,
                 "Language \"{}\" is placed after \"{}\"",
-                v1.0,
-                v2.0
+                v[0].0,
+                v[1].0
             );
         }
     }
+
+    #[test]
+    fn test_language_subtags_sort() {
+        assert!(LANGUAGE_REGION_SCRIPTS
+            .windows(2)
+            .all(|v| (v[0].0, v[0].1) < (v[1].0, v[1].1)));
+        assert!(LANGUAGE_SCRIPT_REGIONS
+            .windows(2)
+            .all(|v| (v[0].0, v[0].1) < (v[1].0, v[1].1)));
+    }
+
+    #[test]
+    fn test_und_subtags_sort() {
+        assert!(SCRIPT_SUBTAGS.windows(2).all(|v| v[0].0 < v[1].0));
+        assert!(REGION_SUBTAGS.windows(2).all(|v| v[0].0 < v[1].0));
+        assert!(REGION_SCRIPT_LANGUAGES
+            .windows(2)
+            .all(|v| (v[0].0, v[0].1) < (v[1].0, v[1].1)));
+    }
+
+    #[test]
+    fn test_und_subtags() {
+        let lc = LocaleExpander::new();
+
+        let mut langid = langid!("und-Cyrl");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+        assert_eq!(langid, langid!("ru-Cyrl-RU"));
+
+        let mut langid = langid!("und-Cyrl-UA");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+        assert_eq!(langid, langid!("uk-Cyrl-UA"));
+
+        let mut langid = langid!("und-Cyrl-US");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+        assert_eq!(langid, langid!("ru-Cyrl-US"));
+
+        // The language of the region, in another of its scripts.
+        let mut langid = langid!("und-Latn-RS");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+        assert_eq!(langid, langid!("sr-Latn-RS"));
+
+        let mut langid = langid!("und-DE");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+        assert_eq!(langid, langid!("de-Latn-DE"));
+
+        let mut langid = langid!("und");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Unmodified);
+    }
+
+    #[test]
+    fn test_likely_subtags() {
+        let lc = LocaleExpander::new();
+        for (input, expected) in [
+            ("ja", "ja-Jpan-JP"),
+            ("de", "de-Latn-DE"),
+            ("sr", "sr-Cyrl-RS"),
+            ("cs", "cs-Latn-CZ"),
+            // Explicit regions are kept.
+            ("de-AT", "de-Latn-AT"),
+            ("fr-CA", "fr-Latn-CA"),
+            ("es-MX", "es-Latn-MX"),
+            ("ru-UA", "ru-Cyrl-UA"),
+            ("nl-BE", "nl-Latn-BE"),
+            // The script may depend on the region, and the region on
+            // the script.
+            ("sr-RU", "sr-Latn-RU"),
+            ("az-IR", "az-Arab-IR"),
+            ("zh-GB", "zh-Hant-GB"),
+            ("zh-Hant", "zh-Hant-TW"),
+            ("en-Cyrl", "en-Cyrl-US"),
+        ] {
+            let mut langid: LanguageIdentifier = input.parse().unwrap();
+            assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+            assert_eq!(langid.to_string(), expected);
+        }
+
+        let mut langid = langid!("pt-Latn-PT");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Unmodified);
+        let mut langid = langid!("tlh-US");
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Unmodified);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let lc = LocaleExpander::new();
+        for (input, expected) in [
+            ("en-Latn-US", "en"),
+            ("en-GB", "en-GB"),
+            ("sr-Latn-RS", "sr-Latn"),
+            ("sr-Cyrl-BA", "sr-BA"),
+            ("zh-Hant-TW", "zh-TW"),
+            ("zh-Hans-TW", "zh-Hans-TW"),
+            ("de-DE-1996", "de-1996"),
+            ("und-Latn", "en"),
+            ("und-Latn-RS", "sr-Latn"),
+            ("tlh-US", "tlh-US"),
+        ] {
+            let mut langid: LanguageIdentifier = input.parse().unwrap();
+            let result = lc.minimize(&mut langid);
+            assert_eq!(langid.to_string(), expected);
+            let modified = if input == expected {
+                TransformResult::Unmodified
+            } else {
+                TransformResult::Modified
+            };
+            assert_eq!(result, modified, "{}", input);
+        }
+    }
+
+    /// Compares the tables with the extended CLDR data they are generated
+    /// from, for `und` and the covered languages with each of the covered
+    /// scripts and regions.
+    #[cfg(feature = "cldr")]
+    #[test]
+    fn test_same_as_cldr() {
+        assert_eq!(CLDR_VERSION, crate::data_version());
+
+        let lc = LocaleExpander::new();
+        let cldr = icu_locid_transform::LocaleExpander::new_extended();
+        let scripts = SCRIPT_SUBTAGS.iter().map(|(script, _, _)| Some(*script));
+        let regions = REGION_SUBTAGS.iter().map(|(region, _, _)| Some(*region));
+        let regions: Vec<Option<Region>> = regions
+            .chain(LANGUAGE_REGION_SCRIPTS.iter().map(|(_, r, _)| Some(*r)))
+            .chain([None])
+            .collect();
+        let languages = LIKELY_SUBTAGS.iter().map(|(language, _, _)| *language);
+        for language in languages.chain([Language::UND]) {
+            for script in scripts.clone().chain([None]) {
+                for region in &regions {
+                    // `und` is only covered in the regions of the table, and
+                    // a bare `und` is left as is, unlike in CLDR.
+                    let covered = match (script, region) {
+                        (None, None) => false,
+                        (_, None) => true,
+                        (_, Some(region)) => REGION_SUBTAGS.iter().any(|(r, _, _)| r == region),
+                    };
+                    if language.is_empty() && !covered {
+                        continue;
+                    }
+                    let input = LanguageIdentifier::from((language, script, *region));
+                    let mut langid = input.clone();
+                    let mut expected = input.clone();
+                    let modified = lc.maximize(&mut langid) == TransformResult::Modified;
+                    let expected_modified = cldr.maximize(&mut expected)
+                        == icu_locid_transform::TransformResult::Modified;
+                    assert_eq!(langid, expected, "{input}");
+                    assert_eq!(modified, expected_modified, "{input}");
+
+                    // Languages of `und` outside of the tables can't be
+                    // minimized.
+                    if LIKELY_SUBTAGS.iter().all(|(l, _, _)| *l != langid.language) {
+                        continue;
+                    }
+                    let mut langid = input.clone();
+                    let mut expected = input.clone();
+                    lc.minimize(&mut langid);
+                    cldr.minimize(&mut expected);
+                    assert_eq!(langid, expected, "{input}");
+                }
+            }
+        }
+    }
 }
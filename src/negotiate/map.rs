@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use icu_locid::LanguageIdentifier;
+
+use super::{filter_matches, FallbackChain, NegotiationStrategy};
+
+/// A map keyed by locales, looking values up through negotiation.
+///
+/// [`LocaleMap::get`] first looks for the requested locale itself, then
+/// walks its [`FallbackChain`], and finally negotiates the requested locale
+/// against the keys with [`NegotiationStrategy::Lookup`], so that `de-AT`
+/// finds the value of `de-DE`. If nothing matches, the value of the default
+/// locale, if any, is returned.
+///
+/// Entries are kept in insertion order, which also decides between keys
+/// matching equally well.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::LocaleMap;
+/// use icu_locid::langid;
+///
+/// let mut messages = LocaleMap::new().with_default(langid!("en-US"));
+/// messages.insert(langid!("en-US"), "Hello");
+/// messages.insert(langid!("de-DE"), "Hallo");
+/// messages.insert(langid!("sr-Cyrl"), "Здраво");
+///
+/// assert_eq!(messages.get(&langid!("de-DE")), Some(&"Hallo"));
+/// assert_eq!(messages.get(&langid!("sr-Cyrl-RS")), Some(&"Здраво"));
+/// assert_eq!(messages.get(&langid!("de-AT")), Some(&"Hallo"));
+/// assert_eq!(messages.get(&langid!("fr")), Some(&"Hello"));
+/// assert_eq!(messages.get_exact(&langid!("de-AT")), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocaleMap<V> {
+    keys: Vec<LanguageIdentifier>,
+    values: Vec<V>,
+    positions: HashMap<LanguageIdentifier, usize>,
+    default: Option<LanguageIdentifier>,
+}
+
+impl<V> Default for LocaleMap<V> {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            values: Vec::new(),
+            positions: HashMap::new(),
+            default: None,
+        }
+    }
+}
+
+impl<V> LocaleMap<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the locale whose value is returned when nothing else matches.
+    pub fn with_default(mut self, default: LanguageIdentifier) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn default_locale(&self) -> Option<&LanguageIdentifier> {
+        self.default.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Inserts a value, returning the previous value of the locale, if any.
+    ///
+    /// Replacing a value keeps the position of the locale.
+    pub fn insert(&mut self, langid: LanguageIdentifier, value: V) -> Option<V> {
+        match self.positions.get(&langid) {
+            Some(&i) => self
+                .values
+                .get_mut(i)
+                .map(|previous| std::mem::replace(previous, value)),
+            None => {
+                self.positions.insert(langid.clone(), self.keys.len());
+                self.keys.push(langid);
+                self.values.push(value);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, langid: &LanguageIdentifier) -> Option<V> {
+        let i = self.positions.remove(langid)?;
+        self.keys.remove(i);
+        for position in self.positions.values_mut() {
+            if *position > i {
+                *position -= 1;
+            }
+        }
+        Some(self.values.remove(i))
+    }
+
+    /// Returns the value of the locale itself, without negotiation.
+    pub fn get_exact(&self, langid: &LanguageIdentifier) -> Option<&V> {
+        self.positions.get(langid).and_then(|&i| self.values.get(i))
+    }
+
+    /// Returns the value best matching the requested locale.
+    pub fn get(&self, requested: &LanguageIdentifier) -> Option<&V> {
+        self.get_key_value(requested).map(|(_, value)| value)
+    }
+
+    /// Same as [`LocaleMap::get`], also returning the matched locale.
+    pub fn get_key_value(
+        &self,
+        requested: &LanguageIdentifier,
+    ) -> Option<(&LanguageIdentifier, &V)> {
+        let i = self.position(requested)?;
+        Some((self.keys.get(i)?, self.values.get(i)?))
+    }
+
+    fn position(&self, requested: &LanguageIdentifier) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+        FallbackChain::new(requested.clone())
+            .find_map(|langid| self.positions.get(&langid).copied())
+            .or_else(|| {
+                let negotiated =
+                    filter_matches(&[requested], &self.keys, NegotiationStrategy::Lookup);
+                negotiated
+                    .first()
+                    .and_then(|langid| self.positions.get(*langid).copied())
+            })
+            .or_else(|| self.positions.get(self.default.as_ref()?).copied())
+    }
+
+    /// Iterates over the locales and their values, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&LanguageIdentifier, &V)> {
+        self.keys.iter().zip(&self.values)
+    }
+
+    /// The locales of the map, in insertion order, which can be used as the
+    /// available locales of a negotiation.
+    pub fn locales(&self) -> &[LanguageIdentifier] {
+        &self.keys
+    }
+}
+
+impl<V> Extend<(LanguageIdentifier, V)> for LocaleMap<V> {
+    fn extend<I: IntoIterator<Item = (LanguageIdentifier, V)>>(&mut self, iter: I) {
+        for (langid, value) in iter {
+            self.insert(langid, value);
+        }
+    }
+}
+
+impl<V> FromIterator<(LanguageIdentifier, V)> for LocaleMap<V> {
+    fn from_iter<I: IntoIterator<Item = (LanguageIdentifier, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
@@ -32,7 +32,11 @@
 //!            expected.iter().map(|t| t.as_ref()).collect::<Vec<&LanguageIdentifier>>());
 //! ```
 //!
-//! # The exact algorithm is custom, and consists of a 6 level strategy:
+//! # The exact algorithm is custom, and scores matches on a 6 level scale:
+//!
+//! Each available locale is scored against each requested locale by the
+//! first of the levels below it matches, and the supported locales are then
+//! ordered by the requested locale they match, and by their score.
 //!
 //! ### 1) Attempt to find an exact match for each requested locale in available locales.
 //!
@@ -119,17 +123,138 @@
 //! ```
 //!
 
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::string_slice,
+    clippy::panic
+)]
+
+use std::fmt::Write;
+
+use icu_locid::subtags::Variants;
 use icu_locid::LanguageIdentifier;
 
-#[cfg(not(feature = "cldr"))]
+use crate::lang_tag::LangTag;
+use index::{AvailableIndex, Candidates};
+use score::{Query, Score, VariantRules};
+
+mod available;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "locale")]
+mod collation;
+mod explain;
+mod fallback;
+#[cfg(feature = "cldr")]
+mod fast_path;
+mod formatting;
+pub(crate) mod index;
+#[cfg(feature = "invariants")]
+mod invariants;
+mod map;
+mod options;
+#[cfg(feature = "utils")]
+mod prepare;
+#[cfg(feature = "serde")]
+mod protocol;
+mod ranges;
+mod region_languages;
+#[cfg(feature = "locale")]
+mod region_override;
+mod report;
+#[cfg(feature = "accept-language")]
+mod requested;
+mod result;
+mod score;
+mod scratch;
+mod shared;
+mod tokenized;
+#[cfg(feature = "accept-language")]
+mod vary;
+#[cfg(feature = "accept-language")]
+mod weighted;
+pub use available::AvailableLocales;
+#[cfg(feature = "cache")]
+pub use cache::CacheStats;
+#[cfg(feature = "locale")]
+pub use collation::{negotiate_collation, CollationMatch};
+pub use explain::{explain_negotiation, Candidate, Explanation, MatchStep, RequestedExplanation};
+pub use fallback::FallbackChain;
+pub use formatting::formatting_locale;
+pub use map::LocaleMap;
+pub use options::{DefaultPlacement, NegotiationOptions, ResultOrder};
+#[cfg(feature = "utils")]
+pub use prepare::{prepare_requested, prepare_requested_with};
+#[cfg(feature = "serde")]
+pub use protocol::{NegotiationRequest, NegotiationResponse};
+pub use ranges::{filter_matches_with_ranges, LanguageRange, SupportedLocale};
+pub use region_languages::likely_languages_for_region;
+#[cfg(feature = "locale")]
+pub use region_override::{negotiate_with_region_override, region_override};
+pub use report::{report, Report, ReportBuilder};
+#[cfg(feature = "accept-language")]
+pub use requested::RequestedLocales;
+pub use result::NegotiationResult;
+pub use scratch::{filter_matches_with_scratch, NegotiationScratch};
+pub use shared::{NegotiatorSnapshot, SharedNegotiator};
+pub use tokenized::{negotiate_tokenized, TokenizedLocale};
+#[cfg(feature = "accept-language")]
+pub use vary::AcceptLanguageClasses;
+#[cfg(feature = "accept-language")]
+pub use weighted::negotiate_weighted;
+// Compiled in the tests of the `cldr` feature, to compare it with CLDR.
+#[cfg(any(test, not(feature = "cldr")))]
 mod likely_subtags;
 #[cfg(feature = "cldr")]
-use icu_locid_transform::{LocaleExpander, TransformResult};
+pub use icu_locid_transform::{LocaleExpander, TransformResult};
+#[cfg(not(feature = "cldr"))]
+pub use likely_subtags::{LocaleExpander, TransformResult};
+
+#[cfg(feature = "cldr")]
+// The release of the data of `icu_locid_transform_data` 1.5, which
+// `icu_locid_transform` 1.5 depends on.
+pub(crate) const DATA_VERSION: &str = "46.0.0-BETA2";
 #[cfg(not(feature = "cldr"))]
-use likely_subtags::{LocaleExpander, TransformResult};
+pub(crate) use likely_subtags::CLDR_VERSION as DATA_VERSION;
+
+// Fails the build if the data isn't from the CLDR release pinned with the
+// `FLUENT_LANGNEG_CLDR_VERSION` environment variable.
+const _: () = {
+    if let Some(pinned) = option_env!("FLUENT_LANGNEG_CLDR_VERSION") {
+        assert!(
+            str_eq(pinned, DATA_VERSION),
+            "The likely subtags data isn't from the CLDR release of FLUENT_LANGNEG_CLDR_VERSION"
+        );
+    }
+};
+
+const fn str_eq(s1: &str, s2: &str) -> bool {
+    let (mut s1, mut s2) = (s1.as_bytes(), s2.as_bytes());
+    loop {
+        match (s1, s2) {
+            ([b1, rest1 @ ..], [b2, rest2 @ ..]) if *b1 == *b2 => {
+                s1 = rest1;
+                s2 = rest2;
+            }
+            ([], []) => return true,
+            _ => return false,
+        }
+    }
+}
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+/// The strategy of a negotiation, see the [module documentation](self).
+///
+/// New strategies may be added in the future, so matches over it need
+/// a wildcard arm.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum NegotiationStrategy {
+    #[default]
     Filtering,
     Matching,
     Lookup,
@@ -144,12 +269,40 @@ fn subtag_matches<P: PartialEq>(
     (as_range1 && subtag1.is_none()) || (as_range2 && subtag2.is_none()) || subtag1 == subtag2
 }
 
+/// Tests whether two language identifiers match.
+///
+/// When `range1` or `range2` is set, missing subtags of the respective
+/// language identifier (including an `und` language) are treated as
+/// wildcards, so `en` as a range matches `en-Latn-US`.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::matches;
+/// use icu_locid::langid;
+///
+/// assert!(matches(&langid!("en"), &langid!("en-US"), true, false));
+/// assert!(!matches(&langid!("en"), &langid!("en-US"), false, true));
+/// assert!(matches(&langid!("en-US"), &langid!("en-US"), false, false));
+/// ```
 #[inline(always)]
-fn matches(
+pub fn matches(
     lid1: &LanguageIdentifier,
     lid2: &LanguageIdentifier,
     range1: bool,
     range2: bool,
+) -> bool {
+    matches_with_variants(lid1, lid2, &lid2.variants, range1, range2)
+}
+
+/// Same as [`matches`], but uses `variants2` in place of the variants of `lid2`.
+#[inline(always)]
+fn matches_with_variants(
+    lid1: &LanguageIdentifier,
+    lid2: &LanguageIdentifier,
+    variants2: &Variants,
+    range1: bool,
+    range2: bool,
 ) -> bool {
     ((range1 && lid1.language.is_empty())
         || (range2 && lid2.language.is_empty())
@@ -157,85 +310,605 @@ fn matches(
         && subtag_matches(&lid1.script, &lid2.script, range1, range2)
         && subtag_matches(&lid1.region, &lid2.region, range1, range2)
         && ((range1 && lid1.variants.is_empty())
-            || (range2 && lid2.variants.is_empty())
-            || lid1.variants == lid2.variants)
+            || (range2 && variants2.is_empty())
+            || same_variants(&lid1.variants, variants2))
 }
 
-pub fn filter_matches<'a, R: 'a + AsRef<LanguageIdentifier>, A: 'a + AsRef<LanguageIdentifier>>(
+/// Compares variants regardless of their order.
+fn same_variants(variants1: &Variants, variants2: &Variants) -> bool {
+    variants1 == variants2
+        || (variants1.len() == variants2.len() && variants1.iter().all(|v| variants2.contains(v)))
+}
+
+/// Tests whether two language identifiers match after both have been
+/// maximized using likely subtags.
+///
+/// Language identifiers with an `und` language are not maximized.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::matches_expanded;
+/// use icu_locid::langid;
+///
+/// assert!(matches_expanded(&langid!("en"), &langid!("en-Latn-US"), false, false));
+/// assert!(!matches_expanded(&langid!("sr"), &langid!("sr-Latn"), false, true));
+/// ```
+pub fn matches_expanded(
+    lid1: &LanguageIdentifier,
+    lid2: &LanguageIdentifier,
+    range1: bool,
+    range2: bool,
+) -> bool {
+    let lc = LocaleExpander::new();
+    let maximize = |lid: &LanguageIdentifier| {
+        let mut lid = lid.clone();
+        if !lid.language.is_empty() {
+            lc.maximize(&mut lid);
+        }
+        lid
+    };
+    matches(&maximize(lid1), &maximize(lid2), range1, range2)
+}
+
+/// Receives the available locales matched by [`find_matches`], by their index.
+pub(crate) trait MatchSink {
+    /// Returns `true` if the available locale has already been matched.
+    fn is_matched(&self, index: usize) -> bool;
+
+    /// Records a match. Returns `false` if no further matches can be accepted.
+    fn push(&mut self, m: Match) -> bool;
+}
+
+/// A match found by [`find_matches`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Match {
+    /// Index of the available locale.
+    pub available: usize,
+    /// Index of the requested locale.
+    pub requested: usize,
+    /// The score of the pair, lower is better.
+    pub score: Score,
+}
+
+/// Scores the available locales against each requested locale, reporting
+/// the kept matches to the sink.
+///
+/// An available locale is supported at most once, for the first requested
+/// locale it matches, and the matches of each requested locale are ordered
+/// by their score, then by the order of the available locales.
+/// On top of that, each strategy keeps:
+///
+/// - `Filtering`: all the matches,
+/// - `Matching`: the best match of each requested locale,
+/// - `Lookup`: the best match of the first requested locale with any.
+///
+/// Without an `index` all available locales are scored for each requested
+/// locale.
+///
+/// Scoring doesn't allocate, so the allocation behavior is fully determined
+/// by the sink.
+pub(crate) fn find_matches<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
     requested: &[R],
-    available: &'a [A],
+    available: &[A],
+    index: Option<&AvailableIndex>,
     strategy: NegotiationStrategy,
-) -> Vec<&'a A> {
-    let mut lc: Option<LocaleExpander> = None;
+    sink: &mut impl MatchSink,
+) {
+    find_matches_with(
+        requested,
+        available,
+        index,
+        strategy,
+        VariantRules::default(),
+        sink,
+    );
+}
 
-    let mut supported_locales = vec![];
+/// Same as [`find_matches`], with the variant handling of the options.
+fn find_matches_with<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    index: Option<&AvailableIndex>,
+    strategy: NegotiationStrategy,
+    rules: VariantRules,
+    sink: &mut impl MatchSink,
+) {
+    find_matches_in(requested, available, index, strategy, rules, None, sink);
+}
 
-    let mut available_locales: Vec<&A> = available.iter().collect();
+/// Same as [`find_matches_with`], sorting the scores of `Filtering` in the
+/// `scored` scratch buffer if there is one.
+///
+/// Without it, the candidates are scored once more for each score they
+/// have, which is a handful at most, so that nothing is allocated.
+fn find_matches_in<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>, S: MatchSink>(
+    requested: &[R],
+    available: &[A],
+    index: Option<&AvailableIndex>,
+    strategy: NegotiationStrategy,
+    rules: VariantRules,
+    mut scored: Option<&mut Vec<(Score, usize)>>,
+    sink: &mut S,
+) {
+    let mut lc: Option<LocaleExpander> = None;
+    let mut maximize = |langid: &mut LanguageIdentifier| {
+        #[cfg(feature = "cldr")]
+        if let Some(modified) = fast_path::maximize(langid) {
+            return modified;
+        }
+        let lc = lc.get_or_insert_with(LocaleExpander::new);
+        match index {
+            Some(index) => index.maximize(available, lc, langid),
+            None => lc.maximize(langid) == TransformResult::Modified,
+        }
+    };
+    for (req_idx, req) in requested.iter().enumerate() {
+        let req = req.as_ref();
+        let query = Query::new(req, &mut maximize).with_rules(rules);
+        let candidates = match index {
+            Some(index) => index.candidates(query.language()),
+            None => Candidates::All(0..available.len()),
+        };
+        let score = |sink: &S, idx: usize, limit: Score| {
+            if sink.is_matched(idx) {
+                None
+            } else {
+                available
+                    .get(idx)
+                    .and_then(|locale| query.score(locale.as_ref(), limit))
+            }
+        };
 
-    macro_rules! test_strategy {
-        ($req:ident, $self_as_range:expr, $other_as_range:expr) => {{
-            let mut match_found = false;
-            available_locales.retain(|locale| {
-                if strategy != NegotiationStrategy::Filtering && match_found {
-                    return true;
+        match strategy {
+            NegotiationStrategy::Filtering => match scored.as_deref_mut() {
+                Some(scored) => {
+                    scored.clear();
+                    scored.extend(
+                        candidates.filter_map(|idx| {
+                            score(sink, idx, Score::MAX).map(|score| (score, idx))
+                        }),
+                    );
+                    // The sort is stable, so the order of the available
+                    // locales is kept within a score.
+                    scored.sort_by_key(|(score, _)| *score);
+                    for &(score, idx) in scored.iter() {
+                        let m = Match {
+                            available: idx,
+                            requested: req_idx,
+                            score,
+                        };
+                        if !sink.push(m) {
+                            return;
+                        }
+                    }
                 }
-
-                if matches(locale.as_ref(), &$req, $self_as_range, $other_as_range) {
-                    match_found = true;
-                    supported_locales.push(*locale);
-                    return false;
+                None => {
+                    let mut levels = [false; Score::MAX as usize + 1];
+                    for idx in candidates.clone() {
+                        if let Some(score) = score(sink, idx, Score::MAX) {
+                            if let Some(level) = levels.get_mut(usize::from(score)) {
+                                *level = true;
+                            }
+                        }
+                    }
+                    let levels = (0..=Score::MAX)
+                        .filter(|level| levels.get(usize::from(*level)).copied().unwrap_or(false));
+                    for level in levels {
+                        // The matches of the previous levels are skipped,
+                        // as they are now matched.
+                        for idx in candidates.clone() {
+                            if score(sink, idx, Score::MAX) != Some(level) {
+                                continue;
+                            }
+                            let m = Match {
+                                available: idx,
+                                requested: req_idx,
+                                score: level,
+                            };
+                            if !sink.push(m) {
+                                return;
+                            }
+                        }
+                    }
                 }
-                true
-            });
-
-            if match_found {
-                match strategy {
-                    NegotiationStrategy::Filtering => {}
-                    NegotiationStrategy::Matching => continue,
-                    NegotiationStrategy::Lookup => break,
+            },
+            NegotiationStrategy::Matching | NegotiationStrategy::Lookup => {
+                let mut best: Option<Match> = None;
+                for idx in candidates {
+                    let limit = best.map_or(Score::MAX, |m| m.score);
+                    if let Some(score) = score(sink, idx, limit) {
+                        best = Some(Match {
+                            available: idx,
+                            requested: req_idx,
+                            score,
+                        });
+                        if score == score::EXACT {
+                            break;
+                        }
+                    }
+                }
+                if let Some(m) = best {
+                    if !sink.push(m) || strategy == NegotiationStrategy::Lookup {
+                        return;
+                    }
                 }
             }
-        }};
+        }
     }
+}
 
-    for req in requested {
-        let req = req.as_ref();
-
-        // 1) Try to find a simple (case-insensitive) string match for the request.
-        test_strategy!(req, false, false);
+struct VecSink {
+    matched: Vec<bool>,
+    matches: Vec<Match>,
+}
 
-        // 2) Try to match against the available locales treated as ranges.
-        test_strategy!(req, true, false);
+impl MatchSink for VecSink {
+    fn is_matched(&self, index: usize) -> bool {
+        self.matched.get(index).copied().unwrap_or(true)
+    }
 
-        // Per Unicode TR35, 4.4 Locale Matching, we don't add likely subtags to
-        // requested locales, so we'll skip it from the rest of the steps.
-        if req.language.is_empty() {
-            continue;
+    fn push(&mut self, m: Match) -> bool {
+        if let Some(matched) = self.matched.get_mut(m.available) {
+            *matched = true;
+            self.matches.push(m);
         }
+        true
+    }
+}
 
-        let mut req = req.to_owned();
-        // 3) Try to match against a maximized version of the requested locale
-        let lc = lc.get_or_insert_with(LocaleExpander::new);
-        if lc.maximize(&mut req) == TransformResult::Modified {
-            test_strategy!(req, true, false);
+struct MaskSink {
+    matched: Vec<bool>,
+}
+
+impl MatchSink for MaskSink {
+    fn is_matched(&self, index: usize) -> bool {
+        self.matched.get(index).copied().unwrap_or(true)
+    }
+
+    fn push(&mut self, m: Match) -> bool {
+        if let Some(matched) = self.matched.get_mut(m.available) {
+            *matched = true;
         }
+        true
+    }
+}
+
+/// Records whether anything matched, and stops at the first match.
+struct FoundSink(bool);
+
+impl MatchSink for FoundSink {
+    fn is_matched(&self, _index: usize) -> bool {
+        false
+    }
+
+    fn push(&mut self, _m: Match) -> bool {
+        self.0 = true;
+        false
+    }
+}
+
+struct BufferSink<'a, 'b, A> {
+    available: &'a [A],
+    out: &'b mut [Option<&'a A>],
+    len: usize,
+}
+
+impl<A> MatchSink for BufferSink<'_, '_, A> {
+    fn is_matched(&self, index: usize) -> bool {
+        let Some(locale) = self.available.get(index) else {
+            return true;
+        };
+        self.out
+            .iter()
+            .take(self.len)
+            .any(|m| m.is_some_and(|m| std::ptr::eq(m, locale)))
+    }
+
+    fn push(&mut self, m: Match) -> bool {
+        let (Some(slot), Some(locale)) =
+            (self.out.get_mut(self.len), self.available.get(m.available))
+        else {
+            return false;
+        };
+        *slot = Some(locale);
+        self.len += 1;
+        self.len < self.out.len()
+    }
+}
+
+pub fn filter_matches<'a, R: 'a + AsRef<LanguageIdentifier>, A: 'a + AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &'a [A],
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let mut supported = vec![];
+    scratch::with_thread_scratch(|scratch| {
+        filter_matches_with_scratch(requested, available, strategy, scratch, &mut supported);
+    });
+    supported
+}
+
+/// Returns the indices of the supported available locales.
+pub(crate) fn filter_indices<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    index: &AvailableIndex,
+    strategy: NegotiationStrategy,
+) -> Vec<usize> {
+    find_all_matches(requested, available, index, strategy)
+        .into_iter()
+        .map(|m| m.available)
+        .collect()
+}
+
+/// Returns the matches of the supported available locales, in the order
+/// of [`ResultOrder::RequestedPriority`].
+fn find_all_matches<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    index: &AvailableIndex,
+    strategy: NegotiationStrategy,
+) -> Vec<Match> {
+    find_all_matches_with(
+        requested,
+        available,
+        index,
+        strategy,
+        VariantRules::default(),
+    )
+}
+
+fn find_all_matches_with<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    index: &AvailableIndex,
+    strategy: NegotiationStrategy,
+    rules: VariantRules,
+) -> Vec<Match> {
+    let mut sink = VecSink {
+        matched: vec![false; available.len()],
+        matches: vec![],
+    };
+    // The result is allocated anyway, so the scores are sorted in a buffer
+    // rather than scanned once per score.
+    find_matches_in(
+        requested,
+        available,
+        Some(index),
+        strategy,
+        rules,
+        Some(&mut vec![]),
+        &mut sink,
+    );
+    #[cfg(feature = "invariants")]
+    invariants::check(requested, available, strategy, &sink.matches);
+    sink.matches
+}
+
+/// Same as [`filter_matches`], but returns a mask over the available
+/// locales, where `true` marks a supported one.
+///
+/// The order of the supported locales is lost, but masks of different
+/// negotiations over the same available locales can be combined cheaply.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::filter_matches_mask;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let available = [langid!("en-US"), langid!("de"), langid!("en-GB"), langid!("fr")];
+///
+/// let mask1 = filter_matches_mask(&[langid!("en"), langid!("de")], &available, NegotiationStrategy::Filtering);
+/// let mask2 = filter_matches_mask(&[langid!("fr"), langid!("de")], &available, NegotiationStrategy::Filtering);
+/// assert_eq!(mask1, &[true, true, true, false]);
+///
+/// let both: Vec<bool> = mask1.iter().zip(&mask2).map(|(a, b)| *a && *b).collect();
+/// assert_eq!(both, &[false, true, false, false]);
+/// ```
+pub fn filter_matches_mask<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    strategy: NegotiationStrategy,
+) -> Vec<bool> {
+    let index = AvailableIndex::new(available);
+    filter_mask(requested, available, &index, strategy)
+}
+
+fn filter_mask<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    index: &AvailableIndex,
+    strategy: NegotiationStrategy,
+) -> Vec<bool> {
+    let mut sink = MaskSink {
+        matched: vec![false; available.len()],
+    };
+    find_matches(requested, available, Some(index), strategy, &mut sink);
+    sink.matched
+}
+
+/// Negotiates each of the lists of requested locales against the same
+/// available locales, in parallel.
+///
+/// The available locales are prepared once and shared between the
+/// negotiations, see [`AvailableLocales::negotiate_many`].
+///
+/// This function is only available with the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub fn negotiate_many<'a, Q, R, A>(
+    requests: &[Q],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<Vec<&'a A>>
+where
+    Q: AsRef<[R]> + Sync,
+    R: AsRef<LanguageIdentifier>,
+    A: AsRef<LanguageIdentifier> + PartialEq + Sync,
+{
+    AvailableLocales::new(available).negotiate_many(requests, default, strategy)
+}
+
+/// Returns `true` if the requested locale has any match in the available
+/// locales.
+///
+/// This runs the same matching as negotiation, but stops at the first
+/// match and doesn't allocate.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::is_supported;
+/// use icu_locid::langid;
+///
+/// let rtl_ui = [langid!("ar"), langid!("he-IL"), langid!("fa")];
+///
+/// assert!(is_supported(&langid!("he"), &rtl_ui));
+/// assert!(is_supported(&langid!("ar-EG"), &rtl_ui));
+/// assert!(!is_supported(&langid!("de"), &rtl_ui));
+/// ```
+pub fn is_supported<A: AsRef<LanguageIdentifier>>(
+    requested: &LanguageIdentifier,
+    available: &[A],
+) -> bool {
+    supported_in(requested, available, None)
+}
 
-        // 4) Try to match against a variant as a range
-        req.variants.clear();
-        test_strategy!(req, true, true);
+/// Returns a key identifying the result of the negotiation, for example to
+/// vary cached responses on.
+///
+/// The key is the list of supported locales in their canonical form, joined
+/// by `,`, so all the lists of requested locales which negotiate to the same
+/// result share the same key. It is empty if no locale is supported.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::convert_vec_str_to_langids_lossy;
+/// use fluent_langneg::negotiate::cache_key;
+/// use fluent_langneg::NegotiationStrategy;
+///
+/// let available = convert_vec_str_to_langids_lossy(["en_us", "de", "fr"]);
+///
+/// let key = |requested| {
+///     let requested = convert_vec_str_to_langids_lossy(requested);
+///     cache_key(&requested, &available, NegotiationStrategy::Filtering)
+/// };
+/// assert_eq!(key(["de-AT", "en"]), "de,en-US");
+/// assert_eq!(key(["de-CH", "en-GB"]), "de,en-US");
+/// assert_eq!(key(["ja", "it"]), "");
+/// ```
+pub fn cache_key<R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    available: &[A],
+    strategy: NegotiationStrategy,
+) -> String {
+    join_key(filter_matches(requested, available, strategy))
+}
 
-        // 5) Try to match against the likely subtag without region
-        req.region = None;
-        if lc.maximize(&mut req) == TransformResult::Modified {
-            test_strategy!(req, true, false);
+fn join_key<'a, A: 'a + AsRef<LanguageIdentifier>>(
+    supported: impl IntoIterator<Item = &'a A>,
+) -> String {
+    let mut key = String::new();
+    for (i, langid) in supported.into_iter().enumerate() {
+        if i > 0 {
+            key.push(',');
         }
+        // Writing to a `String` can't fail.
+        let _ = write!(key, "{}", langid.as_ref());
+    }
+    key
+}
+
+pub(crate) fn supported_in<A: AsRef<LanguageIdentifier>>(
+    requested: &LanguageIdentifier,
+    available: &[A],
+    index: Option<&AvailableIndex>,
+) -> bool {
+    let mut sink = FoundSink(false);
+    find_matches(
+        std::slice::from_ref(requested),
+        available,
+        index,
+        NegotiationStrategy::Lookup,
+        &mut sink,
+    );
+    sink.0
+}
 
-        // 6) Try to match against a region as a range
-        req.region = None;
-        test_strategy!(req, true, true);
+/// Same as [`filter_matches`], but writes the result into the `out` buffer
+/// instead of allocating, and returns the number of supported locales.
+///
+/// Negotiation stops once the buffer is full.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::filter_matches_into;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let requested = [langid!("de-AT"), langid!("en")];
+/// let available = [langid!("en-US"), langid!("de"), langid!("en-GB")];
+///
+/// let mut out = [None; 2];
+/// let len = filter_matches_into(&requested, &available, NegotiationStrategy::Filtering, &mut out);
+/// assert_eq!(len, 2);
+/// assert_eq!(out, [Some(&available[1]), Some(&available[0])]);
+/// ```
+pub fn filter_matches_into<
+    'a,
+    R: 'a + AsRef<LanguageIdentifier>,
+    A: 'a + AsRef<LanguageIdentifier>,
+>(
+    requested: &[R],
+    available: &'a [A],
+    strategy: NegotiationStrategy,
+    out: &mut [Option<&'a A>],
+) -> usize {
+    if out.is_empty() {
+        return 0;
     }
+    let mut sink = BufferSink {
+        available,
+        out,
+        len: 0,
+    };
+    find_matches(requested, available, None, strategy, &mut sink);
+    sink.len
+}
 
-    supported_locales
+/// Same as [`negotiate_languages`], but writes the result into the `out`
+/// buffer instead of allocating, and returns the number of supported locales.
+///
+/// If the buffer is full, the default locale is not added.
+pub fn negotiate_languages_into<
+    'a,
+    R: 'a + AsRef<LanguageIdentifier>,
+    A: 'a + AsRef<LanguageIdentifier> + PartialEq,
+>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+    out: &mut [Option<&'a A>],
+) -> usize {
+    let mut len = filter_matches_into(requested, available, strategy, out);
+    if let Some(default) = default {
+        let add = if strategy == NegotiationStrategy::Lookup {
+            len == 0
+        } else {
+            !out.iter().take(len).any(|m| *m == Some(default))
+        };
+        if let Some(slot) = out.get_mut(len).filter(|_| add) {
+            *slot = Some(default);
+            len += 1;
+        }
+    }
+    len
 }
 
 pub fn negotiate_languages<
@@ -249,7 +922,103 @@ pub fn negotiate_languages<
     strategy: NegotiationStrategy,
 ) -> Vec<&'a A> {
     let mut supported = filter_matches(requested, available, strategy);
+    add_default(&mut supported, default, strategy);
+    supported
+}
 
+/// Same as [`negotiate_languages`], configured with [`NegotiationOptions`].
+pub fn negotiate_languages_with<
+    'a,
+    R: 'a + AsRef<LanguageIdentifier>,
+    A: 'a + AsRef<LanguageIdentifier> + PartialEq,
+>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    options: &NegotiationOptions,
+) -> Vec<&'a A> {
+    let index = AvailableIndex::new(available);
+    let rules = VariantRules {
+        prefer_exact: options.prefer_exact_variants,
+        private: options.private_variants,
+    };
+    let mut matches = find_all_matches_with(requested, available, &index, options.strategy, rules);
+    match options.order {
+        ResultOrder::RequestedPriority => {}
+        ResultOrder::AvailablePriority => matches.sort_by_key(|m| m.available),
+        ResultOrder::MatchQuality => matches.sort_by_key(|m| m.score),
+    }
+    let mut supported: Vec<&A> = matches
+        .iter()
+        .filter_map(|m| available.get(m.available))
+        .collect();
+    if let Some(default) = default {
+        insert_default(&mut supported, requested, default, options);
+    }
+    if options.dedup {
+        dedup(&mut supported);
+    }
+    supported
+}
+
+fn insert_default<'a, R: AsRef<LanguageIdentifier>, A: AsRef<LanguageIdentifier> + PartialEq>(
+    supported: &mut Vec<&'a A>,
+    requested: &[R],
+    default: &'a A,
+    options: &NegotiationOptions,
+) {
+    let covered = |supported: &[&A]| {
+        if options.skip_covered_default {
+            let language = LanguageIdentifier::from(default.as_ref().language);
+            supported
+                .iter()
+                .any(|locale| matches(&language, (*locale).as_ref(), true, false))
+        } else {
+            supported.contains(&default)
+        }
+    };
+    let add = if options.strategy == NegotiationStrategy::Lookup {
+        supported.is_empty() || (options.always_add_default && !covered(supported))
+    } else {
+        !covered(supported)
+    };
+    if !add {
+        return;
+    }
+
+    let index = match options.default_placement {
+        DefaultPlacement::Prepend => 0,
+        DefaultPlacement::AfterExactMatches => supported
+            .iter()
+            .rposition(|locale| {
+                requested
+                    .iter()
+                    .any(|req| req.as_ref() == (*locale).as_ref())
+            })
+            .map_or(0, |idx| idx + 1),
+        DefaultPlacement::Append => supported.len(),
+    };
+    supported.insert(index, default);
+}
+
+fn dedup<A: AsRef<LanguageIdentifier>>(supported: &mut Vec<&A>) {
+    let mut seen: Vec<&LanguageIdentifier> = Vec::with_capacity(supported.len());
+    supported.retain(|locale| {
+        let langid = (*locale).as_ref();
+        if seen.contains(&langid) {
+            false
+        } else {
+            seen.push(langid);
+            true
+        }
+    });
+}
+
+fn add_default<'a, A: PartialEq>(
+    supported: &mut Vec<&'a A>,
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) {
     if let Some(default) = default {
         if strategy == NegotiationStrategy::Lookup {
             if supported.is_empty() {
@@ -259,5 +1028,292 @@ pub fn negotiate_languages<
             supported.push(default);
         }
     }
+}
+
+/// An available tag converted for matching, remembering its position.
+struct IndexedLangId {
+    langid: LanguageIdentifier,
+    index: usize,
+}
+
+impl AsRef<LanguageIdentifier> for IndexedLangId {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        &self.langid
+    }
+}
+
+/// Negotiates between lists of any types implementing [`LangTag`].
+///
+/// This is a convenience wrapper over [`negotiate_languages`]: each call
+/// converts all the tags with [`LangTag::to_langid`] and maps the result
+/// back to the available tags, which costs as much as converting them by
+/// hand. When negotiating repeatedly against the same available tags,
+/// convert them once instead. Tags which cannot be represented as
+/// a [`LanguageIdentifier`] never match.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::negotiate_tags;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::{langid, locale};
+///
+/// let requested = [locale!("de-AT-u-hc-h23"), locale!("en")];
+/// let available = [langid!("en-US"), langid!("de")];
+///
+/// let supported = negotiate_tags(&requested, &available, None, NegotiationStrategy::Filtering);
+/// assert_eq!(supported, &[&available[1], &available[0]]);
+/// ```
+pub fn negotiate_tags<'a, R: LangTag, A: LangTag + PartialEq>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let requested: Vec<LanguageIdentifier> =
+        requested.iter().filter_map(LangTag::to_langid).collect();
+    negotiate_converted(&requested, available, A::to_langid, default, strategy)
+}
+
+/// Negotiates over available items of any type, which are converted
+/// into [`LanguageIdentifier`]s for matching.
+pub(crate) fn negotiate_converted<'a, A: PartialEq>(
+    requested: &[LanguageIdentifier],
+    available: &'a [A],
+    to_langid: impl Fn(&A) -> Option<LanguageIdentifier>,
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let indexed: Vec<IndexedLangId> = available
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| to_langid(item).map(|langid| IndexedLangId { langid, index }))
+        .collect();
+
+    let mut supported: Vec<&A> = filter_matches(requested, &indexed, strategy)
+        .into_iter()
+        .filter_map(|indexed| available.get(indexed.index))
+        .collect();
+    add_default(&mut supported, default, strategy);
+    supported
+}
+
+/// Negotiates between lists of language tags given as strings.
+///
+/// Entries which fail to parse, including the default, are skipped, and
+/// the result is serialized back into canonical strings. This is a single
+/// call for FFI layers, scripts and quick integrations which don't need
+/// to operate on [`LanguageIdentifier`]s.
+///
+/// With the `Matching` and `Lookup` strategies, requested locales available
+/// verbatim (ignoring case and the `-`/`_` separators) are matched without
+/// parsing the available locales at all.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::{negotiate, NegotiationStrategy};
+///
+/// let supported = negotiate(
+///     &["de-AT", "fr_CA"],
+///     &["en-US", "fr", "de"],
+///     Some("en-US"),
+///     NegotiationStrategy::Filtering,
+/// );
+/// assert_eq!(supported, &["de", "fr", "en-US"]);
+/// ```
+pub fn negotiate<R: AsRef<[u8]>, A: AsRef<[u8]>>(
+    requested: &[R],
+    available: &[A],
+    default: Option<&str>,
+    strategy: NegotiationStrategy,
+) -> Vec<String> {
+    let (raw, requested): (Vec<&[u8]>, Vec<LanguageIdentifier>) = requested
+        .iter()
+        .filter_map(|tag| {
+            let tag = tag.as_ref();
+            Some((tag, LanguageIdentifier::try_from_bytes(tag).ok()?))
+        })
+        .unzip();
+    let default: Option<LanguageIdentifier> = default.and_then(|d| d.parse().ok());
+
+    let parsed;
+    let supported = match negotiate_verbatim(&raw, &requested, available, strategy) {
+        Some(mut supported) => {
+            add_default(&mut supported, default.as_ref(), strategy);
+            supported
+        }
+        None => {
+            parsed = crate::convert_vec_str_to_langids_lossy(available);
+            negotiate_languages(&requested, &parsed, default.as_ref(), strategy)
+        }
+    };
+    supported
+        .into_iter()
+        .map(|langid| langid.to_string())
+        .collect()
+}
+
+/// Negotiates between lists of language tags given as strings, and returns
+/// the available tags as they are spelled in the list.
+///
+/// Each requested tag first matches the available tags equal to it, ignoring
+/// case and the `-`/`_` separators, even if they fail to parse, such as legacy
+/// tags like `i-klingon`. Only then are the parsed tags negotiated as usual,
+/// and unparsable ones are skipped.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::negotiate_raw;
+/// use fluent_langneg::NegotiationStrategy;
+///
+/// let available = ["EN_us", "de_DE", "x-legacy-zh"];
+///
+/// let supported = negotiate_raw(
+///     &["X-LEGACY-ZH", "de-AT", "en"],
+///     &available,
+///     Some(&"EN_us"),
+///     NegotiationStrategy::Filtering,
+/// );
+/// assert_eq!(supported, &[&"x-legacy-zh", &"de_DE", &"EN_us"]);
+/// ```
+pub fn negotiate_raw<'a, R: AsRef<[u8]>, A: AsRef<[u8]> + PartialEq>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let indexed: Vec<IndexedLangId> = available
+        .iter()
+        .enumerate()
+        .filter_map(|(index, tag)| {
+            let langid = LanguageIdentifier::try_from_bytes(tag.as_ref()).ok()?;
+            Some(IndexedLangId { langid, index })
+        })
+        .collect();
+    let index = AvailableIndex::new(&indexed);
+
+    let mut sink = RawSink {
+        indexed: &indexed,
+        matched: vec![false; available.len()],
+        found: vec![],
+    };
+    for raw in requested {
+        let raw = raw.as_ref();
+        let before = sink.found.len();
+        for (idx, tag) in available.iter().enumerate() {
+            if !sink.is_matched_raw(idx) && tags_eq(raw, tag.as_ref()) {
+                sink.push_raw(idx);
+                if strategy != NegotiationStrategy::Filtering {
+                    break;
+                }
+            }
+        }
+        let found = sink.found.len() > before;
+        if !found || strategy == NegotiationStrategy::Filtering {
+            if let Ok(langid) = LanguageIdentifier::try_from_bytes(raw) {
+                find_matches(&[langid], &indexed, Some(&index), strategy, &mut sink);
+            }
+        }
+        if strategy == NegotiationStrategy::Lookup && sink.found.len() > before {
+            break;
+        }
+    }
+
+    let mut supported: Vec<&A> = sink
+        .found
+        .iter()
+        .filter_map(|&idx| available.get(idx))
+        .collect();
+    add_default(&mut supported, default, strategy);
     supported
 }
+
+/// Records the matches of [`negotiate_raw`] as indices of the original
+/// available tags.
+struct RawSink<'s> {
+    indexed: &'s [IndexedLangId],
+    matched: Vec<bool>,
+    found: Vec<usize>,
+}
+
+impl RawSink<'_> {
+    fn is_matched_raw(&self, index: usize) -> bool {
+        self.matched.get(index).copied().unwrap_or(true)
+    }
+
+    fn push_raw(&mut self, index: usize) {
+        if let Some(matched) = self.matched.get_mut(index) {
+            *matched = true;
+            self.found.push(index);
+        }
+    }
+}
+
+impl MatchSink for RawSink<'_> {
+    fn is_matched(&self, index: usize) -> bool {
+        self.indexed
+            .get(index)
+            .is_none_or(|indexed| self.is_matched_raw(indexed.index))
+    }
+
+    fn push(&mut self, m: Match) -> bool {
+        if let Some(indexed) = self.indexed.get(m.available) {
+            self.push_raw(indexed.index);
+        }
+        true
+    }
+}
+
+/// Resolves the negotiation with exact matches found by comparing the
+/// strings, without parsing the available locales.
+///
+/// This is only possible for the `Matching` and `Lookup` strategies, when
+/// each requested locale which takes part in the negotiation is available
+/// verbatim. Otherwise `None` is returned, and the full negotiation is needed.
+fn negotiate_verbatim<'a, A: AsRef<[u8]>>(
+    raw: &[&[u8]],
+    requested: &'a [LanguageIdentifier],
+    available: &[A],
+    strategy: NegotiationStrategy,
+) -> Option<Vec<&'a LanguageIdentifier>> {
+    let count = match strategy {
+        NegotiationStrategy::Filtering => return None,
+        NegotiationStrategy::Matching => requested.len(),
+        NegotiationStrategy::Lookup => requested.len().min(1),
+    };
+
+    let mut supported = Vec::with_capacity(count);
+    for (idx, (raw, langid)) in raw.iter().zip(requested).take(count).enumerate() {
+        // A repeated request would be matched by the looser levels, since
+        // its exact match is already taken.
+        if !is_verbatim_candidate(raw)
+            || requested.iter().take(idx).any(|req| req == langid)
+            || !available.iter().any(|tag| tags_eq(raw, tag.as_ref()))
+        {
+            return None;
+        }
+        supported.push(langid);
+    }
+    Some(supported)
+}
+
+/// Returns `true` if the tag has no variants, so that any tag parsing into
+/// the same language identifier only differs from it in case and separators.
+fn is_verbatim_candidate(tag: &[u8]) -> bool {
+    tag.split(|b| *b == b'-' || *b == b'_')
+        .all(|subtag| match subtag {
+            [first, _, _, _] => !first.is_ascii_digit(),
+            _ => subtag.len() < 4,
+        })
+}
+
+fn tags_eq(tag1: &[u8], tag2: &[u8]) -> bool {
+    let is_separator = |b: &u8| *b == b'-' || *b == b'_';
+    tag1.len() == tag2.len()
+        && tag1
+            .iter()
+            .zip(tag2)
+            .all(|(b1, b2)| b1.eq_ignore_ascii_case(b2) || (is_separator(b1) && is_separator(b2)))
+}
@@ -0,0 +1,178 @@
+use super::NegotiationStrategy;
+
+/// Configuration of a negotiation.
+///
+/// The options are built with `const` methods, so the configuration
+/// of a service can be defined as a `static`.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::{negotiate_languages_with, NegotiationOptions};
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// static OPTIONS: NegotiationOptions =
+///     NegotiationOptions::new().strategy(NegotiationStrategy::Lookup);
+///
+/// let available = [langid!("en-US"), langid!("de")];
+/// let supported = negotiate_languages_with(&[langid!("de-AT")], &available, None, &OPTIONS);
+/// assert_eq!(supported, &[&available[1]]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct NegotiationOptions {
+    pub strategy: NegotiationStrategy,
+    /// Whether to remove supported locales equal to an earlier one, such as
+    /// `en_US` after `en-US`. The first occurrence is kept.
+    pub dedup: bool,
+    /// Where the default locale is inserted into the result.
+    pub default_placement: DefaultPlacement,
+    /// Whether the `Lookup` strategy adds the default locale even when
+    /// a match has been found.
+    pub always_add_default: bool,
+    /// Whether to skip the default locale when a supported locale has
+    /// the same language, such as `en-US` after `en-GB`.
+    pub skip_covered_default: bool,
+    /// Order of the supported locales.
+    pub order: ResultOrder,
+    /// Whether available locales with the variants of a requested locale
+    /// rank before all its other matches, such as `de-DE-1901` before `de`
+    /// for `de-1901`, instead of only within a step of the algorithm.
+    pub prefer_exact_variants: bool,
+    /// Whether available locales with a variant which isn't registered,
+    /// see [`is_registered_variant`](crate::subtags::is_registered_variant),
+    /// only match requested locales with that variant, so that a locale
+    /// such as `en-US-acmecorp` is only supported when asked for.
+    pub private_variants: bool,
+}
+
+impl NegotiationOptions {
+    pub const fn new() -> Self {
+        Self {
+            strategy: NegotiationStrategy::Filtering,
+            dedup: false,
+            default_placement: DefaultPlacement::Append,
+            always_add_default: false,
+            skip_covered_default: false,
+            order: ResultOrder::RequestedPriority,
+            prefer_exact_variants: false,
+            private_variants: false,
+        }
+    }
+
+    pub const fn strategy(mut self, strategy: NegotiationStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub const fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    pub const fn default_placement(mut self, placement: DefaultPlacement) -> Self {
+        self.default_placement = placement;
+        self
+    }
+
+    pub const fn always_add_default(mut self, always: bool) -> Self {
+        self.always_add_default = always;
+        self
+    }
+
+    pub const fn skip_covered_default(mut self, skip: bool) -> Self {
+        self.skip_covered_default = skip;
+        self
+    }
+
+    pub const fn order(mut self, order: ResultOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub const fn prefer_exact_variants(mut self, prefer: bool) -> Self {
+        self.prefer_exact_variants = prefer;
+        self
+    }
+
+    pub const fn private_variants(mut self, private: bool) -> Self {
+        self.private_variants = private;
+        self
+    }
+}
+
+/// Position at which the default locale is inserted into the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum DefaultPlacement {
+    /// After all the supported locales.
+    #[default]
+    Append,
+    /// Before all the supported locales.
+    Prepend,
+    /// After the last supported locale equal to one of the requested ones,
+    /// or first if there is no such locale.
+    AfterExactMatches,
+}
+
+/// Order of the supported locales in the result.
+///
+/// Each order is stable, so locales which compare equal keep
+/// their relative order from [`ResultOrder::RequestedPriority`].
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::{negotiate_languages_with, NegotiationOptions, ResultOrder};
+/// use icu_locid::langid;
+///
+/// let requested = [langid!("de-AT"), langid!("en-GB")];
+/// let available = [langid!("en-GB"), langid!("de"), langid!("en-US")];
+///
+/// let supported = |order| {
+///     let options = NegotiationOptions::new().order(order);
+///     negotiate_languages_with(&requested, &available, None, &options)
+/// };
+/// assert_eq!(
+///     supported(ResultOrder::RequestedPriority),
+///     &[&available[1], &available[0], &available[2]]
+/// );
+/// assert_eq!(
+///     supported(ResultOrder::AvailablePriority),
+///     &[&available[0], &available[1], &available[2]]
+/// );
+/// assert_eq!(
+///     supported(ResultOrder::MatchQuality),
+///     &[&available[0], &available[1], &available[2]]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum ResultOrder {
+    /// Locales matching an earlier requested locale come first, and
+    /// the matches of each requested locale are ordered from the best one.
+    #[default]
+    RequestedPriority,
+    /// Locales are in the order of the available locales.
+    AvailablePriority,
+    /// Better matches come first, regardless of the requested locale
+    /// they match, so an exact match of the second requested locale
+    /// precedes a fallback for the first one.
+    MatchQuality,
+}
+
+impl Default for NegotiationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<NegotiationStrategy> for NegotiationOptions {
+    fn from(strategy: NegotiationStrategy) -> Self {
+        Self::new().strategy(strategy)
+    }
+}
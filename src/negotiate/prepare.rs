@@ -0,0 +1,51 @@
+use icu_locid::LanguageIdentifier;
+
+use crate::canonicalize;
+use crate::subtags::is_within;
+
+/// Sanitizes a list of requested locales before negotiation.
+///
+/// The locales are canonicalized with [`canonicalize::langid`], and
+/// duplicates are removed, keeping the first occurrence.
+///
+/// Broader entries following a more specific one they cover, such as `en`
+/// after `en-GB`, are removed as well, since negotiation already falls back
+/// from `en-GB` to other English locales. Use [`prepare_requested_with`]
+/// to keep them.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::prepare_requested;
+/// use fluent_langneg::convert_vec_str_to_langids_lossy;
+///
+/// let requested = convert_vec_str_to_langids_lossy(["en-GB", "iw", "en", "he", "de-DD", "de"]);
+/// assert_eq!(
+///     prepare_requested(&requested),
+///     convert_vec_str_to_langids_lossy(["en-GB", "he", "de-DE"])
+/// );
+/// ```
+pub fn prepare_requested<R: AsRef<LanguageIdentifier>>(requested: &[R]) -> Vec<LanguageIdentifier> {
+    prepare_requested_with(requested, true)
+}
+
+/// Same as [`prepare_requested`], but broader entries following a more
+/// specific one are only removed if `drop_broader` is set.
+pub fn prepare_requested_with<R: AsRef<LanguageIdentifier>>(
+    requested: &[R],
+    drop_broader: bool,
+) -> Vec<LanguageIdentifier> {
+    let mut result: Vec<LanguageIdentifier> = Vec::with_capacity(requested.len());
+    for langid in requested {
+        let mut langid = langid.as_ref().clone();
+        canonicalize::langid(&mut langid);
+
+        let redundant = result
+            .iter()
+            .any(|prev| *prev == langid || (drop_broader && is_within(prev, &langid)));
+        if !redundant {
+            result.push(langid);
+        }
+    }
+    result
+}
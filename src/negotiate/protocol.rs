@@ -0,0 +1,105 @@
+//! Serializable negotiation requests and responses.
+//!
+//! Applications running negotiation in another process, or behind an RPC
+//! service, exchange a [`NegotiationRequest`] and a [`NegotiationResponse`]
+//! instead of maintaining their own protocol. Both serialize with `serde`,
+//! locales being represented by their tags.
+//!
+//! This module is only available with the `serde` feature.
+
+use icu_locid::LanguageIdentifier;
+use serde::{Deserialize, Serialize};
+
+use super::{explain_negotiation, negotiate_languages, Explanation, NegotiationStrategy};
+
+/// The input of a negotiation.
+///
+/// Only `requested` and `available` are required when deserializing.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::NegotiationRequest;
+/// use icu_locid::langid;
+///
+/// let request: NegotiationRequest = serde_json::from_str(
+///     r#"{"requested": ["de-AT"], "available": ["en-US", "de"], "default": "en-US"}"#,
+/// )
+/// .unwrap();
+///
+/// let response = request.negotiate();
+/// assert_eq!(response.supported, &[langid!("de"), langid!("en-US")]);
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"supported":["de","en-US"]}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegotiationRequest {
+    pub requested: Vec<LanguageIdentifier>,
+    pub available: Vec<LanguageIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<LanguageIdentifier>,
+    #[serde(default)]
+    pub strategy: NegotiationStrategy,
+    /// Whether the response includes an [`Explanation`].
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub explain: bool,
+}
+
+/// The output of a negotiation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegotiationResponse {
+    /// The supported locales, as returned by [`negotiate_languages`].
+    pub supported: Vec<LanguageIdentifier>,
+    /// The explanation of the negotiation, if the request asked for one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Explanation>,
+}
+
+impl NegotiationRequest {
+    pub fn new(
+        requested: Vec<LanguageIdentifier>,
+        available: Vec<LanguageIdentifier>,
+        default: Option<LanguageIdentifier>,
+        strategy: NegotiationStrategy,
+    ) -> Self {
+        Self {
+            requested,
+            available,
+            default,
+            strategy,
+            explain: false,
+        }
+    }
+
+    /// Runs the negotiation.
+    pub fn negotiate(&self) -> NegotiationResponse {
+        if self.explain {
+            let explanation = explain_negotiation(
+                &self.requested,
+                &self.available,
+                self.default.as_ref(),
+                self.strategy,
+            );
+            return NegotiationResponse {
+                supported: explanation.supported.clone(),
+                explanation: Some(explanation),
+            };
+        }
+        let supported = negotiate_languages(
+            &self.requested,
+            &self.available,
+            self.default.as_ref(),
+            self.strategy,
+        );
+        NegotiationResponse {
+            supported: supported.into_iter().cloned().collect(),
+            explanation: None,
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+use std::str::FromStr;
+
+use icu_locid::subtags::{Language, Region, Script};
+use icu_locid::{LanguageIdentifier, ParserError};
+
+use super::index::AvailableIndex;
+use super::{find_all_matches, score, NegotiationStrategy};
+use crate::Error;
+
+/// An explicit language range on the available side, such as `es-*`,
+/// for locales which are supported without being available as such,
+/// for example through machine translation.
+///
+/// Each of the language, script and region is either a subtag or
+/// a `*` wildcard, and trailing subtags left out are wildcards as well.
+/// Variants are never restricted.
+///
+/// Unlike an available locale, which is only treated as a range when
+/// nothing closer is available, a range never takes precedence over
+/// an available locale, see [`filter_matches_with_ranges`].
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::LanguageRange;
+/// use icu_locid::langid;
+///
+/// let range: LanguageRange = "es-*".parse().unwrap();
+/// assert!(range.matches(&langid!("es-MX")));
+/// assert!(!range.matches(&langid!("pt-BR")));
+///
+/// let range: LanguageRange = "*-Cyrl".parse().unwrap();
+/// assert!(range.matches(&langid!("sr-Cyrl-RS")));
+/// assert_eq!(range.to_string(), "*-Cyrl");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LanguageRange {
+    /// The language, or `None` for any language.
+    pub language: Option<Language>,
+    /// The script, or `None` for any script.
+    pub script: Option<Script>,
+    /// The region, or `None` for any region.
+    pub region: Option<Region>,
+}
+
+impl LanguageRange {
+    /// Returns `true` if the language identifier is within the range.
+    pub fn matches(&self, langid: &LanguageIdentifier) -> bool {
+        self.language.is_none_or(|l| l == langid.language)
+            && self.script.is_none_or(|s| Some(s) == langid.script)
+            && self.region.is_none_or(|r| Some(r) == langid.region)
+    }
+}
+
+impl FromStr for LanguageRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(|source| Error::parse(s.as_bytes(), source))
+    }
+}
+
+impl LanguageRange {
+    fn parse(s: &str) -> Result<Self, ParserError> {
+        let mut subtags = s.split(['-', '_']);
+        let mut range = Self::default();
+
+        match subtags.next() {
+            Some("*") => {}
+            Some(subtag) => range.language = Some(Language::try_from_bytes(subtag.as_bytes())?),
+            None => return Err(ParserError::InvalidLanguage),
+        }
+
+        // A wildcard in the place of the script may be followed by a region.
+        let mut next = subtags.next();
+        if let Some(subtag) = next {
+            if subtag == "*" {
+                next = subtags.next();
+            } else if let Ok(script) = Script::try_from_bytes(subtag.as_bytes()) {
+                range.script = Some(script);
+                next = subtags.next();
+            }
+        }
+        if let Some(subtag) = next {
+            if subtag != "*" {
+                range.region = Some(Region::try_from_bytes(subtag.as_bytes())?);
+            }
+            next = subtags.next();
+        }
+        match next {
+            Some(_) => Err(ParserError::InvalidSubtag),
+            None => Ok(range),
+        }
+    }
+}
+
+impl fmt::Display for LanguageRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.language {
+            Some(language) => language.fmt(f)?,
+            None => f.write_str("*")?,
+        }
+        match (self.script, self.region) {
+            (Some(script), _) => write!(f, "-{}", script)?,
+            (None, Some(_)) => f.write_str("-*")?,
+            (None, None) => {}
+        }
+        if let Some(region) = self.region {
+            write!(f, "-{}", region)?;
+        }
+        Ok(())
+    }
+}
+
+/// A supported locale of [`filter_matches_with_ranges`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupportedLocale<'a, A> {
+    /// An available locale.
+    Available(&'a A),
+    /// A requested locale, supported through a range.
+    Range {
+        requested: &'a LanguageIdentifier,
+        range: &'a LanguageRange,
+    },
+}
+
+/// Same as [`super::filter_matches`], but requested locales within one of
+/// the `ranges` are supported as well.
+///
+/// A requested locale within a range is added right after the available
+/// locales it matches, unless one of them is equal to it. So for each
+/// requested locale, available locales come first, but a range match
+/// of a requested locale still precedes the matches of the following ones.
+///
+/// With the `Matching` strategy, the range is only used for requested
+/// locales without a match, and with `Lookup`, only if the first requested
+/// locale with a match has none.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::{filter_matches_with_ranges, LanguageRange, SupportedLocale};
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let requested = [langid!("es-MX"), langid!("en-GB")];
+/// let available = [langid!("en-US"), langid!("es-ES")];
+/// let ranges: [LanguageRange; 1] = ["es-*".parse().unwrap()];
+///
+/// let supported = filter_matches_with_ranges(
+///     &requested,
+///     &available,
+///     &ranges,
+///     NegotiationStrategy::Filtering,
+/// );
+/// assert_eq!(
+///     supported,
+///     &[
+///         SupportedLocale::Available(&available[1]),
+///         SupportedLocale::Range { requested: &requested[0], range: &ranges[0] },
+///         SupportedLocale::Available(&available[0]),
+///     ]
+/// );
+/// ```
+pub fn filter_matches_with_ranges<
+    'a,
+    R: AsRef<LanguageIdentifier>,
+    A: AsRef<LanguageIdentifier>,
+>(
+    requested: &'a [R],
+    available: &'a [A],
+    ranges: &'a [LanguageRange],
+    strategy: NegotiationStrategy,
+) -> Vec<SupportedLocale<'a, A>> {
+    let index = AvailableIndex::new(available);
+    let matches = find_all_matches(requested, available, &index, strategy);
+    let range_match = |requested: &'a LanguageIdentifier| {
+        ranges
+            .iter()
+            .find(|range| range.matches(requested))
+            .map(|range| SupportedLocale::Range { requested, range })
+    };
+
+    let mut result = Vec::with_capacity(matches.len() + 1);
+    let mut matches = matches.iter().peekable();
+    for (req_idx, req) in requested.iter().enumerate() {
+        let mut exact = false;
+        let mut found = false;
+        while let Some(m) = matches.next_if(|m| m.requested == req_idx) {
+            exact |= m.score == score::EXACT;
+            found = true;
+            if let Some(locale) = available.get(m.available) {
+                result.push(SupportedLocale::Available(locale));
+            }
+        }
+
+        let wanted = match strategy {
+            NegotiationStrategy::Filtering => !exact,
+            NegotiationStrategy::Matching | NegotiationStrategy::Lookup => !found,
+        };
+        if wanted {
+            if let Some(range) = range_match(req.as_ref()) {
+                result.push(range);
+                found = true;
+            }
+        }
+        if found && strategy == NegotiationStrategy::Lookup {
+            break;
+        }
+    }
+    result
+}
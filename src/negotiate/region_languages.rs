@@ -0,0 +1,152 @@
+use icu_locid::subtags::{language, region, Language, Region};
+use icu_locid::LanguageIdentifier;
+
+use super::{LocaleExpander, TransformResult};
+
+/// Official languages of multilingual regions, from the CLDR territory
+/// information, by decreasing population. Sorted by the region.
+static REGION_LANGUAGES: &[(Region, &[Language])] = &[
+    (region!("AF"), &[language!("fa"), language!("ps")]),
+    (
+        region!("BE"),
+        &[language!("nl"), language!("fr"), language!("de")],
+    ),
+    (
+        region!("BO"),
+        &[language!("es"), language!("qu"), language!("ay")],
+    ),
+    (region!("BY"), &[language!("ru"), language!("be")]),
+    (region!("CA"), &[language!("en"), language!("fr")]),
+    (
+        region!("CH"),
+        &[
+            language!("de"),
+            language!("fr"),
+            language!("it"),
+            language!("rm"),
+        ],
+    ),
+    (region!("CM"), &[language!("fr"), language!("en")]),
+    (region!("CY"), &[language!("el"), language!("tr")]),
+    (
+        region!("ES"),
+        &[
+            language!("es"),
+            language!("ca"),
+            language!("gl"),
+            language!("eu"),
+        ],
+    ),
+    (region!("FI"), &[language!("fi"), language!("sv")]),
+    (region!("IE"), &[language!("en"), language!("ga")]),
+    (region!("IL"), &[language!("he"), language!("ar")]),
+    (
+        region!("IN"),
+        &[
+            language!("hi"),
+            language!("en"),
+            language!("bn"),
+            language!("te"),
+            language!("mr"),
+            language!("ta"),
+        ],
+    ),
+    (region!("KE"), &[language!("sw"), language!("en")]),
+    (region!("KZ"), &[language!("ru"), language!("kk")]),
+    (region!("LK"), &[language!("si"), language!("ta")]),
+    (
+        region!("LU"),
+        &[language!("lb"), language!("fr"), language!("de")],
+    ),
+    (region!("MT"), &[language!("mt"), language!("en")]),
+    (region!("NZ"), &[language!("en"), language!("mi")]),
+    (region!("PH"), &[language!("fil"), language!("en")]),
+    (region!("PK"), &[language!("ur"), language!("en")]),
+    (region!("PY"), &[language!("gn"), language!("es")]),
+    (
+        region!("RW"),
+        &[language!("rw"), language!("en"), language!("fr")],
+    ),
+    (
+        region!("SG"),
+        &[
+            language!("en"),
+            language!("zh"),
+            language!("ms"),
+            language!("ta"),
+        ],
+    ),
+    (region!("TZ"), &[language!("sw"), language!("en")]),
+    (
+        region!("ZA"),
+        &[
+            language!("en"),
+            language!("zu"),
+            language!("xh"),
+            language!("af"),
+        ],
+    ),
+];
+
+/// Returns the languages likely spoken in a region, from the most likely
+/// one, each with the region.
+///
+/// The first language is the likely language of the region, as added by
+/// [`LocaleExpander::maximize`] to `und` with the region, followed by the
+/// other official languages of multilingual regions. With only a region,
+/// for example from GeoIP, the result can be used as the requested locales
+/// of a negotiation.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::likely_languages_for_region;
+/// use icu_locid::langid;
+/// use icu_locid::subtags::region;
+///
+/// assert_eq!(
+///     likely_languages_for_region(region!("CH")),
+///     &[langid!("de-CH"), langid!("fr-CH"), langid!("it-CH"), langid!("rm-CH")]
+/// );
+/// assert_eq!(likely_languages_for_region(region!("FR")), &[langid!("fr-FR")]);
+/// ```
+pub fn likely_languages_for_region(region: Region) -> Vec<LanguageIdentifier> {
+    let mut languages: Vec<Language> = Vec::new();
+
+    let mut likely = LanguageIdentifier {
+        region: Some(region),
+        ..Default::default()
+    };
+    if LocaleExpander::new().maximize(&mut likely) == TransformResult::Modified
+        && !likely.language.is_empty()
+    {
+        languages.push(likely.language);
+    }
+    let idx = REGION_LANGUAGES.binary_search_by(|(r, _)| r.cmp(&region));
+    if let Some((_, region_languages)) = idx.ok().and_then(|idx| REGION_LANGUAGES.get(idx)) {
+        for language in *region_languages {
+            if !languages.contains(language) {
+                languages.push(*language);
+            }
+        }
+    }
+
+    languages
+        .into_iter()
+        .map(|language| {
+            let mut langid = LanguageIdentifier::from(language);
+            langid.region = Some(region);
+            langid
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_languages_sorted() {
+        assert!(REGION_LANGUAGES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
@@ -0,0 +1,113 @@
+use icu_locid::extensions::unicode::key;
+use icu_locid::subtags::Region;
+use icu_locid::{LanguageIdentifier, Locale};
+
+use super::{add_default, filter_matches, NegotiationStrategy};
+
+/// Returns the region of the `-u-rg-` region override of a locale.
+///
+/// The value of the keyword is a subdivision, such as `gbzzzz` for the
+/// whole United Kingdom or `usca` for California, whose region prefix is
+/// returned.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::region_override;
+/// use icu_locid::locale;
+/// use icu_locid::subtags::region;
+///
+/// assert_eq!(region_override(&locale!("en-US-u-rg-gbzzzz")), Some(region!("GB")));
+/// assert_eq!(region_override(&locale!("es-u-rg-419zzzz")), Some(region!("419")));
+/// assert_eq!(region_override(&locale!("en-US")), None);
+/// ```
+pub fn region_override(locale: &Locale) -> Option<Region> {
+    let value = locale
+        .extensions
+        .unicode
+        .keywords
+        .get(&key!("rg"))?
+        .to_string();
+    let len = if value.starts_with(|c: char| c.is_ascii_digit()) {
+        3
+    } else {
+        2
+    };
+    if value.len() <= len {
+        return None;
+    }
+    Region::try_from_bytes(value.get(..len)?.as_bytes()).ok()
+}
+
+/// Same as [`negotiate_languages`], honoring the `-u-rg-` region override
+/// of the requested locales.
+///
+/// A requested locale whose own tag is available still matches it first.
+/// Every other match of the requested locale uses the override region
+/// instead of its own, so that `en-US-u-rg-gbzzzz` prefers `en-GB` over
+/// `en-AU`, and the tag without the override is only tried after it.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::negotiate_with_region_override;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::{langid, locale};
+///
+/// let available = [langid!("en-AU"), langid!("en-GB")];
+/// let supported = negotiate_with_region_override(
+///     &[locale!("en-US-u-rg-gbzzzz")],
+///     &available,
+///     None,
+///     NegotiationStrategy::Lookup,
+/// );
+/// assert_eq!(supported, &[&available[1]]);
+/// ```
+pub fn negotiate_with_region_override<'a, A: AsRef<LanguageIdentifier> + PartialEq>(
+    requested: &[Locale],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let mut supported: Vec<&'a A> = Vec::new();
+    for locale in requested {
+        let base = &locale.id;
+        let remaining: Vec<&'a A> = available
+            .iter()
+            .filter(|a| !supported.iter().any(|s| std::ptr::eq(*s, *a)))
+            .collect();
+
+        let mut found: Vec<&'a A> = Vec::new();
+        let overridden = region_override(locale)
+            .filter(|r| Some(*r) != base.region)
+            .map(|region| {
+                let mut overridden = base.clone();
+                overridden.region = Some(region);
+                overridden
+            });
+        let langids = match overridden {
+            Some(overridden) => {
+                found.extend(remaining.iter().find(|a| a.as_ref() == base));
+                vec![overridden, base.clone()]
+            }
+            None => vec![base.clone()],
+        };
+        for langid in &langids {
+            for a in filter_matches(std::slice::from_ref(langid), &remaining, strategy) {
+                if !found.iter().any(|f| std::ptr::eq(*f, *a)) {
+                    found.push(a);
+                }
+            }
+        }
+
+        if strategy != NegotiationStrategy::Filtering {
+            found.truncate(1);
+        }
+        supported.extend(found);
+        if strategy == NegotiationStrategy::Lookup && !supported.is_empty() {
+            break;
+        }
+    }
+    add_default(&mut supported, default, strategy);
+    supported
+}
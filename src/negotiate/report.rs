@@ -0,0 +1,125 @@
+//! Aggregate statistics of negotiations over a corpus of requests.
+//!
+//! Given the available locales and the lists of requested locales of many
+//! users, for example parsed from the `Accept-Language` headers in access
+//! logs, a [`Report`] tells how well the available locales serve them,
+//! and which missing locales are requested the most.
+
+use std::collections::HashMap;
+
+use icu_locid::LanguageIdentifier;
+
+use super::index::AvailableIndex;
+use super::{find_all_matches, score, NegotiationStrategy};
+
+/// Statistics of negotiations over a corpus of requests.
+///
+/// Each list of requested locales is resolved with the `Lookup` strategy,
+/// and counted in exactly one of `exact`, `fallback` and `default`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Report {
+    /// Number of lists of requested locales.
+    pub requests: usize,
+    /// Number of lists whose first requested locale is available.
+    pub exact: usize,
+    /// Number of lists resolved to another locale.
+    pub fallback: usize,
+    /// Number of lists without any match, served with the default locale.
+    pub default: usize,
+    /// Requested locales which are not available, with the number of lists
+    /// requesting them, from the most requested one.
+    pub unavailable: Vec<(LanguageIdentifier, usize)>,
+}
+
+/// Collects a [`Report`] one list of requested locales at a time.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::convert_vec_str_to_langids_lossy;
+/// use fluent_langneg::negotiate::ReportBuilder;
+/// use icu_locid::langid;
+///
+/// let available = [langid!("en-US"), langid!("de")];
+///
+/// let mut builder = ReportBuilder::new(&available);
+/// for requested in [&["en-US", "en"][..], &["de-AT"], &["pt-BR", "en"], &["pt-BR"], &["ja"]] {
+///     builder.add(&convert_vec_str_to_langids_lossy(requested));
+/// }
+/// let report = builder.build();
+///
+/// assert_eq!(report.requests, 5);
+/// assert_eq!((report.exact, report.fallback, report.default), (1, 2, 2));
+/// assert_eq!(
+///     report.unavailable,
+///     &[(langid!("en"), 2), (langid!("pt-BR"), 2), (langid!("de-AT"), 1), (langid!("ja"), 1)]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ReportBuilder<'a, A> {
+    available: &'a [A],
+    index: AvailableIndex,
+    report: Report,
+    unavailable: HashMap<LanguageIdentifier, usize>,
+}
+
+impl<'a, A: AsRef<LanguageIdentifier>> ReportBuilder<'a, A> {
+    pub fn new(available: &'a [A]) -> Self {
+        Self {
+            available,
+            index: AvailableIndex::new(available),
+            report: Report::default(),
+            unavailable: HashMap::new(),
+        }
+    }
+
+    /// Negotiates one list of requested locales and records the result.
+    pub fn add<R: AsRef<LanguageIdentifier>>(&mut self, requested: &[R]) {
+        let matches = find_all_matches(
+            requested,
+            self.available,
+            &self.index,
+            NegotiationStrategy::Lookup,
+        );
+        self.report.requests += 1;
+        match matches.first() {
+            Some(m) if m.requested == 0 && m.score == score::EXACT => self.report.exact += 1,
+            Some(_) => self.report.fallback += 1,
+            None => self.report.default += 1,
+        }
+
+        for (idx, langid) in requested.iter().map(AsRef::as_ref).enumerate() {
+            let repeated = requested.iter().take(idx).any(|r| r.as_ref() == langid);
+            if !repeated && !self.available.iter().any(|a| a.as_ref() == langid) {
+                *self.unavailable.entry(langid.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    pub fn build(self) -> Report {
+        let mut unavailable: Vec<_> = self.unavailable.into_iter().collect();
+        unavailable.sort_by(|(l1, c1), (l2, c2)| {
+            c2.cmp(c1).then_with(|| l1.to_string().cmp(&l2.to_string()))
+        });
+        Report {
+            unavailable,
+            ..self.report
+        }
+    }
+}
+
+/// Builds a [`Report`] of all the lists of requested locales.
+pub fn report<I, R, A>(requests: I, available: &[A]) -> Report
+where
+    I: IntoIterator,
+    I::Item: AsRef<[R]>,
+    R: AsRef<LanguageIdentifier>,
+    A: AsRef<LanguageIdentifier>,
+{
+    let mut builder = ReportBuilder::new(available);
+    for requested in requests {
+        builder.add(requested.as_ref());
+    }
+    builder.build()
+}
@@ -0,0 +1,133 @@
+use icu_locid::LanguageIdentifier;
+
+use super::prepare_requested_with;
+use crate::accepted_languages;
+use crate::detect::LanguageDetector;
+
+/// Builds the requested locales of a negotiation from the sources
+/// an application knows the preferences of the user from.
+///
+/// The sources are ordered by precedence, regardless of the order in which
+/// they are set:
+///
+/// 1. the explicit setting of the user, such as a profile preference,
+/// 2. a cookie remembering an earlier choice,
+/// 3. the locale of the URL, see [`crate::routing`],
+/// 4. the `Accept-Language` header,
+/// 5. the locales of the operating system,
+/// 6. the languages detected in a sample of text written by the user, only
+///    used if neither of the two previous sources has any locale.
+///
+/// The result is canonicalized and deduplicated like
+/// [`prepare_requested_with`], keeping the first occurrence.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::RequestedLocales;
+/// use icu_locid::langid;
+///
+/// let requested = RequestedLocales::new()
+///     .accept_language("de-AT,de;q=0.9,en;q=0.5")
+///     .cookie(Some(langid!("en-GB")))
+///     .os([langid!("de-AT")])
+///     .build();
+/// assert_eq!(requested, &[langid!("en-GB"), langid!("de-AT"), langid!("de"), langid!("en")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestedLocales {
+    user_setting: Option<LanguageIdentifier>,
+    cookie: Option<LanguageIdentifier>,
+    url: Option<LanguageIdentifier>,
+    accept_language: Vec<LanguageIdentifier>,
+    os: Vec<LanguageIdentifier>,
+    detected: Vec<LanguageIdentifier>,
+}
+
+impl RequestedLocales {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn user_setting(mut self, locale: Option<LanguageIdentifier>) -> Self {
+        self.user_setting = locale;
+        self
+    }
+
+    pub fn cookie(mut self, locale: Option<LanguageIdentifier>) -> Self {
+        self.cookie = locale;
+        self
+    }
+
+    pub fn url(mut self, locale: Option<LanguageIdentifier>) -> Self {
+        self.url = locale;
+        self
+    }
+
+    /// Sets the locales of an `Accept-Language` header value, skipping
+    /// the ones which fail to parse.
+    pub fn accept_language(mut self, header: &str) -> Self {
+        self.accept_language = accepted_languages::parse(header);
+        self
+    }
+
+    /// Sets the locales of the `Accept-Language` header, already parsed.
+    pub fn accept_language_locales(
+        mut self,
+        locales: impl IntoIterator<Item = LanguageIdentifier>,
+    ) -> Self {
+        self.accept_language = locales.into_iter().collect();
+        self
+    }
+
+    pub fn os(mut self, locales: impl IntoIterator<Item = LanguageIdentifier>) -> Self {
+        self.os = locales.into_iter().collect();
+        self
+    }
+
+    /// Sets the languages detected in a text sample written by the user.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use fluent_langneg::negotiate::RequestedLocales;
+    /// use icu_locid::langid;
+    ///
+    /// let detector = |_: &str| vec![langid!("pl")];
+    ///
+    /// let requested = RequestedLocales::new()
+    ///     .cookie(Some(langid!("en-GB")))
+    ///     .text_sample("Dzień dobry, mam pytanie", &detector)
+    ///     .build();
+    /// assert_eq!(requested, &[langid!("en-GB"), langid!("pl")]);
+    ///
+    /// let requested = RequestedLocales::new()
+    ///     .accept_language("de")
+    ///     .text_sample("Dzień dobry, mam pytanie", &detector)
+    ///     .build();
+    /// assert_eq!(requested, &[langid!("de")]);
+    /// ```
+    pub fn text_sample(mut self, text: &str, detector: &impl LanguageDetector) -> Self {
+        self.detected = detector.detect(text);
+        self
+    }
+
+    /// Returns the requested locales, from the preferred one.
+    pub fn build(&self) -> Vec<LanguageIdentifier> {
+        let requested: Vec<&LanguageIdentifier> = self
+            .user_setting
+            .iter()
+            .chain(&self.cookie)
+            .chain(&self.url)
+            .chain(&self.accept_language)
+            .chain(&self.os)
+            .chain(
+                Some(&self.detected)
+                    .filter(|_| self.accept_language.is_empty() && self.os.is_empty())
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect();
+        prepare_requested_with(&requested, false)
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use icu_locid::LanguageIdentifier;
+
+/// The supported locales of a negotiation, from the preferred one.
+///
+/// The negotiation functions return a plain `Vec`, which converts into a
+/// `NegotiationResult` to be logged, iterated or rendered in templates.
+/// Its `Display` implementation joins the canonical tags with commas.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::NegotiationResult;
+/// use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+/// use icu_locid::langid;
+///
+/// let available = [langid!("en-US"), langid!("de"), langid!("de-AT")];
+/// let result = NegotiationResult::from(negotiate_languages(
+///     &[langid!("de-CH")],
+///     &available,
+///     Some(&available[0]),
+///     NegotiationStrategy::Filtering,
+/// ));
+///
+/// assert_eq!(result.to_string(), "de, de-AT, en-US");
+/// assert_eq!(result.len(), 3);
+/// assert_eq!(result.first(), Some(&available[1]));
+/// for locale in &result {
+///     assert!(available.contains(locale));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiationResult<'a, A> {
+    supported: Vec<&'a A>,
+}
+
+impl<'a, A> NegotiationResult<'a, A> {
+    pub fn len(&self) -> usize {
+        self.supported.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.supported.is_empty()
+    }
+
+    /// Returns the preferred supported locale.
+    pub fn first(&self) -> Option<&'a A> {
+        self.supported.first().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a A> + '_ {
+        self.supported.iter().copied()
+    }
+
+    pub fn as_slice(&self) -> &[&'a A] {
+        &self.supported
+    }
+
+    pub fn into_vec(self) -> Vec<&'a A> {
+        self.supported
+    }
+}
+
+impl<'a, A> From<Vec<&'a A>> for NegotiationResult<'a, A> {
+    fn from(supported: Vec<&'a A>) -> Self {
+        Self { supported }
+    }
+}
+
+impl<'a, A> IntoIterator for NegotiationResult<'a, A> {
+    type Item = &'a A;
+    type IntoIter = std::vec::IntoIter<&'a A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.supported.into_iter()
+    }
+}
+
+impl<'r, 'a, A> IntoIterator for &'r NegotiationResult<'a, A> {
+    type Item = &'a A;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'r, &'a A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.supported.iter().copied()
+    }
+}
+
+impl<A: AsRef<LanguageIdentifier>> fmt::Display for NegotiationResult<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, locale) in self.supported.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            locale.as_ref().fmt(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+//! Scoring of pairs of requested and available locales.
+//!
+//! Each requested locale is turned into a [`Query`] holding a list of
+//! patterns, from the closest to the loosest one. The score of an available
+//! locale is the penalty of the first pattern it matches, so a lower score
+//! is a better match, and an available locale which matches no pattern
+//! is not supported at all.
+//!
+//! The strategies differ only in which of the scored available locales
+//! they keep, see [`super::find_matches`].
+
+use icu_locid::subtags::{Language, Variants};
+use icu_locid::LanguageIdentifier;
+
+use super::{matches_with_variants, same_variants};
+use crate::subtags::is_registered_variant;
+
+/// Penalty of a match, lower is better.
+pub(crate) type Score = u8;
+
+/// The available locale is equal to the requested one.
+pub(crate) const EXACT: Score = 0;
+/// The available locale, treated as a range, covers the requested one.
+pub(crate) const RANGE: Score = 1;
+/// The available locale, treated as a range, covers the requested one
+/// maximized using likely subtags.
+pub(crate) const MAXIMIZATION: Score = 2;
+/// The available locale matches the maximized requested one
+/// with any variant.
+pub(crate) const VARIANT: Score = 3;
+/// The available locale covers the likely script of the requested
+/// language, without its region.
+pub(crate) const SCRIPT: Score = 4;
+/// The available locale matches the requested language and script
+/// with any region.
+pub(crate) const REGION: Score = 5;
+
+/// Added to the score of a match whose variants differ from the requested
+/// ones, when exact variants are preferred, so that it ranks after all the
+/// matches with the requested variants.
+pub(crate) const VARIANT_MISMATCH: Score = 8;
+
+/// Returns the step of the algorithm of a score, without the penalty
+/// for the variants.
+pub(crate) fn step(score: Score) -> Score {
+    score % VARIANT_MISMATCH
+}
+
+const EMPTY_VARIANTS: Variants = Variants::new();
+
+/// Variant handling of negotiation, see
+/// [`NegotiationOptions`](super::NegotiationOptions).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct VariantRules {
+    pub prefer_exact: bool,
+    pub private: bool,
+}
+
+#[derive(Debug)]
+struct Pattern {
+    score: Score,
+    /// Language, script and region to match, never with variants,
+    /// so that it doesn't allocate.
+    subtags: LanguageIdentifier,
+    /// Whether the pattern has the variants of the requested locale,
+    /// as opposed to none.
+    with_variants: bool,
+    /// Whether missing subtags of the pattern are treated as wildcards.
+    as_range: bool,
+}
+
+/// The patterns of a requested locale, ordered by their score.
+#[derive(Debug)]
+pub(crate) struct Query<'r> {
+    variants: &'r Variants,
+    patterns: [Option<Pattern>; 6],
+    rules: VariantRules,
+}
+
+impl<'r> Query<'r> {
+    /// Builds the patterns of the requested locale, maximizing it with the
+    /// provided function, which returns `true` if it modified the locale.
+    pub fn new(
+        requested: &'r LanguageIdentifier,
+        mut maximize: impl FnMut(&mut LanguageIdentifier) -> bool,
+    ) -> Self {
+        let pattern = |score, subtags: &LanguageIdentifier, with_variants, as_range| {
+            Some(Pattern {
+                score,
+                subtags: LanguageIdentifier::from((
+                    subtags.language,
+                    subtags.script,
+                    subtags.region,
+                )),
+                with_variants,
+                as_range,
+            })
+        };
+
+        let mut patterns = [None, None, None, None, None, None];
+        patterns[0] = pattern(EXACT, requested, true, false);
+        patterns[1] = pattern(RANGE, requested, true, false);
+
+        // Per Unicode TR35, 4.4 Locale Matching, we don't add likely subtags to
+        // a bare `und` requested locale, so we'll skip the rest of the patterns.
+        // A script or a region on its own still implies a likely language,
+        // without which the patterns are skipped as well.
+        let mut base =
+            LanguageIdentifier::from((requested.language, requested.script, requested.region));
+        let maximized = (requested.script.is_some() || requested.region.is_some())
+            && requested.language.is_empty()
+            && maximize(&mut base);
+        if !base.language.is_empty() {
+            if maximized || maximize(&mut base) {
+                patterns[2] = pattern(MAXIMIZATION, &base, true, false);
+            }
+            patterns[3] = pattern(VARIANT, &base, false, true);
+
+            base.region = None;
+            if maximize(&mut base) {
+                patterns[4] = pattern(SCRIPT, &base, false, false);
+            }
+
+            base.region = None;
+            patterns[5] = pattern(REGION, &base, false, true);
+        }
+
+        Self {
+            variants: &requested.variants,
+            patterns,
+            rules: VariantRules::default(),
+        }
+    }
+
+    pub fn with_rules(mut self, rules: VariantRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Returns the language of the available locales which can match,
+    /// apart from `und` ones.
+    ///
+    /// This is the requested language, unless it's `und` and likely
+    /// subtags provide one.
+    pub fn language(&self) -> Language {
+        self.patterns
+            .iter()
+            .flatten()
+            .last()
+            .map_or(Language::UND, |p| p.subtags.language)
+    }
+
+    /// Returns the score of the available locale, if it is below `limit`.
+    pub fn score(&self, available: &LanguageIdentifier, limit: Score) -> Option<Score> {
+        if self.rules.private
+            && available
+                .variants
+                .iter()
+                .any(|v| !is_registered_variant(*v) && !self.variants.contains(v))
+        {
+            return None;
+        }
+        let penalty =
+            if self.rules.prefer_exact && !same_variants(&available.variants, self.variants) {
+                VARIANT_MISMATCH
+            } else {
+                0
+            };
+        self.patterns
+            .iter()
+            .flatten()
+            .take_while(|p| p.score + penalty < limit)
+            .find(|p| {
+                let variants = if p.with_variants {
+                    self.variants
+                } else {
+                    &EMPTY_VARIANTS
+                };
+                // Only an exact match requires the available locale
+                // to have all of the subtags of the pattern.
+                matches_with_variants(
+                    available,
+                    &p.subtags,
+                    variants,
+                    p.score != EXACT,
+                    p.as_range,
+                )
+            })
+            .map(|p| p.score + penalty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icu_locid::langid;
+    use icu_locid::subtags::{region, script};
+
+    #[test]
+    fn test_score() {
+        let requested = langid!("en-GB");
+        let query = Query::new(&requested, |langid| {
+            let modified = langid.script.is_none() || langid.region.is_none();
+            langid.script.get_or_insert(script!("Latn"));
+            langid.region.get_or_insert(region!("US"));
+            modified
+        });
+
+        assert_eq!(query.score(&langid!("en-GB"), Score::MAX), Some(EXACT));
+        assert_eq!(query.score(&langid!("en"), Score::MAX), Some(RANGE));
+        assert_eq!(
+            query.score(&langid!("en-Latn-GB"), Score::MAX),
+            Some(MAXIMIZATION)
+        );
+        assert_eq!(
+            query.score(&langid!("en-Latn-US"), Score::MAX),
+            Some(SCRIPT)
+        );
+        assert_eq!(query.score(&langid!("en-AU"), Score::MAX), Some(REGION));
+        assert_eq!(query.score(&langid!("fr"), Score::MAX), None);
+        assert_eq!(query.score(&langid!("en-AU"), SCRIPT), None);
+    }
+}
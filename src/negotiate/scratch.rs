@@ -0,0 +1,115 @@
+use std::cell::Cell;
+
+use icu_locid::LanguageIdentifier;
+
+use super::index::AvailableIndex;
+use super::score::Score;
+use super::{find_matches_in, Match, NegotiationStrategy, VariantRules, VecSink};
+
+/// Buffers reused between negotiations, see [`filter_matches_with_scratch`].
+///
+/// A negotiation indexes the available locales, and keeps track of the
+/// matches found so far. The scratch keeps these buffers, so once they have
+/// grown to the size of the largest negotiation, the following ones don't
+/// allocate at all.
+#[derive(Debug, Default)]
+pub struct NegotiationScratch {
+    index: AvailableIndex,
+    matched: Vec<bool>,
+    matches: Vec<Match>,
+    scored: Vec<(Score, usize)>,
+}
+
+impl NegotiationScratch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Same as [`filter_matches`](super::filter_matches), but uses the buffers
+/// of the scratch, and writes the supported locales into `supported`,
+/// after clearing it.
+///
+/// [`filter_matches`](super::filter_matches) itself uses a scratch local to
+/// the thread, so the only allocation left is the one of the result, which
+/// this function lets the caller reuse as well.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::{filter_matches_with_scratch, NegotiationScratch};
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let available = [langid!("en-US"), langid!("de"), langid!("en-GB")];
+/// let mut scratch = NegotiationScratch::new();
+/// let mut supported = Vec::with_capacity(available.len());
+///
+/// for requested in [[langid!("de-AT")], [langid!("en")]] {
+///     filter_matches_with_scratch(
+///         &requested,
+///         &available,
+///         NegotiationStrategy::Lookup,
+///         &mut scratch,
+///         &mut supported,
+///     );
+///     assert_eq!(supported.len(), 1);
+/// }
+/// assert_eq!(supported, &[&available[0]]);
+/// ```
+pub fn filter_matches_with_scratch<
+    'a,
+    R: AsRef<LanguageIdentifier>,
+    A: AsRef<LanguageIdentifier>,
+>(
+    requested: &[R],
+    available: &'a [A],
+    strategy: NegotiationStrategy,
+    scratch: &mut NegotiationScratch,
+    supported: &mut Vec<&'a A>,
+) {
+    scratch.index.rebuild(available);
+    let mut matched = std::mem::take(&mut scratch.matched);
+    matched.clear();
+    matched.resize(available.len(), false);
+    let mut matches = std::mem::take(&mut scratch.matches);
+    matches.clear();
+
+    let mut sink = VecSink { matched, matches };
+    find_matches_in(
+        requested,
+        available,
+        Some(&scratch.index),
+        strategy,
+        VariantRules::default(),
+        Some(&mut scratch.scored),
+        &mut sink,
+    );
+    #[cfg(feature = "invariants")]
+    super::invariants::check(requested, available, strategy, &sink.matches);
+
+    supported.clear();
+    supported.extend(
+        sink.matches
+            .iter()
+            .filter_map(|m| available.get(m.available)),
+    );
+    scratch.matched = sink.matched;
+    scratch.matches = sink.matches;
+}
+
+thread_local! {
+    static SCRATCH: Cell<NegotiationScratch> = Cell::default();
+}
+
+/// Runs `f` with the scratch of the current thread.
+///
+/// The scratch is moved out of the thread local for the duration of `f`,
+/// so a nested call, or one during the destruction of the thread, gets
+/// an empty one instead.
+pub(super) fn with_thread_scratch<T>(f: impl FnOnce(&mut NegotiationScratch) -> T) -> T {
+    let mut scratch = SCRATCH.try_with(Cell::take).unwrap_or_default();
+    let result = f(&mut scratch);
+    let _ = SCRATCH.try_with(|cell| cell.set(scratch));
+    result
+}
@@ -0,0 +1,160 @@
+use std::sync::{Arc, RwLock};
+
+use icu_locid::LanguageIdentifier;
+
+use super::index::AvailableIndex;
+use super::{add_default, filter_indices, supported_in, NegotiationStrategy};
+
+/// A set of available locales shared between threads, which can be replaced
+/// while negotiations are running.
+///
+/// Each negotiation runs against a [`NegotiatorSnapshot`], an immutable and
+/// prepared set of available locales, like [`AvailableLocales`](super::AvailableLocales).
+/// [`SharedNegotiator::replace`] prepares the new snapshot before swapping
+/// it in, so that the lock is only held to exchange two pointers. The
+/// negotiations which took the previous snapshot keep using it until they
+/// drop it.
+///
+/// Clones share the same snapshot.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::SharedNegotiator;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let negotiator = SharedNegotiator::new(vec![langid!("en-US"), langid!("de")], None);
+///
+/// // A request handler takes the snapshot once for all its negotiations.
+/// let snapshot = negotiator.snapshot();
+///
+/// // A language pack is installed meanwhile.
+/// negotiator.replace(vec![langid!("en-US"), langid!("de"), langid!("fr")], None);
+///
+/// let requested = [langid!("fr-CA"), langid!("de")];
+/// let strategy = NegotiationStrategy::Filtering;
+/// assert_eq!(snapshot.negotiate_languages(&requested, strategy), &[&langid!("de")]);
+/// assert_eq!(
+///     negotiator.negotiate_languages(&requested, strategy),
+///     &[langid!("fr"), langid!("de")]
+/// );
+/// assert_eq!(negotiator.snapshot().generation(), snapshot.generation() + 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedNegotiator {
+    current: Arc<RwLock<Arc<NegotiatorSnapshot>>>,
+}
+
+impl SharedNegotiator {
+    pub fn new(available: Vec<LanguageIdentifier>, default: Option<LanguageIdentifier>) -> Self {
+        let snapshot = NegotiatorSnapshot::new(available, default, 0);
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(snapshot))),
+        }
+    }
+
+    /// Returns the current snapshot.
+    pub fn snapshot(&self) -> Arc<NegotiatorSnapshot> {
+        Arc::clone(&self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Replaces the available locales and the default one, and returns the
+    /// previous snapshot.
+    pub fn replace(
+        &self,
+        available: Vec<LanguageIdentifier>,
+        default: Option<LanguageIdentifier>,
+    ) -> Arc<NegotiatorSnapshot> {
+        // The generation is read again under the write lock, since another
+        // replacement may have happened while the snapshot was prepared.
+        let mut snapshot = NegotiatorSnapshot::new(available, default, 0);
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        snapshot.generation = current.generation + 1;
+        std::mem::replace(&mut *current, Arc::new(snapshot))
+    }
+
+    /// Negotiates against the current snapshot, see
+    /// [`NegotiatorSnapshot::negotiate_languages`].
+    ///
+    /// The supported locales are cloned, since they can't borrow from
+    /// a snapshot which may be replaced.
+    pub fn negotiate_languages<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> Vec<LanguageIdentifier> {
+        self.snapshot()
+            .negotiate_languages(requested, strategy)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+}
+
+/// The available locales of a [`SharedNegotiator`] at some point in time.
+#[derive(Debug)]
+pub struct NegotiatorSnapshot {
+    available: Vec<LanguageIdentifier>,
+    default: Option<LanguageIdentifier>,
+    index: AvailableIndex,
+    generation: u64,
+}
+
+impl NegotiatorSnapshot {
+    fn new(
+        available: Vec<LanguageIdentifier>,
+        default: Option<LanguageIdentifier>,
+        generation: u64,
+    ) -> Self {
+        Self {
+            index: AvailableIndex::new(&available),
+            available,
+            default,
+            generation,
+        }
+    }
+
+    pub fn available(&self) -> &[LanguageIdentifier] {
+        &self.available
+    }
+
+    pub fn default_locale(&self) -> Option<&LanguageIdentifier> {
+        self.default.as_ref()
+    }
+
+    /// Returns the number of replacements which preceded the snapshot.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Same as [`super::is_supported`], using the snapshot.
+    pub fn is_supported(&self, requested: &LanguageIdentifier) -> bool {
+        supported_in(requested, &self.available, Some(&self.index))
+    }
+
+    /// Same as [`filter_matches`](super::filter_matches) over the available
+    /// locales of the snapshot.
+    pub fn filter_matches<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> Vec<&LanguageIdentifier> {
+        filter_indices(requested, &self.available, &self.index, strategy)
+            .into_iter()
+            .filter_map(|i| self.available.get(i))
+            .collect()
+    }
+
+    /// Same as [`negotiate_languages`](super::negotiate_languages) over the
+    /// available and default locales of the snapshot.
+    pub fn negotiate_languages<R: AsRef<LanguageIdentifier>>(
+        &self,
+        requested: &[R],
+        strategy: NegotiationStrategy,
+    ) -> Vec<&LanguageIdentifier> {
+        let mut supported = self.filter_matches(requested, strategy);
+        add_default(&mut supported, self.default.as_ref(), strategy);
+        supported
+    }
+}
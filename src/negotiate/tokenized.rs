@@ -0,0 +1,140 @@
+use icu_locid::subtags::{Language, Region, Script, Variant, Variants};
+use icu_locid::LanguageIdentifier;
+
+use super::{negotiate_languages, NegotiationStrategy};
+
+/// Subtags of a locale which the caller has already split, for example
+/// while parsing its own preferences format.
+///
+/// It is implemented for tuples of `(language, script, region)` and
+/// `(language, script, region, variants)`, both with string subtags and
+/// with the subtag types of `icu_locid`.
+pub trait TokenizedLocale {
+    /// Returns the language identifier of the subtags, or `None` if one of
+    /// them is invalid.
+    fn to_langid(&self) -> Option<LanguageIdentifier>;
+}
+
+impl TokenizedLocale for (&str, Option<&str>, Option<&str>) {
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        (self.0, self.1, self.2, &[][..]).to_langid()
+    }
+}
+
+impl TokenizedLocale for (&str, Option<&str>, Option<&str>, &[&str]) {
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        let (language, script, region, variants) = *self;
+        let variants = variants
+            .iter()
+            .map(|variant| Variant::try_from_bytes(variant.as_bytes()).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(LanguageIdentifier {
+            language: Language::try_from_bytes(language.as_bytes()).ok()?,
+            script: match script {
+                Some(script) => Some(Script::try_from_bytes(script.as_bytes()).ok()?),
+                None => None,
+            },
+            region: match region {
+                Some(region) => Some(Region::try_from_bytes(region.as_bytes()).ok()?),
+                None => None,
+            },
+            variants: to_variants(variants),
+        })
+    }
+}
+
+impl TokenizedLocale for (Language, Option<Script>, Option<Region>) {
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        Some(LanguageIdentifier::from(*self))
+    }
+}
+
+impl TokenizedLocale for (Language, Option<Script>, Option<Region>, &[Variant]) {
+    fn to_langid(&self) -> Option<LanguageIdentifier> {
+        let (language, script, region, variants) = *self;
+        Some(LanguageIdentifier {
+            language,
+            script,
+            region,
+            variants: to_variants(variants.to_vec()),
+        })
+    }
+}
+
+/// Sorts and deduplicates the variants like the parser does, without
+/// allocating for a single variant.
+fn to_variants(mut variants: Vec<Variant>) -> Variants {
+    match variants.as_slice() {
+        [] => Variants::new(),
+        [variant] => Variants::from_variant(*variant),
+        _ => {
+            variants.sort_unstable();
+            variants.dedup();
+            Variants::from_vec_unchecked(variants)
+        }
+    }
+}
+
+/// An available locale with its language identifier.
+#[derive(PartialEq)]
+struct Entry<'a, A> {
+    langid: LanguageIdentifier,
+    locale: &'a A,
+}
+
+impl<A> AsRef<LanguageIdentifier> for Entry<'_, A> {
+    fn as_ref(&self) -> &LanguageIdentifier {
+        &self.langid
+    }
+}
+
+/// Same as [`negotiate_languages`], over locales already split into
+/// subtags.
+///
+/// The subtags are validated one by one, and the language identifiers are
+/// assembled from them, which skips the splitting and the allocations of
+/// parsing a tag. Locales with an invalid subtag are ignored.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::negotiate_tokenized;
+/// use fluent_langneg::NegotiationStrategy;
+///
+/// let requested = [("sr", None, Some("RS")), ("en", None, None)];
+/// let available = [
+///     ("en", None, Some("US")),
+///     ("sr", Some("Cyrl"), None),
+///     ("sr", Some("Latn"), None),
+///     ("e", None, None),
+/// ];
+///
+/// let supported = negotiate_tokenized(
+///     &requested,
+///     &available,
+///     Some(&available[0]),
+///     NegotiationStrategy::Lookup,
+/// );
+/// assert_eq!(supported, &[&available[1]]);
+/// ```
+pub fn negotiate_tokenized<'a, R: TokenizedLocale, A: TokenizedLocale + PartialEq>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let requested: Vec<LanguageIdentifier> = requested
+        .iter()
+        .filter_map(TokenizedLocale::to_langid)
+        .collect();
+    let entries: Vec<Entry<'a, A>> = available
+        .iter()
+        .filter_map(|locale| locale.to_langid().map(|langid| Entry { langid, locale }))
+        .collect();
+    let default =
+        default.and_then(|locale| locale.to_langid().map(|langid| Entry { langid, locale }));
+    negotiate_languages(&requested, &entries, default.as_ref(), strategy)
+        .into_iter()
+        .map(|entry| entry.locale)
+        .collect()
+}
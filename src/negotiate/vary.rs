@@ -0,0 +1,82 @@
+//! Equivalence classes of `Accept-Language` values.
+//!
+//! A response negotiated from the `Accept-Language` header has to be cached
+//! with `Vary: Accept-Language`, and since the header values are as varied as
+//! the users, most of the cached responses are duplicates. Two header values
+//! are equivalent when they negotiate to the same result, so a caching proxy
+//! can replace the header by the key of its class before using it as a cache
+//! key, and store each response only once.
+
+use icu_locid::LanguageIdentifier;
+
+use super::{join_key, AvailableLocales, NegotiationStrategy};
+use crate::accepted_languages;
+
+/// The equivalence classes of `Accept-Language` values for a set of
+/// available locales and a strategy.
+///
+/// The key of a class is the list of supported locales, as returned by
+/// [`cache_key`](super::cache_key), so there is exactly one class per
+/// distinct result. It is also a valid `Accept-Language` value, which
+/// negotiates to the same result with the `Matching` and `Lookup`
+/// strategies.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::negotiate::AcceptLanguageClasses;
+/// use fluent_langneg::NegotiationStrategy;
+/// use icu_locid::langid;
+///
+/// let available = [langid!("en-US"), langid!("fr"), langid!("de")];
+/// let classes = AcceptLanguageClasses::new(&available, NegotiationStrategy::Lookup);
+///
+/// assert_eq!(classes.normalize("fr-CA,fr;q=0.9,en;q=0.8"), "fr");
+/// assert_eq!(classes.normalize("fr-BE"), "fr");
+/// assert_eq!(classes.normalize("ja"), "");
+/// assert_eq!(classes.classes().unwrap(), ["en-US", "fr", "de", ""]);
+/// ```
+#[derive(Debug)]
+pub struct AcceptLanguageClasses<'a, A> {
+    available: AvailableLocales<'a, A>,
+    strategy: NegotiationStrategy,
+}
+
+impl<'a, A: AsRef<LanguageIdentifier>> AcceptLanguageClasses<'a, A> {
+    pub fn new(available: &'a [A], strategy: NegotiationStrategy) -> Self {
+        Self {
+            available: AvailableLocales::new(available),
+            strategy,
+        }
+    }
+
+    /// Returns the key of the class of an `Accept-Language` value.
+    pub fn normalize(&self, header: &str) -> String {
+        self.key(&accepted_languages::parse(header))
+    }
+
+    /// Returns the key of the class of a list of requested locales.
+    pub fn key<R: AsRef<LanguageIdentifier>>(&self, requested: &[R]) -> String {
+        join_key(self.available.filter_matches(requested, self.strategy))
+    }
+
+    /// Returns the keys of all the classes, the empty one last, or `None`
+    /// if the strategy isn't `Lookup`.
+    ///
+    /// With the other strategies, any ordered subset of the available
+    /// locales may be a class, so there are too many of them to list.
+    pub fn classes(&self) -> Option<Vec<String>> {
+        if self.strategy != NegotiationStrategy::Lookup {
+            return None;
+        }
+        let mut classes: Vec<String> = Vec::new();
+        for langid in self.available.as_slice() {
+            let key = self.key(&[langid.as_ref()]);
+            if !classes.contains(&key) {
+                classes.push(key);
+            }
+        }
+        classes.push(String::new());
+        Some(classes)
+    }
+}
@@ -0,0 +1,88 @@
+use icu_locid::LanguageIdentifier;
+
+use super::index::AvailableIndex;
+use super::{add_default, find_all_matches, NegotiationStrategy};
+use crate::accepted_languages::stable_sort_by_quality;
+
+/// Negotiates weighted requested locales against weighted available
+/// locales, the way HTTP content negotiation combines the preferences of
+/// the client and the server.
+///
+/// Each requested locale comes with its client quality, for example from
+/// [`parse_weighted`](crate::accepted_languages::parse_weighted), and each
+/// available locale with a server weight between `0.0` and `1.0`, for
+/// example lower for a machine translated locale. An available locale is
+/// supported by the requested locales with the highest quality matching it,
+/// as with [`NegotiationStrategy::Filtering`], and its combined quality is
+/// the product of the quality and the weight.
+///
+/// The supported locales are sorted by decreasing combined quality, the
+/// order of negotiation deciding between equal ones, and those below
+/// `min_quality`, or with a combined quality of zero, are left out. The
+/// default locale is added last, so it is preferred when no locale reaches
+/// the threshold.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::accepted_languages::parse_weighted;
+/// use fluent_langneg::negotiate::negotiate_weighted;
+/// use icu_locid::langid;
+///
+/// let requested = parse_weighted("fr-CA, de;q=0.9");
+/// // French is machine translated.
+/// let available = [(langid!("en-US"), 1.0), (langid!("fr"), 0.5), (langid!("de"), 1.0)];
+///
+/// let supported = negotiate_weighted(&requested, &available, Some(&available[0].0), 0.0);
+/// assert_eq!(supported, &[&langid!("de"), &langid!("fr"), &langid!("en-US")]);
+///
+/// let requested = parse_weighted("fr-CA, de;q=0.2");
+/// let supported = negotiate_weighted(&requested, &available, Some(&available[0].0), 0.6);
+/// assert_eq!(supported, &[&langid!("en-US")]);
+/// ```
+pub fn negotiate_weighted<'a, R, A>(
+    requested: &[(R, f32)],
+    available: &'a [(A, f32)],
+    default: Option<&'a A>,
+    min_quality: f32,
+) -> Vec<&'a A>
+where
+    R: AsRef<LanguageIdentifier>,
+    A: AsRef<LanguageIdentifier> + PartialEq,
+{
+    let mut by_quality: Vec<(&LanguageIdentifier, f32)> = requested
+        .iter()
+        .map(|(langid, quality)| (langid.as_ref(), *quality))
+        .collect();
+    stable_sort_by_quality(&mut by_quality);
+    let requested_langids: Vec<&LanguageIdentifier> =
+        by_quality.iter().map(|(langid, _)| *langid).collect();
+    let available_langids: Vec<&LanguageIdentifier> = available
+        .iter()
+        .map(|(langid, _)| langid.as_ref())
+        .collect();
+    let index = AvailableIndex::new(&available_langids);
+
+    let mut combined: Vec<(usize, f32)> = find_all_matches(
+        &requested_langids,
+        &available_langids,
+        &index,
+        NegotiationStrategy::Filtering,
+    )
+    .into_iter()
+    .filter_map(|m| {
+        let (_, requested_quality) = by_quality.get(m.requested)?;
+        let (_, available_quality) = available.get(m.available)?;
+        Some((m.available, requested_quality * available_quality))
+    })
+    .filter(|&(_, quality)| quality > 0.0 && quality >= min_quality)
+    .collect();
+    stable_sort_by_quality(&mut combined);
+
+    let mut supported: Vec<&'a A> = combined
+        .into_iter()
+        .filter_map(|(i, _)| available.get(i).map(|(locale, _)| locale))
+        .collect();
+    add_default(&mut supported, default, NegotiationStrategy::Filtering);
+    supported
+}
@@ -0,0 +1,258 @@
+//! Owned mirrors of the negotiation types, for RPC services.
+//!
+//! The results of the negotiation functions borrow the available locales,
+//! which ties them to the lifetime of the request handler. The plain types
+//! hold tags as strings instead, so that they can be converted to and from
+//! the messages generated for protocols like gRPC, and moved across tasks
+//! and threads freely.
+//!
+//! The conversions from the borrowed types are infallible `From`
+//! implementations. The conversions back look the tags up in the available
+//! locales, with a `resolve` method returning `None` if one is missing.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::plain::{PlainRequest, PlainResult};
+//! use fluent_langneg::NegotiationStrategy;
+//! use icu_locid::langid;
+//!
+//! let request = PlainRequest {
+//!     requested: vec!["de-AT".to_string(), "en".to_string()],
+//!     available: vec!["en-US".to_string(), "de".to_string()],
+//!     default: None,
+//!     strategy: NegotiationStrategy::Filtering,
+//! };
+//! let result = request.negotiate().unwrap();
+//! assert_eq!(result.supported, &["de", "en-US"]);
+//!
+//! let available = [langid!("en-US"), langid!("de")];
+//! let supported = result.resolve(&available).unwrap();
+//! assert_eq!(supported.as_slice(), &[&available[1], &available[0]]);
+//! assert_eq!(PlainResult::from(supported), result);
+//! ```
+
+use icu_locid::extensions::unicode::{Key, Unicode, Value};
+use icu_locid::{LanguageIdentifier, Locale};
+
+use crate::ecma402::{KeywordAvailability, MatcherResult, ResolvedKeyword, ResolvedLocale};
+use crate::negotiate::{CollationMatch, NegotiationResult};
+use crate::{convert_vec_str_to_langids, negotiate_languages, Error, NegotiationStrategy};
+
+/// The input of a negotiation, with the locales as tags.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlainRequest {
+    pub requested: Vec<String>,
+    pub available: Vec<String>,
+    pub default: Option<String>,
+    pub strategy: NegotiationStrategy,
+}
+
+impl PlainRequest {
+    /// Runs the negotiation.
+    ///
+    /// The requested tags come from clients, and the invalid ones are
+    /// skipped, while an invalid available or default tag is an error.
+    pub fn negotiate(&self) -> Result<PlainResult, Error> {
+        let requested = crate::convert_vec_str_to_langids_lossy(&self.requested);
+        let available = convert_vec_str_to_langids(&self.available)?;
+        let default = self
+            .default
+            .as_deref()
+            .map(crate::parse_langid)
+            .transpose()?;
+        let supported =
+            negotiate_languages(&requested, &available, default.as_ref(), self.strategy);
+        Ok(PlainResult::from(supported))
+    }
+}
+
+/// The supported locales of a negotiation, see [`NegotiationResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlainResult {
+    pub supported: Vec<String>,
+}
+
+impl PlainResult {
+    /// Looks the supported tags up in the available locales.
+    pub fn resolve<'a, A: AsRef<LanguageIdentifier>>(
+        &self,
+        available: &'a [A],
+    ) -> Option<NegotiationResult<'a, A>> {
+        self.supported
+            .iter()
+            .map(|tag| find_langid(available, tag))
+            .collect::<Option<Vec<_>>>()
+            .map(NegotiationResult::from)
+    }
+}
+
+impl<A: AsRef<LanguageIdentifier>> From<Vec<&A>> for PlainResult {
+    fn from(supported: Vec<&A>) -> Self {
+        Self {
+            supported: supported.iter().map(|a| a.as_ref().to_string()).collect(),
+        }
+    }
+}
+
+impl<A: AsRef<LanguageIdentifier>> From<NegotiationResult<'_, A>> for PlainResult {
+    fn from(result: NegotiationResult<'_, A>) -> Self {
+        Self::from(result.into_vec())
+    }
+}
+
+/// An available locale picked by [`negotiate_collation`](crate::negotiate::negotiate_collation),
+/// see [`CollationMatch`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlainCollationMatch {
+    pub locale: String,
+    pub collation: Option<String>,
+}
+
+impl PlainCollationMatch {
+    /// Looks the locale up in the available locales.
+    pub fn resolve<'a>(&self, available: &'a [Locale]) -> Option<CollationMatch<'a>> {
+        let locale = self.locale.parse::<Locale>().ok()?;
+        let collation = match &self.collation {
+            Some(collation) => Some(collation.parse::<Value>().ok()?),
+            None => None,
+        };
+        Some(CollationMatch {
+            locale: available.iter().find(|a| **a == locale)?,
+            collation,
+        })
+    }
+}
+
+impl From<CollationMatch<'_>> for PlainCollationMatch {
+    fn from(matched: CollationMatch<'_>) -> Self {
+        Self {
+            locale: matched.locale.to_string(),
+            collation: matched.collation.map(|value| value.to_string()),
+        }
+    }
+}
+
+/// Result of a locale matcher of the [`ecma402`](crate::ecma402) module,
+/// see [`MatcherResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlainMatcherResult {
+    pub available: String,
+    /// The Unicode extension, such as `u-ca-buddhist`.
+    pub extension: Option<String>,
+}
+
+impl PlainMatcherResult {
+    /// Looks the locale up in the available locales.
+    pub fn resolve<'a, A: AsRef<LanguageIdentifier>>(
+        &self,
+        available: &'a [A],
+    ) -> Option<MatcherResult<'a, A>> {
+        let extension = match &self.extension {
+            Some(extension) => Some(parse_unicode(extension)?),
+            None => None,
+        };
+        Some(MatcherResult {
+            available: find_langid(available, &self.available)?,
+            extension,
+        })
+    }
+}
+
+impl<A: AsRef<LanguageIdentifier>> From<MatcherResult<'_, A>> for PlainMatcherResult {
+    fn from(result: MatcherResult<'_, A>) -> Self {
+        Self {
+            available: result.available.as_ref().to_string(),
+            extension: result.extension.map(|extension| extension.to_string()),
+        }
+    }
+}
+
+/// A keyword resolved by [`resolve_locale`](crate::ecma402::resolve_locale),
+/// see [`ResolvedKeyword`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlainKeyword {
+    pub key: String,
+    pub value: String,
+    pub requested: bool,
+}
+
+impl From<ResolvedKeyword> for PlainKeyword {
+    fn from(keyword: ResolvedKeyword) -> Self {
+        Self {
+            key: keyword.key.to_string(),
+            value: keyword.value.to_string(),
+            requested: keyword.requested,
+        }
+    }
+}
+
+impl TryFrom<&PlainKeyword> for ResolvedKeyword {
+    type Error = Error;
+
+    fn try_from(keyword: &PlainKeyword) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: keyword
+                .key
+                .parse::<Key>()
+                .map_err(|source| Error::parse(keyword.key.as_bytes(), source))?,
+            value: keyword
+                .value
+                .parse::<Value>()
+                .map_err(|source| Error::parse(keyword.value.as_bytes(), source))?,
+            requested: keyword.requested,
+        })
+    }
+}
+
+/// Result of [`resolve_locale`](crate::ecma402::resolve_locale), see
+/// [`ResolvedLocale`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlainResolvedLocale {
+    pub available: String,
+    pub keywords: Vec<PlainKeyword>,
+}
+
+impl PlainResolvedLocale {
+    /// Looks the locale up in the available locales.
+    pub fn resolve<'a>(&self, available: &'a [KeywordAvailability]) -> Option<ResolvedLocale<'a>> {
+        let keywords = self
+            .keywords
+            .iter()
+            .map(|keyword| ResolvedKeyword::try_from(keyword).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(ResolvedLocale {
+            available: find_langid(available, &self.available)?,
+            keywords,
+        })
+    }
+}
+
+impl From<ResolvedLocale<'_>> for PlainResolvedLocale {
+    fn from(resolved: ResolvedLocale<'_>) -> Self {
+        Self {
+            available: resolved.available.langid.to_string(),
+            keywords: resolved
+                .keywords
+                .into_iter()
+                .map(PlainKeyword::from)
+                .collect(),
+        }
+    }
+}
+
+fn find_langid<'a, A: AsRef<LanguageIdentifier>>(available: &'a [A], tag: &str) -> Option<&'a A> {
+    let langid = tag.parse::<LanguageIdentifier>().ok()?;
+    available.iter().find(|a| *a.as_ref() == langid)
+}
+
+fn parse_unicode(extension: &str) -> Option<Unicode> {
+    let locale = format!("und-{extension}").parse::<Locale>().ok()?;
+    Some(locale.extensions.unicode)
+}
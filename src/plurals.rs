@@ -0,0 +1,50 @@
+//! Plural categories of locales, based on CLDR data.
+//!
+//! Translation quality tooling checks, right after negotiation, that the
+//! messages of a catalog provide a variant for each plural category of the
+//! negotiated locale.
+//!
+//! This module is only available with the `plurals` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::plurals::{cardinal_categories, ordinal_categories, PluralCategory};
+//! use icu_locid::langid;
+//!
+//! assert_eq!(
+//!     cardinal_categories(&langid!("pl")),
+//!     &[PluralCategory::One, PluralCategory::Few, PluralCategory::Many, PluralCategory::Other]
+//! );
+//! assert_eq!(cardinal_categories(&langid!("ja")), &[PluralCategory::Other]);
+//! assert_eq!(
+//!     ordinal_categories(&langid!("en")),
+//!     &[PluralCategory::One, PluralCategory::Two, PluralCategory::Few, PluralCategory::Other]
+//! );
+//! ```
+
+use icu_locid::LanguageIdentifier;
+use icu_plurals::{PluralRuleType, PluralRules};
+
+pub use icu_plurals::PluralCategory;
+
+fn categories(langid: &LanguageIdentifier, rule_type: PluralRuleType) -> Vec<PluralCategory> {
+    match PluralRules::try_new(&langid.into(), rule_type) {
+        Ok(rules) => rules.categories().collect(),
+        Err(_) => vec![PluralCategory::Other],
+    }
+}
+
+/// Returns the plural categories of cardinal numbers in the locale, such as
+/// `one` and `other` for English.
+///
+/// Locales without data only have the `other` category.
+pub fn cardinal_categories(langid: &LanguageIdentifier) -> Vec<PluralCategory> {
+    categories(langid, PluralRuleType::Cardinal)
+}
+
+/// Returns the plural categories of ordinal numbers in the locale, such as
+/// `one`, `two`, `few` and `other` for English.
+pub fn ordinal_categories(langid: &LanguageIdentifier) -> Vec<PluralCategory> {
+    categories(langid, PluralRuleType::Ordinal)
+}
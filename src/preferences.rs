@@ -0,0 +1,195 @@
+//! Regional preferences of a negotiated locale.
+//!
+//! The first day of the week, the measurement system and the temperature
+//! unit depend on the region of a locale rather than on its language. The
+//! region is resolved the way negotiation falls back on regions: the
+//! `-u-rg-` region override first, then the region of the locale, then its
+//! likely region, and finally the world, `001`. The `-u-fw-`, `-u-ms-` and
+//! `-u-mu-` keywords override the regional data.
+//!
+//! The data comes from the CLDR supplemental data. This module is only
+//! available with the `region-preferences` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::preferences::{
+//!     first_day_of_week, measurement_system, temperature_unit, MeasurementSystem,
+//!     TemperatureUnit, Weekday,
+//! };
+//! use icu_locid::{langid, locale, Locale};
+//!
+//! let locale = Locale::from(langid!("en"));
+//! assert_eq!(first_day_of_week(&locale), Weekday::Sunday);
+//! assert_eq!(measurement_system(&locale), MeasurementSystem::UsSystem);
+//! assert_eq!(temperature_unit(&locale), TemperatureUnit::Fahrenheit);
+//!
+//! let locale = locale!("en-US-u-rg-dezzzz");
+//! assert_eq!(first_day_of_week(&locale), Weekday::Monday);
+//! assert_eq!(temperature_unit(&locale), TemperatureUnit::Celsius);
+//! ```
+
+use icu_locid::extensions::unicode::{key, Key};
+use icu_locid::subtags::{region, Region};
+use icu_locid::Locale;
+
+use crate::negotiate::{region_override, LocaleExpander};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeasurementSystem {
+    Metric,
+    /// The United States customary system.
+    UsSystem,
+    /// The metric system with imperial units for some measures, as used in
+    /// the United Kingdom.
+    UkSystem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+/// Regions whose week starts on Friday, sorted.
+static FRIDAY_FIRST: &[&str] = &["MV"];
+/// Regions whose week starts on Saturday, sorted.
+static SATURDAY_FIRST: &[&str] = &[
+    "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+];
+/// Regions whose week starts on Sunday, sorted.
+static SUNDAY_FIRST: &[&str] = &[
+    "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET", "GT", "GU",
+    "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO", "MT", "MX",
+    "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV", "TH", "TT", "TW",
+    "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+];
+/// Regions using the United States customary system, sorted.
+static US_SYSTEM: &[&str] = &["LR", "MM", "US"];
+/// Regions using the United Kingdom system, sorted.
+static UK_SYSTEM: &[&str] = &["GB"];
+/// Regions measuring temperatures in Fahrenheit, sorted.
+static FAHRENHEIT: &[&str] = &["BS", "BZ", "KY", "PR", "PW", "US"];
+
+/// Returns the region the preferences of the locale are resolved for.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::preferences::preference_region;
+/// use icu_locid::locale;
+/// use icu_locid::subtags::region;
+///
+/// assert_eq!(preference_region(&locale!("fr-CA")), region!("CA"));
+/// assert_eq!(preference_region(&locale!("fr-CA-u-rg-bezzzz")), region!("BE"));
+/// assert_eq!(preference_region(&locale!("und")), region!("001"));
+/// ```
+pub fn preference_region(locale: &Locale) -> Region {
+    if let Some(region) = region_override(locale).or(locale.id.region) {
+        return region;
+    }
+    let mut langid = locale.id.clone();
+    if !langid.language.is_empty() {
+        LocaleExpander::new().maximize(&mut langid);
+    }
+    langid.region.unwrap_or(region!("001"))
+}
+
+/// Returns the first day of the week of the locale.
+pub fn first_day_of_week(locale: &Locale) -> Weekday {
+    match keyword(locale, key!("fw")).as_deref() {
+        Some("mon") => return Weekday::Monday,
+        Some("tue") => return Weekday::Tuesday,
+        Some("wed") => return Weekday::Wednesday,
+        Some("thu") => return Weekday::Thursday,
+        Some("fri") => return Weekday::Friday,
+        Some("sat") => return Weekday::Saturday,
+        Some("sun") => return Weekday::Sunday,
+        _ => {}
+    }
+    let region = preference_region(locale);
+    if contains(FRIDAY_FIRST, region) {
+        Weekday::Friday
+    } else if contains(SATURDAY_FIRST, region) {
+        Weekday::Saturday
+    } else if contains(SUNDAY_FIRST, region) {
+        Weekday::Sunday
+    } else {
+        Weekday::Monday
+    }
+}
+
+/// Returns the measurement system of the locale.
+pub fn measurement_system(locale: &Locale) -> MeasurementSystem {
+    match keyword(locale, key!("ms")).as_deref() {
+        Some("metric") => return MeasurementSystem::Metric,
+        Some("ussystem") => return MeasurementSystem::UsSystem,
+        Some("uksystem") => return MeasurementSystem::UkSystem,
+        _ => {}
+    }
+    let region = preference_region(locale);
+    if contains(US_SYSTEM, region) {
+        MeasurementSystem::UsSystem
+    } else if contains(UK_SYSTEM, region) {
+        MeasurementSystem::UkSystem
+    } else {
+        MeasurementSystem::Metric
+    }
+}
+
+/// Returns the unit temperatures are displayed in for the locale.
+pub fn temperature_unit(locale: &Locale) -> TemperatureUnit {
+    match keyword(locale, key!("mu")).as_deref() {
+        Some("celsius") => return TemperatureUnit::Celsius,
+        Some("fahrenhe") => return TemperatureUnit::Fahrenheit,
+        _ => {}
+    }
+    if contains(FAHRENHEIT, preference_region(locale)) {
+        TemperatureUnit::Fahrenheit
+    } else {
+        TemperatureUnit::Celsius
+    }
+}
+
+fn keyword(locale: &Locale, key: Key) -> Option<String> {
+    locale
+        .extensions
+        .unicode
+        .keywords
+        .get(&key)
+        .map(|v| v.to_string())
+}
+
+fn contains(regions: &[&str], region: Region) -> bool {
+    regions.binary_search(&region.as_str()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions_sorted() {
+        for table in [
+            FRIDAY_FIRST,
+            SATURDAY_FIRST,
+            SUNDAY_FIRST,
+            US_SYSTEM,
+            UK_SYSTEM,
+            FAHRENHEIT,
+        ] {
+            assert!(table.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+}
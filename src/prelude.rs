@@ -0,0 +1,37 @@
+//! Commonly used items, for glob importing.
+//!
+//! The language identifier types are re-exported from the version of
+//! `icu_locid` this crate is built against, which avoids version mismatch
+//! errors when the application depends on a different one.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::prelude::*;
+//!
+//! let requested = convert_vec_str_to_langids_lossy(["de-AT", "en"]);
+//! let available = convert_vec_str_to_langids_lossy(["en-US", "de"]);
+//! let default: LanguageIdentifier = "en-US".parse().unwrap();
+//!
+//! let supported = negotiate_languages(
+//!     &requested,
+//!     &available,
+//!     Some(&default),
+//!     NegotiationStrategy::Filtering,
+//! );
+//! assert_eq!(supported, &[&available[1], &available[0]]);
+//! ```
+
+pub use crate::{
+    convert_vec_str_to_langids, convert_vec_str_to_langids_lossy,
+    convert_vec_str_to_langids_lossy_with, negotiate, negotiate_languages, ConvertError, Error,
+    LanguageIdentifier, LangugeIdentifierParserError, NegotiationOptions, NegotiationStrategy,
+};
+
+#[cfg(feature = "accept-language")]
+pub use crate::parse_accepted_languages;
+#[cfg(feature = "locale")]
+pub use crate::Locale;
+
+#[cfg(feature = "utils")]
+pub use crate::ext::LangIdExt;
@@ -0,0 +1,291 @@
+//! Registry of metadata attached to available locales.
+//!
+//! Applications usually keep some metadata about each of the locales they
+//! support, such as a native name, translation completeness or a flag.
+//!
+//! [`LocaleRegistry`] stores such metadata keyed by a language identifier,
+//! and looks it up using the same matching as language negotiation, so
+//! metadata registered under `de` is found for `de-AT`.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::registry::LocaleRegistry;
+//! use fluent_langneg::negotiate_languages;
+//! use fluent_langneg::NegotiationStrategy;
+//! use icu_locid::langid;
+//!
+//! let mut registry = LocaleRegistry::new();
+//! registry.insert(langid!("de"), "Deutsch");
+//! registry.insert(langid!("en-US"), "English (US)");
+//! registry.insert(langid!("en-GB"), "English (UK)");
+//!
+//! assert_eq!(registry.get(&langid!("de-AT")), Some(&"Deutsch"));
+//! assert_eq!(registry.get(&langid!("en-GB")), Some(&"English (UK)"));
+//! assert_eq!(registry.get(&langid!("fr")), None);
+//!
+//! let available: Vec<_> = registry.locales().collect();
+//! let supported = negotiate_languages(
+//!   &[langid!("en-CA")],
+//!   &available,
+//!   None,
+//!   NegotiationStrategy::Filtering
+//! );
+//!
+//! let names: Vec<_> = registry.attach(&supported).into_iter().map(|(_, name)| name).collect();
+//! assert_eq!(names, &[Some(&"English (US)"), Some(&"English (UK)")]);
+//! ```
+//!
+//! # Dynamic registration
+//!
+//! Locales can be registered and removed at runtime, for example when
+//! language packs are downloaded on demand. Each change bumps the
+//! [`LocaleRegistry::generation`] and is reported to the subscribers,
+//! so that negotiation results kept elsewhere can be invalidated.
+//!
+//! ```
+//! use std::sync::mpsc;
+//!
+//! use fluent_langneg::registry::{LocaleRegistry, RegistryEvent};
+//! use icu_locid::langid;
+//!
+//! let mut registry = LocaleRegistry::new();
+//! registry.insert(langid!("en-US"), "en-US.ftl");
+//!
+//! let (sender, receiver) = mpsc::channel();
+//! registry.subscribe(move |event| {
+//!     let _ = sender.send(event.clone());
+//! });
+//!
+//! registry.insert(langid!("de"), "de.ftl");
+//! registry.remove(&langid!("en-US"));
+//!
+//! assert_eq!(
+//!     receiver.try_iter().collect::<Vec<_>>(),
+//!     &[RegistryEvent::Inserted(langid!("de")), RegistryEvent::Removed(langid!("en-US"))]
+//! );
+//! assert_eq!(registry.generation(), 3);
+//! ```
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use icu_locid::LanguageIdentifier;
+
+use crate::negotiate::index::AvailableIndex;
+use crate::negotiate::{filter_indices, NegotiationStrategy};
+
+/// A change of a [`LocaleRegistry`], reported to its subscribers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegistryEvent {
+    /// A new locale has been registered.
+    Inserted(LanguageIdentifier),
+    /// The value of a registered locale has been replaced.
+    Replaced(LanguageIdentifier),
+    /// A locale has been removed.
+    Removed(LanguageIdentifier),
+}
+
+/// Identifies a subscriber of a [`LocaleRegistry`], see
+/// [`LocaleRegistry::unsubscribe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+type Subscriber = Box<dyn FnMut(&RegistryEvent) + Send>;
+
+/// Metadata of available locales, see the [module documentation](self).
+///
+/// Subscribers are not cloned along with the registry, and are not
+/// compared by `PartialEq`.
+///
+/// The subscribers are only required to be `Send`, so the registry is `Send`
+/// but not `Sync`. To share it between threads, wrap it in a `Mutex`.
+pub struct LocaleRegistry<T> {
+    locales: Vec<LanguageIdentifier>,
+    values: Vec<T>,
+    // Built on the first lookup after a change, so that registering many
+    // locales doesn't rebuild it each time.
+    index: OnceLock<AvailableIndex>,
+    generation: u64,
+    subscribers: Vec<(SubscriptionId, Subscriber)>,
+    next_subscription: u64,
+}
+
+impl<T> LocaleRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            locales: vec![],
+            values: vec![],
+            index: OnceLock::new(),
+            generation: 0,
+            subscribers: vec![],
+            next_subscription: 0,
+        }
+    }
+
+    /// Registers the value for the locale.
+    ///
+    /// If the locale has already been registered, the previous value
+    /// is replaced and returned.
+    pub fn insert(&mut self, langid: LanguageIdentifier, value: T) -> Option<T> {
+        let idx = self.locales.iter().position(|l| *l == langid);
+        match idx.and_then(|idx| self.values.get_mut(idx)) {
+            Some(current) => {
+                let previous = std::mem::replace(current, value);
+                self.changed(RegistryEvent::Replaced(langid));
+                Some(previous)
+            }
+            None => {
+                self.locales.push(langid.clone());
+                self.values.push(value);
+                self.changed(RegistryEvent::Inserted(langid));
+                None
+            }
+        }
+    }
+
+    /// Removes the locale from the registry, returning its value.
+    pub fn remove(&mut self, langid: &LanguageIdentifier) -> Option<T> {
+        let idx = self.locales.iter().position(|l| l == langid)?;
+        let langid = self.locales.remove(idx);
+        let value = self.values.remove(idx);
+        self.changed(RegistryEvent::Removed(langid));
+        Some(value)
+    }
+
+    /// Returns the number of changes made to the registry so far.
+    ///
+    /// A negotiation result computed against the registered locales stays
+    /// valid for as long as the generation doesn't change.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Registers a callback called with each following change of the registry.
+    ///
+    /// The callback runs synchronously within the modifying call, so it
+    /// should be cheap, for example sending the event over a channel.
+    pub fn subscribe<F>(&mut self, callback: F) -> SubscriptionId
+    where
+        F: FnMut(&RegistryEvent) + Send + 'static,
+    {
+        let id = SubscriptionId(self.next_subscription);
+        self.next_subscription += 1;
+        self.subscribers.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Removes a subscriber. Returns `false` if it has already been removed.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let len = self.subscribers.len();
+        self.subscribers.retain(|(sid, _)| *sid != id);
+        self.subscribers.len() != len
+    }
+
+    fn changed(&mut self, event: RegistryEvent) {
+        if !matches!(event, RegistryEvent::Replaced(_)) {
+            self.index.take();
+        }
+        self.generation += 1;
+        for (_, subscriber) in self.subscribers.iter_mut() {
+            subscriber(&event);
+        }
+    }
+
+    /// Returns the value registered for the best available match
+    /// of the requested locale.
+    pub fn get(&self, requested: &LanguageIdentifier) -> Option<&T> {
+        let found = filter_indices(
+            &[requested],
+            &self.locales,
+            self.index
+                .get_or_init(|| AvailableIndex::new(&self.locales)),
+            NegotiationStrategy::Lookup,
+        );
+        found.first().and_then(|idx| self.values.get(*idx))
+    }
+
+    /// Returns the value registered for exactly this locale.
+    pub fn get_exact(&self, langid: &LanguageIdentifier) -> Option<&T> {
+        let idx = self.locales.iter().position(|l| l == langid)?;
+        self.values.get(idx)
+    }
+
+    /// Pairs each locale of a negotiation result with the value
+    /// registered for it.
+    pub fn attach<'a, A: AsRef<LanguageIdentifier>>(
+        &self,
+        supported: &'a [A],
+    ) -> Vec<(&'a A, Option<&T>)> {
+        supported
+            .iter()
+            .map(|langid| (langid, self.get(langid.as_ref())))
+            .collect()
+    }
+
+    /// Iterates over the registered locales, in the order of registration.
+    ///
+    /// The result can be used as the available locales for negotiation.
+    pub fn locales(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+        self.locales.iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&LanguageIdentifier, &T)> {
+        self.locales.iter().zip(self.values.iter())
+    }
+
+    pub fn len(&self) -> usize {
+        self.locales.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locales.is_empty()
+    }
+}
+
+impl<T: Clone> Clone for LocaleRegistry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            locales: self.locales.clone(),
+            values: self.values.clone(),
+            index: self.index.clone(),
+            generation: self.generation,
+            subscribers: vec![],
+            next_subscription: 0,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for LocaleRegistry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.locales == other.locales && self.values == other.values
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LocaleRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocaleRegistry")
+            .field("locales", &self.locales)
+            .field("values", &self.values)
+            .field("generation", &self.generation)
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+impl<T> Default for LocaleRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(LanguageIdentifier, T)> for LocaleRegistry<T> {
+    fn from_iter<I: IntoIterator<Item = (LanguageIdentifier, T)>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for (langid, value) in iter {
+            registry.insert(langid, value);
+        }
+        registry
+    }
+}
@@ -0,0 +1,139 @@
+//! Compatibility with RFC 3066 language tags, as found in `xml:lang`.
+//!
+//! XML documents, as well as older metadata formats, carry language tags
+//! following RFC 3066, which BCP47 obsoleted. Most of them are still valid,
+//! but some need to be converted before taking part in negotiation:
+//!
+//! - tags registered as a whole, such as `i-klingon` or `zh-min-nan`, are
+//!   replaced with their modern equivalent, `tlh` and `nan`,
+//! - ISO 639-2 codes, such as `ger`, and deprecated subtags are replaced
+//!   like [`canonicalize::parse`] does,
+//! - private use subtags, starting at `x-`, are dropped.
+//!
+//! An empty `xml:lang` value is valid, and means that the content has no
+//! language, unlike `und`, which is content in an undetermined language.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::rfc3066;
+//! use icu_locid::langid;
+//!
+//! assert_eq!(rfc3066::parse_xml_lang("i-navajo").unwrap(), Some(langid!("nv")));
+//! assert_eq!(rfc3066::parse_xml_lang("de-DE-x-dialect").unwrap(), Some(langid!("de-DE")));
+//! assert_eq!(rfc3066::parse_xml_lang("").unwrap(), None);
+//! assert_eq!(rfc3066::to_xml_lang(None), "");
+//! ```
+
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::string_slice,
+    clippy::panic
+)]
+
+use icu_locid::{LanguageIdentifier, ParserError};
+
+use crate::{canonicalize, Error};
+
+/// Tags registered as a whole, mapped to their replacement, or to `None`
+/// if they have none.
+///
+/// Sorted by the tag.
+static REGISTERED_TAGS: &[(&str, Option<&str>)] = &[
+    ("art-lojban", Some("jbo")),
+    ("cel-gaulish", None),
+    ("en-gb-oed", Some("en-GB-oxendict")),
+    ("i-ami", Some("ami")),
+    ("i-bnn", Some("bnn")),
+    ("i-default", None),
+    ("i-enochian", None),
+    ("i-hak", Some("hak")),
+    ("i-klingon", Some("tlh")),
+    ("i-lux", Some("lb")),
+    ("i-mingo", None),
+    ("i-navajo", Some("nv")),
+    ("i-pwn", Some("pwn")),
+    ("i-tao", Some("tao")),
+    ("i-tay", Some("tay")),
+    ("i-tsu", Some("tsu")),
+    ("no-bok", Some("nb")),
+    ("no-nyn", Some("nn")),
+    ("sgn-be-fr", Some("sfb")),
+    ("sgn-be-nl", Some("vgt")),
+    ("sgn-ch-de", Some("sgg")),
+    ("zh-guoyu", Some("cmn")),
+    ("zh-hakka", Some("hak")),
+    ("zh-min", None),
+    ("zh-min-nan", Some("nan")),
+    ("zh-xiang", Some("hsn")),
+];
+
+/// Parses an RFC 3066 language tag into a language identifier.
+///
+/// Tags which have no modern equivalent, such as `i-default`, as well as
+/// private use tags, such as `x-klingon`, fail to parse.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::rfc3066;
+/// use icu_locid::langid;
+///
+/// assert_eq!(rfc3066::parse("no-bok").unwrap(), langid!("nb"));
+/// assert_eq!(rfc3066::parse("fre-CA").unwrap(), langid!("fr-CA"));
+/// assert!(rfc3066::parse("i-default").is_err());
+/// ```
+pub fn parse(tag: &str) -> Result<LanguageIdentifier, Error> {
+    let invalid = |offset, source| Error::Parse {
+        input: tag.to_string(),
+        offset,
+        source,
+    };
+
+    let mut offset = 0;
+    for subtag in tag.split('-') {
+        let valid = (1..=8).contains(&subtag.len())
+            && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+            && (offset > 0 || subtag.bytes().all(|b| b.is_ascii_alphabetic()));
+        if !valid {
+            return Err(invalid(offset, ParserError::InvalidSubtag));
+        }
+        offset += subtag.len() + 1;
+    }
+
+    let lowercase = tag.to_ascii_lowercase();
+    let idx = REGISTERED_TAGS.binary_search_by(|(t, _)| t.cmp(&lowercase.as_str()));
+    if let Some((_, replacement)) = idx.ok().and_then(|idx| REGISTERED_TAGS.get(idx)) {
+        return match replacement {
+            Some(replacement) => crate::parse_langid(replacement),
+            None => Err(invalid(0, ParserError::InvalidLanguage)),
+        };
+    }
+
+    let public = match lowercase.find("-x-") {
+        Some(end) => tag.get(..end).unwrap_or(tag),
+        None if lowercase.starts_with("x-") || lowercase.starts_with("i-") => {
+            return Err(invalid(0, ParserError::InvalidLanguage));
+        }
+        None => tag,
+    };
+    canonicalize::parse(public)
+}
+
+/// Parses an `xml:lang` value, where an empty value means no language.
+///
+/// Leading and trailing whitespace is ignored.
+pub fn parse_xml_lang(value: &str) -> Result<Option<LanguageIdentifier>, Error> {
+    match value.trim() {
+        "" => Ok(None),
+        tag => parse(tag).map(Some),
+    }
+}
+
+/// Returns the `xml:lang` value of a language, where `None` is an empty
+/// value meaning no language.
+pub fn to_xml_lang(langid: Option<&LanguageIdentifier>) -> String {
+    langid.map_or_else(String::new, |langid| langid.to_string())
+}
@@ -0,0 +1,189 @@
+//! Locales of web requests from their URL.
+//!
+//! Localized websites usually carry the locale in the URL, either as the
+//! first segment of the path, such as `/en-GB/docs`, or as a subdomain,
+//! such as `fr.example.com`, and combine it with the `Accept-Language`
+//! header into the requested locales of the negotiation.
+//!
+//! Many path segments and subdomains, such as `api` or `www`, are valid
+//! language identifiers, so a locale is only extracted from the URL if it
+//! is one of the locales the site uses in its URLs.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::routing::{locale_from_path, requested_locales, UrlPriority};
+//! use fluent_langneg::{negotiate_languages, parse_accepted_languages, NegotiationStrategy};
+//! use icu_locid::langid;
+//!
+//! let available = [langid!("en-US"), langid!("fr"), langid!("fr-CA")];
+//!
+//! let (url_locale, rest) = locale_from_path("/fr/docs/", &available).unwrap();
+//! assert_eq!(rest, "/docs/");
+//!
+//! let requested = requested_locales(
+//!     Some(url_locale),
+//!     &parse_accepted_languages("fr-CA,en;q=0.5"),
+//!     UrlPriority::Refined,
+//! );
+//! assert_eq!(requested, &[langid!("fr-CA"), langid!("fr"), langid!("en")]);
+//!
+//! let supported = negotiate_languages(&requested, &available, None, NegotiationStrategy::Lookup);
+//! assert_eq!(supported, &[&available[2]]);
+//! ```
+
+use icu_locid::LanguageIdentifier;
+
+use crate::subtags::is_within;
+
+/// How the locale of the URL is combined with the `Accept-Language` ones,
+/// see [`requested_locales`].
+///
+/// New priorities may be added in the future, so matches over it need
+/// a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum UrlPriority {
+    /// The URL locale comes first, followed by the `Accept-Language` ones.
+    #[default]
+    First,
+    /// Same as `First`, but the `Accept-Language` locales within the URL
+    /// locale come before it, so that `/fr/` with `fr-CA` preferred
+    /// requests `fr-CA` first.
+    Refined,
+    /// The `Accept-Language` locales come first, and the URL locale is
+    /// only a fallback.
+    Last,
+    /// The URL locale is the only requested locale, and the
+    /// `Accept-Language` ones are only used without one.
+    Exclusive,
+}
+
+/// Returns the locale of the first segment of the path, if it is one of
+/// `allowed`, with the rest of the path.
+///
+/// The segment is compared ignoring case and the `-`/`_` separators, and
+/// a query or a fragment following it is part of the rest.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::routing::locale_from_path;
+/// use icu_locid::langid;
+///
+/// let allowed = [langid!("en-GB"), langid!("de")];
+///
+/// assert_eq!(locale_from_path("/en-gb/docs", &allowed), Some((&allowed[0], "/docs")));
+/// assert_eq!(locale_from_path("/de?q=1", &allowed), Some((&allowed[1], "?q=1")));
+/// assert_eq!(locale_from_path("/api/v1", &allowed), None);
+/// ```
+pub fn locale_from_path<'p, 'a, A: AsRef<LanguageIdentifier>>(
+    path: &'p str,
+    allowed: &'a [A],
+) -> Option<(&'a A, &'p str)> {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    let end = path.find(['/', '?', '#']).unwrap_or(path.len());
+    let (segment, rest) = path.split_at(end);
+    Some((find_allowed(segment, allowed)?, rest))
+}
+
+/// Returns the locale of the first label of the host, if it is one of
+/// `allowed`.
+///
+/// Only hosts with at least three labels have a locale, and a port
+/// is ignored.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::routing::locale_from_host;
+/// use icu_locid::langid;
+///
+/// let allowed = [langid!("fr"), langid!("pt-BR")];
+///
+/// assert_eq!(locale_from_host("fr.example.com", &allowed), Some(&allowed[0]));
+/// assert_eq!(locale_from_host("PT-BR.example.com:8080", &allowed), Some(&allowed[1]));
+/// assert_eq!(locale_from_host("www.example.com", &allowed), None);
+/// assert_eq!(locale_from_host("fr.com", &allowed), None);
+/// ```
+pub fn locale_from_host<'a, A: AsRef<LanguageIdentifier>>(
+    host: &str,
+    allowed: &'a [A],
+) -> Option<&'a A> {
+    let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+    let mut labels = host.split('.');
+    let label = labels.next()?;
+    if labels.count() < 2 {
+        return None;
+    }
+    find_allowed(label, allowed)
+}
+
+fn find_allowed<'a, A: AsRef<LanguageIdentifier>>(tag: &str, allowed: &'a [A]) -> Option<&'a A> {
+    let langid = LanguageIdentifier::try_from_bytes(tag.as_bytes()).ok()?;
+    allowed.iter().find(|a| *a.as_ref() == langid)
+}
+
+/// Combines the locale of the URL with the `Accept-Language` locales into
+/// the requested locales of the negotiation.
+///
+/// Locales requested more than once are only kept at their first position.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::routing::{requested_locales, UrlPriority};
+/// use icu_locid::langid;
+///
+/// let header = [langid!("de-AT"), langid!("en")];
+///
+/// assert_eq!(
+///     requested_locales(Some(&langid!("en")), &header, UrlPriority::First),
+///     &[langid!("en"), langid!("de-AT")]
+/// );
+/// assert_eq!(
+///     requested_locales(Some(&langid!("fr")), &header, UrlPriority::Last),
+///     &[langid!("de-AT"), langid!("en"), langid!("fr")]
+/// );
+/// assert_eq!(
+///     requested_locales(None, &header, UrlPriority::Exclusive),
+///     &header
+/// );
+/// ```
+pub fn requested_locales<R: AsRef<LanguageIdentifier>>(
+    url: Option<&LanguageIdentifier>,
+    accept_language: &[R],
+    priority: UrlPriority,
+) -> Vec<LanguageIdentifier> {
+    let header = accept_language.iter().map(AsRef::as_ref);
+    let mut result: Vec<LanguageIdentifier> = Vec::with_capacity(accept_language.len() + 1);
+    let mut push = |langid: &LanguageIdentifier| {
+        if !result.contains(langid) {
+            result.push(langid.clone());
+        }
+    };
+    match (url, priority) {
+        (None, _) => header.for_each(push),
+        (Some(url), UrlPriority::Exclusive) => push(url),
+        (Some(url), UrlPriority::Last) => {
+            header.for_each(&mut push);
+            push(url);
+        }
+        (Some(url), UrlPriority::Refined) => {
+            header
+                .clone()
+                .filter(|langid| is_within(langid, url))
+                .for_each(&mut push);
+            push(url);
+            header.for_each(push);
+        }
+        (Some(url), UrlPriority::First) => {
+            push(url);
+            header.for_each(push);
+        }
+    }
+    result
+}
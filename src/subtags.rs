@@ -0,0 +1,337 @@
+//! Operations on the subtags of language identifiers.
+//!
+//! Fallback resolution code often has to combine a user locale with
+//! a default one, or find out in which subtags two locales differ.
+//!
+//! Routing and caching layers need to decide whether a resource tagged
+//! with a broader locale can serve a request for a more specific one,
+//! see [`is_within`].
+//!
+//! Resource fallback implementations can walk from a specific locale towards
+//! more generic ones with [`truncate`] and [`common_ancestor`].
+//!
+//! For clustering and analytics over user locales, [`distance`] scores
+//! how far apart two locales are, independently of negotiation.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::subtags::{difference, merge};
+//! use icu_locid::langid;
+//!
+//! let user = langid!("de");
+//! let default = langid!("en-Latn-US");
+//!
+//! let diff = difference(&user, &default);
+//! assert!(diff.language && diff.script && diff.region);
+//! assert!(!diff.variants);
+//!
+//! assert_eq!(merge(&user, &default), langid!("de-Latn-US"));
+//! ```
+
+use icu_locid::subtags::{Language, Variant, Variants};
+use icu_locid::LanguageIdentifier;
+
+use crate::negotiate::{matches, LocaleExpander};
+
+/// Subtags in which two language identifiers differ.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Difference {
+    pub language: bool,
+    pub script: bool,
+    pub region: bool,
+    pub variants: bool,
+}
+
+impl Difference {
+    /// Returns `true` if the language identifiers are the same.
+    pub fn is_empty(&self) -> bool {
+        !(self.language || self.script || self.region || self.variants)
+    }
+}
+
+/// Returns the subtags in which the two language identifiers differ.
+pub fn difference(lid1: &LanguageIdentifier, lid2: &LanguageIdentifier) -> Difference {
+    Difference {
+        language: lid1.language != lid2.language,
+        script: lid1.script != lid2.script,
+        region: lid1.region != lid2.region,
+        variants: lid1.variants != lid2.variants,
+    }
+}
+
+/// Copies the subtags missing in `langid` from `other`.
+///
+/// An `und` language and an empty list of variants are considered missing.
+///
+/// Returns `true` if the language identifier has been modified.
+pub fn fill_missing(langid: &mut LanguageIdentifier, other: &LanguageIdentifier) -> bool {
+    let mut modified = false;
+    if langid.language.is_empty() && !other.language.is_empty() {
+        langid.language = other.language;
+        modified = true;
+    }
+    if langid.script.is_none() && other.script.is_some() {
+        langid.script = other.script;
+        modified = true;
+    }
+    if langid.region.is_none() && other.region.is_some() {
+        langid.region = other.region;
+        modified = true;
+    }
+    if langid.variants.is_empty() && !other.variants.is_empty() {
+        langid.variants = other.variants.clone();
+        modified = true;
+    }
+    modified
+}
+
+/// Returns a new language identifier with the subtags of `langid`,
+/// and the subtags missing in it taken from `other`.
+pub fn merge(langid: &LanguageIdentifier, other: &LanguageIdentifier) -> LanguageIdentifier {
+    let mut result = langid.clone();
+    fill_missing(&mut result, other);
+    result
+}
+
+/// Returns `true` if `specific` is covered by `range`, treating missing
+/// subtags of `range` as wildcards.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::subtags::is_within;
+/// use icu_locid::langid;
+///
+/// assert!(is_within(&langid!("en-Latn-US"), &langid!("en")));
+/// assert!(is_within(&langid!("en-GB"), &langid!("en")));
+/// assert!(!is_within(&langid!("en"), &langid!("en-Latn-US")));
+/// assert!(is_within(&langid!("fr"), &langid!("und")));
+/// ```
+pub fn is_within(specific: &LanguageIdentifier, range: &LanguageIdentifier) -> bool {
+    matches(range, specific, true, false)
+}
+
+/// Returns the number of subtags present in the language identifier.
+///
+/// An `und` language is not counted, while each variant is.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::subtags::specificity;
+/// use icu_locid::langid;
+///
+/// assert_eq!(specificity(&langid!("und")), 0);
+/// assert_eq!(specificity(&langid!("en")), 1);
+/// assert_eq!(specificity(&langid!("en-GB")), 2);
+/// assert_eq!(specificity(&langid!("de-Latn-DE-1996")), 4);
+/// ```
+pub fn specificity(langid: &LanguageIdentifier) -> u8 {
+    let count = usize::from(!langid.language.is_empty())
+        + usize::from(langid.script.is_some())
+        + usize::from(langid.region.is_some())
+        + langid.variants.len();
+    u8::try_from(count).unwrap_or(u8::MAX)
+}
+
+/// Variant subtags of the IANA Language Subtag Registry.
+static REGISTERED_VARIANTS: &[&str] = &[
+    "1606nict", "1694acad", "1901", "1959acad", "1994", "1996", "abl1943", "akhmimic", "akuapem",
+    "alalc97", "aluku", "anpezo", "ao1990", "aranes", "arevela", "arevmda", "arkaika", "asante",
+    "auvern", "baku1926", "balanka", "barla", "basiceng", "bauddha", "bciav", "bcizbl", "biscayan",
+    "biske", "blasl", "bohoric", "boont", "bornholm", "cisaup", "colb1945", "cornu", "creiss",
+    "dajnko", "ekavsk", "emodeng", "fascia", "fodom", "fonipa", "fonkirsh", "fonnapa", "fonupa",
+    "fonxsamp", "gallo", "gascon", "gherd", "grclass", "grital", "grmistr", "hepburn", "heploc",
+    "hognorsk", "hsistemo", "ijekavsk", "itihasa", "ivanchov", "jauer", "jyutping", "kkcor",
+    "kociewie", "kscor", "laukika", "leidentr", "lemosin", "lengadoc", "lipaw", "ltg1929",
+    "ltg2007", "luna1918", "mdcegyp", "mdctrans", "metelko", "monoton", "ndyuka", "nedis",
+    "newfound", "nicard", "njiva", "nulik", "osojs", "oxendict", "pahawh2", "pahawh3", "pahawh4",
+    "pamaka", "peano", "pehoeji", "petr1708", "pinyin", "polyton", "provenc", "puter", "rigik",
+    "rozaj", "rumgr", "scotland", "scouse", "simple", "solba", "sotav", "spanglis", "surmiran",
+    "sursilv", "sutsilv", "synnejyl", "tailo", "tarask", "tongyong", "tunumiit", "uccor", "ucrcor",
+    "ulster", "unifon", "vaidika", "valbadia", "valencia", "vallader", "vecdruka", "viennese",
+    "vivaraup", "wadegile", "xsistemo",
+];
+
+/// Returns `true` if the variant is registered in the IANA Language Subtag
+/// Registry, as opposed to a private one, such as a product or customer
+/// specific build of a locale.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::subtags::is_registered_variant;
+/// use icu_locid::subtags::variant;
+///
+/// assert!(is_registered_variant(variant!("1996")));
+/// assert!(is_registered_variant(variant!("valencia")));
+/// assert!(!is_registered_variant(variant!("acmecorp")));
+/// ```
+pub fn is_registered_variant(variant: Variant) -> bool {
+    REGISTERED_VARIANTS.binary_search(&variant.as_str()).is_ok()
+}
+
+/// Distance added when the maximized languages differ.
+pub const LANGUAGE_DISTANCE: u16 = 100;
+/// Distance added when the maximized scripts differ.
+pub const SCRIPT_DISTANCE: u16 = 40;
+/// Distance added when the maximized regions differ.
+pub const REGION_DISTANCE: u16 = 4;
+/// Distance added when the variants differ.
+pub const VARIANTS_DISTANCE: u16 = 2;
+/// Distance added when the language identifiers are only equal
+/// after maximization.
+pub const MAXIMIZATION_DISTANCE: u16 = 1;
+
+/// Scores how far apart two language identifiers are.
+///
+/// Both language identifiers are maximized using likely subtags first,
+/// unless their language is `und`, and each differing subtag adds its
+/// distance. `0` means the language identifiers are identical.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::subtags::distance;
+/// use icu_locid::langid;
+///
+/// assert_eq!(distance(&langid!("en-US"), &langid!("en-US")), 0);
+/// assert_eq!(distance(&langid!("en"), &langid!("en-Latn-US")), 1);
+/// assert_eq!(distance(&langid!("de-AT"), &langid!("de-Latn-AT")), 1);
+/// assert!(distance(&langid!("en-US"), &langid!("en-GB")) < distance(&langid!("sr-Cyrl"), &langid!("sr-Latn")));
+/// assert!(distance(&langid!("sr-Cyrl"), &langid!("sr-Latn")) < distance(&langid!("en"), &langid!("fr")));
+/// ```
+pub fn distance(lid1: &LanguageIdentifier, lid2: &LanguageIdentifier) -> u16 {
+    if lid1 == lid2 {
+        return 0;
+    }
+
+    let lc = LocaleExpander::new();
+    let maximize = |lid: &LanguageIdentifier| {
+        let mut lid = lid.clone();
+        if !lid.language.is_empty() {
+            lc.maximize(&mut lid);
+        }
+        lid
+    };
+    let diff = difference(&maximize(lid1), &maximize(lid2));
+
+    let mut result = 0;
+    if diff.language {
+        result += LANGUAGE_DISTANCE;
+    }
+    if diff.script {
+        result += SCRIPT_DISTANCE;
+    }
+    if diff.region {
+        result += REGION_DISTANCE;
+    }
+    if diff.variants {
+        result += VARIANTS_DISTANCE;
+    }
+    if result == 0 {
+        MAXIMIZATION_DISTANCE
+    } else {
+        result
+    }
+}
+
+/// Returns the language identifier with its least significant present
+/// subtag removed, or `None` if there is nothing left to remove.
+///
+/// Variants are removed one at a time, starting from the last one, followed
+/// by the region, the script, and finally the language.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::subtags::truncate;
+/// use icu_locid::langid;
+///
+/// assert_eq!(truncate(&langid!("sr-Latn-RS")), Some(langid!("sr-Latn")));
+/// assert_eq!(truncate(&langid!("sr-Latn")), Some(langid!("sr")));
+/// assert_eq!(truncate(&langid!("sr")), Some(langid!("und")));
+/// assert_eq!(truncate(&langid!("und")), None);
+/// ```
+pub fn truncate(langid: &LanguageIdentifier) -> Option<LanguageIdentifier> {
+    let mut result = langid.clone();
+    if let Some((_, rest)) = langid.variants.split_last() {
+        result.variants = Variants::from_vec_unchecked(rest.to_vec());
+    } else if result.region.is_some() {
+        result.region = None;
+    } else if result.script.is_some() {
+        result.script = None;
+    } else if !result.language.is_empty() {
+        result.language = Language::UND;
+    } else {
+        return None;
+    }
+    Some(result)
+}
+
+/// Returns the most specific language identifier which both language
+/// identifiers descend from, or `None` if their languages differ.
+///
+/// Subtags are compared from the most significant one, and the first
+/// difference ends the common part.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_langneg::subtags::common_ancestor;
+/// use icu_locid::langid;
+///
+/// assert_eq!(
+///     common_ancestor(&langid!("sr-Latn-RS"), &langid!("sr-Latn-ME")),
+///     Some(langid!("sr-Latn"))
+/// );
+/// assert_eq!(
+///     common_ancestor(&langid!("en-US"), &langid!("en-Latn-US")),
+///     Some(langid!("en"))
+/// );
+/// assert_eq!(common_ancestor(&langid!("en"), &langid!("fr")), None);
+/// ```
+pub fn common_ancestor(
+    lid1: &LanguageIdentifier,
+    lid2: &LanguageIdentifier,
+) -> Option<LanguageIdentifier> {
+    if lid1.language != lid2.language {
+        return None;
+    }
+    let mut result = LanguageIdentifier::from(lid1.language);
+    if lid1.script != lid2.script {
+        return Some(result);
+    }
+    result.script = lid1.script;
+    if lid1.region != lid2.region {
+        return Some(result);
+    }
+    result.region = lid1.region;
+    let common = lid1
+        .variants
+        .iter()
+        .zip(lid2.variants.iter())
+        .take_while(|(v1, v2)| v1 == v2)
+        .map(|(v, _)| *v)
+        .collect();
+    result.variants = Variants::from_vec_unchecked(common);
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_variants_sorted() {
+        assert!(REGISTERED_VARIANTS.windows(2).all(|w| w[0] < w[1]));
+        for variant in REGISTERED_VARIANTS {
+            assert!(
+                Variant::try_from_bytes(variant.as_bytes()).is_ok(),
+                "{}",
+                variant
+            );
+        }
+    }
+}
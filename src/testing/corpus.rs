@@ -0,0 +1,194 @@
+//! Generated corpora for benchmarking negotiation.
+//!
+//! The crate benchmarks use these generators, so that applications and
+//! bindings can measure their own setups against the same inputs and compare
+//! the numbers with the ones of the crate.
+//!
+//! The generators are deterministic: the same arguments always produce the
+//! same locales, in the same order.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::testing::corpus::{available_locales, requested_locales, RequestKind};
+//! use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+//!
+//! let available = available_locales(100);
+//! assert_eq!(available.len(), 100);
+//!
+//! let requested = requested_locales(&available, 5, RequestKind::Hit);
+//! let supported = negotiate_languages(&requested, &available, None, NegotiationStrategy::Filtering);
+//! assert!(supported.len() >= 5);
+//!
+//! let requested = requested_locales(&available, 5, RequestKind::Miss);
+//! let supported = negotiate_languages(&requested, &available, None, NegotiationStrategy::Filtering);
+//! assert!(supported.is_empty());
+//! ```
+
+use icu_locid::subtags::Region;
+use icu_locid::LanguageIdentifier;
+
+/// Languages of the available locales, from the most commonly localized.
+const LANGUAGES: &[&str] = &[
+    "en", "de", "fr", "es", "it", "pt", "ja", "zh-Hans", "zh-Hant", "ru", "ko", "nl", "pl", "sv",
+    "tr", "ar", "he", "cs", "da", "fi", "nb", "el", "hu", "ro", "uk", "id", "th", "vi", "hi", "bn",
+    "fa", "ms", "sk", "bg", "hr", "sr-Cyrl", "sr-Latn", "sl", "lt", "lv", "et", "ca", "eu", "gl",
+    "is", "ga", "cy", "sq", "mk", "ka", "hy", "az", "kk", "uz-Latn", "ta", "te", "mr", "ur", "sw",
+    "af",
+];
+
+/// Regions combined with the languages, from the most commonly localized.
+const REGIONS: &[&str] = &[
+    "US", "GB", "DE", "FR", "ES", "IT", "BR", "JP", "CN", "TW", "RU", "KR", "NL", "PL", "SE", "TR",
+    "SA", "IL", "CZ", "DK", "FI", "NO", "GR", "HU", "RO", "UA", "ID", "TH", "VN", "IN", "CA", "AU",
+    "MX", "AR", "CH", "AT", "BE", "PT", "IE", "NZ",
+];
+
+/// Languages which [`available_locales`] never generates.
+const MISSING_LANGUAGES: &[&str] = &[
+    "am", "as", "be", "bs", "fo", "fy", "gu", "haw", "kl", "km", "kn", "ky", "lo", "mi", "mn",
+    "mt", "my", "ne", "or", "pa", "ps", "si", "so", "tg", "tk", "to", "xh", "yo", "zu",
+];
+
+/// `Accept-Language` headers sent by browsers.
+pub const ACCEPT_LANGUAGE_SAMPLES: &[&str] = &[
+    "en-US,en;q=0.9",
+    "en-GB,en-US;q=0.9,en;q=0.8",
+    "de-DE,de;q=0.9,en-US;q=0.8,en;q=0.7",
+    "fr-FR,fr;q=0.9,en-US;q=0.8,en;q=0.7",
+    "es-419,es;q=0.9",
+    "pt-BR,pt;q=0.9,en-US;q=0.8,en;q=0.7",
+    "ja,en-US;q=0.9,en;q=0.8",
+    "zh-CN,zh;q=0.9",
+    "zh-TW,zh;q=0.9,en-US;q=0.8,en;q=0.7",
+    "ko-KR,ko;q=0.9,en-US;q=0.8,en;q=0.7",
+    "ru-RU,ru;q=0.9,en-US;q=0.8,en;q=0.7",
+    "tr-TR,tr;q=0.9,en-US;q=0.8,en;q=0.7,de;q=0.6",
+    "nl-BE,nl;q=0.9,fr-BE;q=0.8,fr;q=0.7,en;q=0.6",
+    "sr-Latn-RS,sr;q=0.8,en;q=0.5",
+    "ar,en-US;q=0.7,en;q=0.3",
+    "*",
+];
+
+/// Returns the maximum number of locales [`available_locales`] generates.
+pub fn max_available_locales() -> usize {
+    LANGUAGES.len() * (REGIONS.len() + 1)
+}
+
+/// Generates `count` distinct available locales.
+///
+/// The languages come first, then the languages combined with regions, so
+/// small sets look like the locales of an application, and large ones like
+/// the locales of a platform. At most [`max_available_locales`] are returned.
+pub fn available_locales(count: usize) -> Vec<LanguageIdentifier> {
+    (0..=REGIONS.len())
+        .flat_map(|round| {
+            LANGUAGES.iter().enumerate().map(move |(i, language)| {
+                if round == 0 {
+                    (*language).to_string()
+                } else {
+                    let region = REGIONS[(i + round - 1) % REGIONS.len()];
+                    format!("{language}-{region}")
+                }
+            })
+        })
+        .filter_map(|tag| tag.parse().ok())
+        .take(count)
+        .collect()
+}
+
+/// The kind of requested locales generated by [`requested_locales`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    /// Locales taken from the available ones, matching exactly.
+    Hit,
+    /// Locales with languages missing from the available ones.
+    Miss,
+    /// Locales with the language of an available one, but another region,
+    /// which go through the later steps of the matching, including the
+    /// addition of likely subtags.
+    Maximize,
+}
+
+/// Generates `count` distinct requested locales of the given kind against
+/// the available locales.
+///
+/// Fewer locales are returned for a `Hit` or `Maximize` request larger than
+/// the available locales allow.
+pub fn requested_locales(
+    available: &[LanguageIdentifier],
+    count: usize,
+    kind: RequestKind,
+) -> Vec<LanguageIdentifier> {
+    match kind {
+        RequestKind::Hit => {
+            // Evenly spaced, so that large sets aren't only hit on their
+            // first locales.
+            let count = count.min(available.len());
+            (0..count)
+                .map(|i| available[i * available.len() / count].clone())
+                .collect()
+        }
+        RequestKind::Miss => {
+            let count = count.min(MISSING_LANGUAGES.len() * REGIONS.len());
+            (0..count)
+                .filter_map(|i| {
+                    let language = MISSING_LANGUAGES[i % MISSING_LANGUAGES.len()];
+                    let region = REGIONS[i / MISSING_LANGUAGES.len()];
+                    format!("{language}-{region}").parse().ok()
+                })
+                .collect()
+        }
+        RequestKind::Maximize => {
+            let mut requested: Vec<LanguageIdentifier> = Vec::with_capacity(count);
+            for i in 0..available.len() * REGIONS.len() {
+                if requested.len() == count {
+                    break;
+                }
+                let mut langid = available[i % available.len()].clone();
+                langid.region = REGIONS[(i + i / available.len()) % REGIONS.len()]
+                    .parse::<Region>()
+                    .ok();
+                if !available.contains(&langid) && !requested.contains(&langid) {
+                    requested.push(langid);
+                }
+            }
+            requested
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_languages() {
+        for missing in MISSING_LANGUAGES {
+            assert!(!LANGUAGES
+                .iter()
+                .any(|l| l.split('-').next() == Some(missing)));
+        }
+    }
+
+    #[test]
+    fn test_requested_locales() {
+        let available = available_locales(70);
+        for kind in [RequestKind::Hit, RequestKind::Miss, RequestKind::Maximize] {
+            let requested = requested_locales(&available, 20, kind);
+            assert_eq!(requested.len(), 20);
+            for (i, langid) in requested.iter().enumerate() {
+                assert!(!requested[..i].contains(langid), "{langid} is duplicated");
+            }
+        }
+    }
+
+    #[test]
+    fn test_available_locales() {
+        let available = available_locales(usize::MAX);
+        assert_eq!(available.len(), max_available_locales());
+        for (i, langid) in available.iter().enumerate() {
+            assert!(!available[..i].contains(langid), "{langid} is duplicated");
+        }
+    }
+}
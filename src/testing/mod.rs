@@ -0,0 +1,229 @@
+//! Conformance suite of language negotiation.
+//!
+//! The crate is tested against a corpus of JSON fixtures shared by the
+//! Project Fluent implementations. This module exposes the fixture format
+//! and the runner, so that wrappers of the crate, such as FFI or WASM
+//! bindings, can run the same suite against their own API. Likewise, the
+//! [`corpus`] module generates the inputs of the benchmarks of the crate.
+//!
+//! A fixture file contains a list of test sets:
+//!
+//! ```json
+//! [
+//!   { "input": [["de-AT"], ["de", "en-US"], "en-US"], "strategy": "lookup", "output": ["de"] }
+//! ]
+//! ```
+//!
+//! This module is only available with the `testing` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::testing::{run_fixture_set_with, NegotiateTestInput, NegotiateTestSet};
+//!
+//! let set: NegotiateTestSet = serde_json::from_str(
+//!     r#"{ "input": [["de-AT"], ["de", "en-US"], "en-US"], "strategy": "lookup", "output": ["de"] }"#
+//! ).unwrap();
+//!
+//! // A binding exposing negotiation over strings.
+//! let result = run_fixture_set_with(&set, |set| {
+//!     let (requested, available, default) = match &set.input {
+//!         NegotiateTestInput::NoDefault(r, a) => (r, a, None),
+//!         NegotiateTestInput::Default(r, a, d) => (r, a, Some(d.as_str())),
+//!     };
+//!     fluent_langneg::negotiate(requested, available, default, set.strategy())
+//! });
+//! assert!(result.is_ok());
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use icu_locid::LanguageIdentifier;
+use serde::{Deserialize, Serialize};
+
+use crate::{convert_vec_str_to_langids_lossy, negotiate_languages, NegotiationStrategy};
+
+pub mod corpus;
+
+/// Input of a test set: the requested and available locales, and
+/// optionally the default one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NegotiateTestInput {
+    NoDefault(Vec<String>, Vec<String>),
+    Default(Vec<String>, Vec<String>, String),
+}
+
+/// A single negotiation with its expected output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NegotiateTestSet {
+    pub input: NegotiateTestInput,
+    /// One of `filtering`, `matching` or `lookup`, `filtering` if missing.
+    pub strategy: Option<String>,
+    pub output: Vec<String>,
+}
+
+impl NegotiateTestSet {
+    pub fn requested(&self) -> Vec<LanguageIdentifier> {
+        match &self.input {
+            NegotiateTestInput::NoDefault(requested, _)
+            | NegotiateTestInput::Default(requested, _, _) => {
+                convert_vec_str_to_langids_lossy(requested)
+            }
+        }
+    }
+
+    pub fn available(&self) -> Vec<LanguageIdentifier> {
+        match &self.input {
+            NegotiateTestInput::NoDefault(_, available)
+            | NegotiateTestInput::Default(_, available, _) => {
+                convert_vec_str_to_langids_lossy(available)
+            }
+        }
+    }
+
+    pub fn default(&self) -> Option<LanguageIdentifier> {
+        match &self.input {
+            NegotiateTestInput::NoDefault(..) => None,
+            NegotiateTestInput::Default(_, _, default) => default.parse().ok(),
+        }
+    }
+
+    pub fn strategy(&self) -> NegotiationStrategy {
+        match self.strategy.as_deref() {
+            Some("matching") => NegotiationStrategy::Matching,
+            Some("lookup") => NegotiationStrategy::Lookup,
+            _ => NegotiationStrategy::Filtering,
+        }
+    }
+
+    pub fn expected(&self) -> Vec<LanguageIdentifier> {
+        convert_vec_str_to_langids_lossy(&self.output)
+    }
+}
+
+/// Error of a conformance run.
+#[derive(Debug)]
+pub enum FixtureError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The output of a test set differs from the expected one.
+    Mismatch {
+        /// Position of the test set in the fixture.
+        index: usize,
+        expected: Vec<LanguageIdentifier>,
+        actual: Vec<LanguageIdentifier>,
+    },
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to read the fixture: {}", err),
+            Self::Json(err) => write!(f, "Failed to parse the fixture: {}", err),
+            Self::Mismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Test set {} failed: expected {:?}, got {:?}",
+                index, expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FixtureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+/// Reads the test sets of a fixture file.
+pub fn read_fixture<P: AsRef<Path>>(path: P) -> Result<Vec<NegotiateTestSet>, FixtureError> {
+    let file = File::open(path).map_err(FixtureError::Io)?;
+    serde_json::from_reader(io::BufReader::new(file)).map_err(FixtureError::Json)
+}
+
+/// Runs the test set against [`negotiate_languages`].
+pub fn run_fixture_set(set: &NegotiateTestSet) -> Result<(), FixtureError> {
+    run_fixture_set_with(set, |set| {
+        let available = set.available();
+        let default = set.default();
+        negotiate_languages(
+            &set.requested(),
+            &available,
+            default.as_ref(),
+            set.strategy(),
+        )
+        .into_iter()
+        .map(|langid| langid.to_string())
+        .collect::<Vec<_>>()
+    })
+}
+
+/// Runs the test set against a custom negotiation, which returns
+/// the supported locales as strings.
+///
+/// The output is compared with the expected one after parsing, so it doesn't
+/// need to be canonicalized.
+pub fn run_fixture_set_with<F, S>(set: &NegotiateTestSet, negotiate: F) -> Result<(), FixtureError>
+where
+    F: FnOnce(&NegotiateTestSet) -> Vec<S>,
+    S: AsRef<[u8]>,
+{
+    let actual = convert_vec_str_to_langids_lossy(negotiate(set));
+    let expected = set.expected();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(FixtureError::Mismatch {
+            index: 0,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Runs all the test sets of a fixture file against [`negotiate_languages`].
+pub fn run_fixture<P: AsRef<Path>>(path: P) -> Result<(), FixtureError> {
+    for (index, set) in read_fixture(path)?.iter().enumerate() {
+        run_fixture_set(set).map_err(|err| with_index(err, index))?;
+    }
+    Ok(())
+}
+
+/// Runs all the test sets of a fixture file against a custom negotiation,
+/// see [`run_fixture_set_with`].
+pub fn run_fixture_with<P, F, S>(path: P, mut negotiate: F) -> Result<(), FixtureError>
+where
+    P: AsRef<Path>,
+    F: FnMut(&NegotiateTestSet) -> Vec<S>,
+    S: AsRef<[u8]>,
+{
+    for (index, set) in read_fixture(path)?.iter().enumerate() {
+        run_fixture_set_with(set, &mut negotiate).map_err(|err| with_index(err, index))?;
+    }
+    Ok(())
+}
+
+fn with_index(err: FixtureError, index: usize) -> FixtureError {
+    match err {
+        FixtureError::Mismatch {
+            expected, actual, ..
+        } => FixtureError::Mismatch {
+            index,
+            expected,
+            actual,
+        },
+        err => err,
+    }
+}
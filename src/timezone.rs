@@ -0,0 +1,73 @@
+//! Requested locales hinted by the time zone of the user.
+//!
+//! When the only signals about a user are coarse, such as the time zone
+//! reported by a browser, the region of the time zone still tells which
+//! regional variant of a language, or which languages, the user likely
+//! wants. Time zones are mapped to regions through their CLDR identifiers,
+//! whose first two letters are the region, so `Europe/Vienna`, `atvie`,
+//! is in `AT`.
+//!
+//! This module is only available with the `timezones` feature.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::timezone::{region_for_time_zone, requested_from_tz};
+//! use icu_locid::langid;
+//! use icu_locid::subtags::region;
+//!
+//! assert_eq!(region_for_time_zone("Europe/Vienna"), Some(region!("AT")));
+//! assert_eq!(region_for_time_zone("Etc/UTC"), None);
+//!
+//! assert_eq!(
+//!     requested_from_tz("America/Toronto", Some(&langid!("fr"))),
+//!     &[langid!("fr-CA"), langid!("fr"), langid!("en-CA")]
+//! );
+//! ```
+
+use icu_locid::subtags::Region;
+use icu_locid::LanguageIdentifier;
+use icu_timezone::TimeZoneIdMapper;
+
+use crate::negotiate::{likely_languages_for_region, prepare_requested_with};
+
+/// Returns the region of an IANA time zone, or `None` if the time zone is
+/// unknown or not tied to a region, such as `Etc/UTC`.
+pub fn region_for_time_zone(tz: &str) -> Option<Region> {
+    let id = TimeZoneIdMapper::new()
+        .as_borrowed()
+        .iana_to_bcp47(tz.trim())?;
+    let id = id.0.as_str();
+    // Identifiers not tied to a region are short, such as `utc`, or have
+    // digits, such as `utce01` or `est5edt`.
+    if id.len() < 5 || !id.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    Region::try_from_bytes(&id.as_bytes()[..2]).ok()
+}
+
+/// Builds requested locales from a time zone and an optional language.
+///
+/// The language hint comes first, with the region of the time zone, then
+/// as is, followed by the languages likely spoken in the region, see
+/// [`likely_languages_for_region`]. If the time zone has no region, only
+/// the language hint is requested.
+pub fn requested_from_tz(
+    tz: &str,
+    language_hint: Option<&LanguageIdentifier>,
+) -> Vec<LanguageIdentifier> {
+    let region = region_for_time_zone(tz);
+    let mut requested: Vec<LanguageIdentifier> = Vec::new();
+    if let Some(hint) = language_hint {
+        if let Some(region) = region.filter(|_| hint.region.is_none()) {
+            let mut regional = hint.clone();
+            regional.region = Some(region);
+            requested.push(regional);
+        }
+        requested.push(hint.clone());
+    }
+    if let Some(region) = region {
+        requested.extend(likely_languages_for_region(region));
+    }
+    prepare_requested_with(&requested, false)
+}
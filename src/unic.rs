@@ -0,0 +1,247 @@
+//! Negotiation API operating on `unic_langid` types.
+//!
+//! Projects built on `fluent` crates which still use
+//! `unic_langid::LanguageIdentifier` can use these functions as drop-in
+//! replacements for the top-level ones, without migrating their locale
+//! types to `icu_locid` first.
+//!
+//! This module is only available with the `unic-langid` feature.
+//!
+//! With the `fluent-fallback` feature, `NegotiatedLocales` provides the
+//! negotiated locales to `fluent_fallback::Localization`.
+//!
+//! # Example:
+//!
+//! ```
+//! use fluent_langneg::unic::{
+//!     convert_vec_str_to_langids_lossy, negotiate_languages, LanguageIdentifier,
+//! };
+//! use fluent_langneg::NegotiationStrategy;
+//!
+//! let requested = convert_vec_str_to_langids_lossy(&["pl", "fr", "en-US"]);
+//! let available = convert_vec_str_to_langids_lossy(&["it", "de", "fr", "en-GB", "en-US"]);
+//! let default: LanguageIdentifier = "en-US".parse().expect("Parsing langid failed.");
+//!
+//! let supported = negotiate_languages(
+//!   &requested,
+//!   &available,
+//!   Some(&default),
+//!   NegotiationStrategy::Filtering
+//! );
+//!
+//! let expected = convert_vec_str_to_langids_lossy(&["fr", "en-US", "en-GB"]);
+//! assert_eq!(supported,
+//!            expected.iter().map(|t| t.as_ref()).collect::<Vec<&LanguageIdentifier>>());
+//! ```
+
+#[cfg(feature = "fluent-fallback")]
+use std::sync::Arc;
+
+pub use unic_langid::{LanguageIdentifier, LanguageIdentifierError};
+
+use crate::Error;
+
+use crate::lang_tag::LangTag;
+use crate::negotiate::{negotiate_converted, NegotiationStrategy};
+
+pub fn negotiate_languages<
+    'a,
+    R: 'a + AsRef<LanguageIdentifier>,
+    A: 'a + AsRef<LanguageIdentifier> + PartialEq,
+>(
+    requested: &[R],
+    available: &'a [A],
+    default: Option<&'a A>,
+    strategy: NegotiationStrategy,
+) -> Vec<&'a A> {
+    let requested: Vec<_> = requested
+        .iter()
+        .filter_map(|r| r.as_ref().to_langid())
+        .collect();
+    negotiate_converted(
+        &requested,
+        available,
+        |a| a.as_ref().to_langid(),
+        default,
+        strategy,
+    )
+}
+
+pub fn parse_accepted_languages(s: &str) -> Vec<LanguageIdentifier> {
+    crate::accepted_languages::tags(s)
+        .filter_map(|t| t.parse().ok())
+        .collect()
+}
+
+pub fn convert_vec_str_to_langids<'a, I, J>(input: I) -> Result<Vec<LanguageIdentifier>, Error>
+where
+    I: IntoIterator<Item = J>,
+    J: AsRef<[u8]> + 'a,
+{
+    input
+        .into_iter()
+        .map(|s| LanguageIdentifier::from_bytes(s.as_ref()).map_err(Error::Unic))
+        .collect()
+}
+
+pub fn convert_vec_str_to_langids_lossy<'a, I, J>(input: I) -> Vec<LanguageIdentifier>
+where
+    I: IntoIterator<Item = J>,
+    J: AsRef<[u8]> + 'a,
+{
+    input
+        .into_iter()
+        .filter_map(|t| LanguageIdentifier::from_bytes(t.as_ref()).ok())
+        .collect()
+}
+
+/// The result of a negotiation, computed when it is first iterated.
+///
+/// It implements `fluent_fallback::env::LocalesProvider`, so it can be
+/// passed to `fluent_fallback::Localization` as is, and the negotiation
+/// only happens once the localization needs its locales. The supported
+/// locales are followed by the default one, if it isn't supported already,
+/// and each of them is followed by its parents, such as `sr-Latn` and `sr`
+/// for `sr-Latn-RS`, so that resources missing from a regional locale are
+/// found in its parent before the next locale. Each locale is yielded once.
+///
+/// This type is only available with the `fluent-fallback` feature.
+///
+/// # Example:
+///
+/// ```
+/// use fluent_fallback::env::LocalesProvider;
+/// use fluent_langneg::unic::{convert_vec_str_to_langids_lossy, NegotiatedLocales};
+/// use fluent_langneg::NegotiationStrategy;
+///
+/// let provider = NegotiatedLocales::new(
+///     convert_vec_str_to_langids_lossy(["de-AT", "fr"]),
+///     convert_vec_str_to_langids_lossy(["en-US", "fr", "de"]),
+///     "en-US".parse().ok(),
+///     NegotiationStrategy::Filtering,
+/// );
+///
+/// let locales: Vec<_> = provider.locales().collect();
+/// assert_eq!(locales, convert_vec_str_to_langids_lossy(["de", "fr", "en-US", "en"]));
+/// ```
+#[cfg(feature = "fluent-fallback")]
+#[derive(Debug, Clone)]
+pub struct NegotiatedLocales {
+    inner: Arc<Negotiation>,
+}
+
+#[cfg(feature = "fluent-fallback")]
+#[derive(Debug)]
+struct Negotiation {
+    requested: Vec<LanguageIdentifier>,
+    available: Vec<LanguageIdentifier>,
+    default: Option<LanguageIdentifier>,
+    strategy: NegotiationStrategy,
+}
+
+#[cfg(feature = "fluent-fallback")]
+impl NegotiatedLocales {
+    pub fn new(
+        requested: Vec<LanguageIdentifier>,
+        available: Vec<LanguageIdentifier>,
+        default: Option<LanguageIdentifier>,
+        strategy: NegotiationStrategy,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Negotiation {
+                requested,
+                available,
+                default,
+                strategy,
+            }),
+        }
+    }
+
+    /// Returns a provider negotiating the new requested locales against
+    /// the same available ones.
+    pub fn with_requested(&self, requested: Vec<LanguageIdentifier>) -> Self {
+        Self::new(
+            requested,
+            self.inner.available.clone(),
+            self.inner.default.clone(),
+            self.inner.strategy,
+        )
+    }
+}
+
+/// Iterator over the locales of [`NegotiatedLocales`].
+#[cfg(feature = "fluent-fallback")]
+#[derive(Debug)]
+pub struct NegotiatedLocalesIter {
+    inner: Arc<Negotiation>,
+    supported: Option<std::vec::IntoIter<LanguageIdentifier>>,
+    /// The next parent of the last supported locale.
+    parent: Option<LanguageIdentifier>,
+    yielded: Vec<LanguageIdentifier>,
+}
+
+#[cfg(feature = "fluent-fallback")]
+impl Iterator for NegotiatedLocalesIter {
+    type Item = LanguageIdentifier;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let langid = match self.parent.take() {
+                Some(parent) => parent,
+                None => {
+                    let inner = &self.inner;
+                    self.supported
+                        .get_or_insert_with(|| {
+                            negotiate_languages(
+                                &inner.requested,
+                                &inner.available,
+                                inner.default.as_ref(),
+                                inner.strategy,
+                            )
+                            .into_iter()
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                        })
+                        .next()?
+                }
+            };
+            self.parent = parent(&langid);
+            if !self.yielded.contains(&langid) {
+                self.yielded.push(langid.clone());
+                return Some(langid);
+            }
+        }
+    }
+}
+
+/// Same as [`crate::subtags::truncate`], stopping before `und`.
+#[cfg(feature = "fluent-fallback")]
+fn parent(langid: &LanguageIdentifier) -> Option<LanguageIdentifier> {
+    let mut parent = langid.clone();
+    let variants: Vec<_> = langid.variants().copied().collect();
+    if let Some((_, rest)) = variants.split_last() {
+        parent.set_variants(rest);
+    } else if parent.region.is_some() {
+        parent.region = None;
+    } else if parent.script.is_some() {
+        parent.script = None;
+    } else {
+        return None;
+    }
+    Some(parent)
+}
+
+#[cfg(feature = "fluent-fallback")]
+impl fluent_fallback::env::LocalesProvider for NegotiatedLocales {
+    type Iter = NegotiatedLocalesIter;
+
+    fn locales(&self) -> Self::Iter {
+        NegotiatedLocalesIter {
+            inner: Arc::clone(&self.inner),
+            supported: None,
+            parent: None,
+            yielded: vec![],
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Checks that the negotiation into a caller-provided buffer doesn't allocate.
+//!
+//! Kept apart from the other tests, as it replaces the global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use fluent_langneg::negotiate::{filter_matches, filter_matches_into, negotiate_languages_into};
+use fluent_langneg::NegotiationStrategy;
+use icu_locid::langid;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn buffer_negotiation_does_not_allocate() {
+    let requested = [langid!("de-AT"), langid!("en"), langid!("sr-Latn")];
+    let available = [
+        langid!("en-US"),
+        langid!("de"),
+        langid!("en-GB"),
+        langid!("sr-Cyrl"),
+        langid!("de-CH"),
+        langid!("en"),
+    ];
+    let default = &available[0];
+
+    for strategy in [
+        NegotiationStrategy::Filtering,
+        NegotiationStrategy::Matching,
+        NegotiationStrategy::Lookup,
+    ] {
+        let mut out = [None; 6];
+        let (len, count) =
+            allocations(|| filter_matches_into(&requested, &available, strategy, &mut out));
+        assert!(len > 0);
+        assert_eq!(
+            count, 0,
+            "filter_matches_into allocated with {:?}",
+            strategy
+        );
+
+        let mut out = [None; 6];
+        let (len, count) = allocations(|| {
+            negotiate_languages_into(&requested, &available, Some(default), strategy, &mut out)
+        });
+        assert!(len > 0);
+        assert_eq!(
+            count, 0,
+            "negotiate_languages_into allocated with {:?}",
+            strategy
+        );
+    }
+
+    // Same result as the allocating negotiation, which sorts the scores.
+    for strategy in [
+        NegotiationStrategy::Filtering,
+        NegotiationStrategy::Matching,
+        NegotiationStrategy::Lookup,
+    ] {
+        let mut out = [None; 6];
+        let len = filter_matches_into(&requested, &available, strategy, &mut out);
+        assert_eq!(
+            out.iter().take(len).filter_map(|m| *m).collect::<Vec<_>>(),
+            filter_matches(&requested, &available, strategy)
+        );
+    }
+}
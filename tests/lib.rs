@@ -1,88 +1,42 @@
-use std::error::Error;
 use std::fs;
 use std::fs::File;
-use std::path::Path;
 
+use fluent_langneg::canonicalize;
+use fluent_langneg::convert_vec_str_to_langids;
 use fluent_langneg::convert_vec_str_to_langids_lossy;
 use fluent_langneg::negotiate_languages;
 use fluent_langneg::parse_accepted_languages;
+use fluent_langneg::registry::LocaleRegistry;
+use fluent_langneg::subtags;
 use fluent_langneg::NegotiationStrategy;
 use icu_locid::{langid, locale, LanguageIdentifier, Locale};
 
+use fluent_langneg::testing::{
+    run_fixture, run_fixture_with, NegotiateTestInput, NegotiateTestSet,
+};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
-#[serde(untagged)]
-enum NegotiateTestInput {
-    NoDefault(Vec<String>, Vec<String>),
-    Default(Vec<String>, Vec<String>, String),
-}
-
-#[derive(Serialize, Deserialize)]
-struct NegotiateTestSet {
-    input: NegotiateTestInput,
-    strategy: Option<String>,
-    output: Vec<String>,
-}
-
 #[derive(Serialize, Deserialize)]
 struct AcceptedLanguagesTestSet {
     input: String,
     output: Vec<String>,
 }
 
-fn read_negotiate_testsets<P: AsRef<Path>>(
-    path: P,
-) -> Result<Vec<NegotiateTestSet>, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let sets = serde_json::from_reader(file)?;
-    Ok(sets)
-}
-
 fn test_negotiate_fixtures(path: &str) {
     println!("Testing path: {}", path);
-    let tests = read_negotiate_testsets(path).unwrap();
+    run_fixture(path).unwrap_or_else(|err| panic!("Test in {} failed: {}", path, err));
+}
 
-    for test in tests {
-        let strategy = match test.strategy {
-            Some(strategy) => match strategy.as_str() {
-                "filtering" => NegotiationStrategy::Filtering,
-                "matching" => NegotiationStrategy::Matching,
-                "lookup" => NegotiationStrategy::Lookup,
-                _ => NegotiationStrategy::Filtering,
-            },
-            _ => NegotiationStrategy::Filtering,
-        };
-        match test.input {
-            NegotiateTestInput::NoDefault(requested, available) => {
-                let requested = convert_vec_str_to_langids_lossy(requested);
-                let available = convert_vec_str_to_langids_lossy(available);
-                let output = convert_vec_str_to_langids_lossy(test.output);
-                let output2: Vec<&LanguageIdentifier> = output.iter().collect();
-                assert_eq!(
-                    negotiate_languages(&requested, &available, None, strategy),
-                    output2,
-                    "Test in {} failed",
-                    path
-                );
-            }
-            NegotiateTestInput::Default(requested, available, default) => {
-                let requested = convert_vec_str_to_langids_lossy(requested);
-                let available = convert_vec_str_to_langids_lossy(available);
-                let output = convert_vec_str_to_langids_lossy(test.output);
-                let output2: Vec<&LanguageIdentifier> = output.iter().collect();
-                assert_eq!(
-                    negotiate_languages(
-                        &requested,
-                        &available,
-                        default.parse().ok().as_ref(),
-                        strategy
-                    ),
-                    output2,
-                    "Test in {} failed",
-                    path
-                );
-            }
+/// Runs all the negotiation fixtures against a custom negotiation.
+fn test_negotiate_fixtures_with<S: AsRef<[u8]>>(
+    mut negotiate: impl FnMut(&NegotiateTestSet) -> Vec<S>,
+) {
+    for dir in ["filtering", "matching", "lookup"] {
+        let paths = fs::read_dir(format!("./tests/fixtures/negotiate/{}", dir)).unwrap();
+        for path in paths {
+            let path = path.unwrap().path();
+            run_fixture_with(&path, &mut negotiate)
+                .unwrap_or_else(|err| panic!("Test in {:?} failed: {}", path, err));
         }
     }
 }
@@ -154,17 +108,17 @@ fn langid_matching() {
 
 #[test]
 fn cldr_feature() {
-    // In this case, the full likelySubtags algorithm knows that `mn` -> `mn-Cyrl`, but
-    // the mock doesn't.
+    // In this case, the full likelySubtags algorithm knows that `ks` -> `ks-Arab`, but
+    // the mock, which only covers the most used languages, doesn't.
     #[cfg(feature = "cldr")]
     assert_eq!(
         negotiate_languages(
-            &[langid!("mn")],
-            &[langid!("mn-Latn"), langid!("mn-Cyrl")],
+            &[langid!("ks")],
+            &[langid!("ks-Deva"), langid!("ks-Arab")],
             None,
             NegotiationStrategy::Filtering
         ),
-        &[&langid!("mn-Cyrl")]
+        &[&langid!("ks-Arab")]
     );
 
     // In result, the mock will just return both in undefined
@@ -172,8 +126,8 @@ fn cldr_feature() {
     #[cfg(not(feature = "cldr"))]
     assert_eq!(
         negotiate_languages(
-            &[langid!("mn")],
-            &[langid!("mn-Latn"), langid!("mn-Cyrl")],
+            &[langid!("ks")],
+            &[langid!("ks-Deva"), langid!("ks-Arab")],
             None,
             NegotiationStrategy::Filtering
         )
@@ -182,6 +136,44 @@ fn cldr_feature() {
     );
 }
 
+#[test]
+fn mock_likely_subtags() {
+    // The mock knows the likely script of the most used languages.
+    assert_eq!(
+        negotiate_languages(
+            &[langid!("mn")],
+            &[langid!("mn-Latn"), langid!("mn-Cyrl")],
+            None,
+            NegotiationStrategy::Filtering
+        ),
+        &[&langid!("mn-Cyrl")]
+    );
+}
+
+#[test]
+fn und_requests() {
+    let available = convert_vec_str_to_langids_lossy(["en", "uk", "ru", "de-AT", "und-Cyrl"]);
+
+    let supported = |requested: LanguageIdentifier| -> Vec<String> {
+        negotiate_languages(
+            &[requested],
+            &available,
+            None,
+            NegotiationStrategy::Filtering,
+        )
+        .into_iter()
+        .map(|l| l.to_string())
+        .collect()
+    };
+
+    // A script or a region implies a likely language.
+    assert_eq!(supported(langid!("und-Cyrl")), &["und-Cyrl", "ru"]);
+    assert_eq!(supported(langid!("und-UA")), &["uk", "und-Cyrl"]);
+    assert_eq!(supported(langid!("und-DE")), &["de-AT"]);
+    // A bare `und` doesn't.
+    assert!(supported(langid!("und")).is_empty());
+}
+
 #[test]
 fn locale_matching() {
     let loc_en_us = locale!("en-US-u-hc-h12");
@@ -210,3 +202,2694 @@ fn locale_matching() {
         &[&loc_en, &loc_de],
     );
 }
+
+#[test]
+fn canonicalize_iso639() {
+    let mut requested = convert_vec_str_to_langids_lossy(["ger-AT", "fre", "sqi", "yue", "en"]);
+    let modified: Vec<bool> = requested.iter_mut().map(canonicalize::language).collect();
+
+    assert_eq!(modified, &[true, true, true, false, false]);
+    assert_eq!(
+        requested,
+        &[
+            langid!("de-AT"),
+            langid!("fr"),
+            langid!("sq"),
+            langid!("yue"),
+            langid!("en")
+        ]
+    );
+}
+
+#[test]
+fn canonicalize_iso3166() {
+    let mut requested = convert_vec_str_to_langids_lossy(["en-840", "es-419", "de-AT"]);
+    let modified: Vec<bool> = requested.iter_mut().map(canonicalize::region).collect();
+
+    assert_eq!(modified, &[true, false, false]);
+    assert_eq!(
+        requested,
+        &[langid!("en-US"), langid!("es-419"), langid!("de-AT")]
+    );
+
+    assert_eq!(canonicalize::parse("en-USA").ok(), Some(langid!("en-US")));
+    assert_eq!(canonicalize::parse("ger_AUT").ok(), Some(langid!("de-AT")));
+    assert_eq!(
+        canonicalize::parse("zh-Hant-TWN").ok(),
+        Some(langid!("zh-Hant-TW"))
+    );
+    assert!(matches!(
+        canonicalize::parse("en-USA-XYZ"),
+        Err(fluent_langneg::Error::Parse { .. })
+    ));
+}
+
+#[test]
+fn canonicalize_collapse_script() {
+    use fluent_langneg::canonicalize::CanonicalizeOptions;
+
+    let options = CanonicalizeOptions::new().collapse_script(true);
+    let mut requested =
+        convert_vec_str_to_langids_lossy(["sr-Cyrl", "sr-Latn", "iw-Hebr-IL", "zh-Hant-TW", "en"]);
+    let modified: Vec<bool> = requested
+        .iter_mut()
+        .map(|langid| canonicalize::langid_with(langid, options))
+        .collect();
+
+    assert_eq!(modified, &[true, false, true, true, false]);
+    assert_eq!(
+        requested,
+        &[
+            langid!("sr"),
+            langid!("sr-Latn"),
+            langid!("he-IL"),
+            langid!("zh-TW"),
+            langid!("en")
+        ]
+    );
+
+    let mut langid = langid!("sr-Cyrl");
+    assert!(!canonicalize::langid_with(
+        &mut langid,
+        CanonicalizeOptions::default()
+    ));
+    assert_eq!(langid, langid!("sr-Cyrl"));
+}
+
+#[test]
+fn locale_registry() {
+    let mut registry: LocaleRegistry<u8> = [(langid!("de"), 100), (langid!("en-US"), 80)]
+        .into_iter()
+        .collect();
+
+    assert_eq!(registry.get(&langid!("de-AT")), Some(&100));
+    assert_eq!(registry.get(&langid!("en")), Some(&80));
+    assert_eq!(registry.get_exact(&langid!("en")), None);
+    assert_eq!(registry.get(&langid!("pl")), None);
+
+    assert_eq!(registry.insert(langid!("de"), 90), Some(100));
+    assert_eq!(registry.get(&langid!("de-CH")), Some(&90));
+
+    assert_eq!(registry.remove(&langid!("de")), Some(90));
+    assert_eq!(registry.get(&langid!("de-CH")), None);
+    assert_eq!(registry.len(), 1);
+}
+
+#[test]
+fn locale_registry_subscribers() {
+    use fluent_langneg::registry::RegistryEvent;
+    use std::sync::{Arc, Mutex};
+
+    let mut registry = LocaleRegistry::new();
+    let events = Arc::new(Mutex::new(vec![]));
+    let log = Arc::clone(&events);
+    let id = registry.subscribe(move |event| log.lock().unwrap().push(event.clone()));
+
+    registry.insert(langid!("de"), 1);
+    registry.insert(langid!("de"), 2);
+    assert_eq!(registry.get(&langid!("de-AT")), Some(&2));
+    assert_eq!(registry.remove(&langid!("fr")), None);
+    registry.insert(langid!("fr"), 3);
+    assert_eq!(registry.get(&langid!("fr-CA")), Some(&3));
+    assert_eq!(registry.generation(), 3);
+
+    let copy = registry.clone();
+    assert_eq!(copy, registry);
+
+    assert!(registry.unsubscribe(id));
+    assert!(!registry.unsubscribe(id));
+    registry.remove(&langid!("de"));
+    assert_eq!(registry.get(&langid!("de-AT")), None);
+    assert_eq!(registry.generation(), 4);
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        &[
+            RegistryEvent::Inserted(langid!("de")),
+            RegistryEvent::Replaced(langid!("de")),
+            RegistryEvent::Inserted(langid!("fr")),
+        ]
+    );
+}
+
+#[test]
+fn locale_unicode_extensions() {
+    for input in [
+        "de-u-co-phonebk",
+        "th-u-nu-thai",
+        "en-US-u-ab-cd-kn-zz-yyyy",
+    ] {
+        let loc: Locale = input.parse().unwrap();
+        assert_eq!(loc.to_string(), input);
+    }
+
+    let requested: Vec<Locale> = ["de-AT-u-co-phonebk", "th-u-nu-thai"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    let available = [locale!("th"), locale!("de")];
+    assert_eq!(
+        negotiate_languages(&requested, &available, None, NegotiationStrategy::Filtering),
+        &[&locale!("de"), &locale!("th")],
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_feature() {
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        available: Vec<LanguageIdentifier>,
+        default: LanguageIdentifier,
+        strategy: NegotiationStrategy,
+    }
+
+    let json = r#"{"available":["en-US","fr","de-AT"],"default":"en-US","strategy":"matching"}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.available[2], langid!("de-AT"));
+    assert_eq!(config.strategy, NegotiationStrategy::Matching);
+    assert_eq!(serde_json::to_string(&config).unwrap(), json);
+
+    assert!(serde_json::from_str::<LanguageIdentifier>(r#""en-$$""#).is_err());
+}
+
+#[test]
+fn subtags_difference_and_merge() {
+    let diff = subtags::difference(&langid!("de-DE-1996"), &langid!("de-AT-1996"));
+    assert!(diff.region);
+    assert!(!diff.language && !diff.script && !diff.variants);
+    assert!(subtags::difference(&langid!("und"), &langid!("und")).is_empty());
+
+    let mut langid = langid!("und-AT");
+    assert!(subtags::fill_missing(
+        &mut langid,
+        &langid!("de-Latn-DE-1996")
+    ));
+    assert_eq!(langid, langid!("de-Latn-AT-1996"));
+    assert!(!subtags::fill_missing(&mut langid, &langid!("fr-Latn-FR")));
+
+    assert_eq!(
+        subtags::merge(&langid!("sr-ME"), &langid!("sr-Latn")),
+        langid!("sr-Latn-ME")
+    );
+}
+
+#[test]
+fn subtags_distance() {
+    let langids =
+        convert_vec_str_to_langids_lossy(["und", "en", "en-US", "en-GB", "fr", "sr-Latn"]);
+    for lid1 in &langids {
+        assert_eq!(subtags::distance(lid1, lid1), 0);
+        for lid2 in &langids {
+            assert_eq!(subtags::distance(lid1, lid2), subtags::distance(lid2, lid1));
+        }
+    }
+    assert!(subtags::distance(&langid!("en"), &langid!("en-GB")) > 0);
+
+    // Explicit regions are kept when maximizing.
+    assert_eq!(
+        subtags::distance(&langid!("de"), &langid!("de-AT")),
+        subtags::REGION_DISTANCE
+    );
+    assert_eq!(
+        subtags::distance(&langid!("fr-CA"), &langid!("fr-Latn-CA")),
+        subtags::MAXIMIZATION_DISTANCE
+    );
+    assert_eq!(
+        subtags::distance(&langid!("es-MX"), &langid!("es-ES")),
+        subtags::REGION_DISTANCE
+    );
+
+    // The maximization distance is only added when the language
+    // identifiers are equal after maximization.
+    assert_eq!(
+        subtags::distance(&langid!("en"), &langid!("en-US")),
+        subtags::MAXIMIZATION_DISTANCE
+    );
+    assert_eq!(
+        subtags::distance(&langid!("en-US"), &langid!("fr-FR")),
+        subtags::LANGUAGE_DISTANCE + subtags::REGION_DISTANCE
+    );
+    assert!(
+        subtags::distance(&langid!("de-AT"), &langid!("de-CH"))
+            < subtags::distance(&langid!("de-AT"), &langid!("fr-CH"))
+    );
+}
+
+#[test]
+fn subtags_truncate() {
+    let mut chain = vec![];
+    let mut current = "de-Latn-DE-1901-1996".parse().ok();
+    while let Some(langid) = current {
+        current = subtags::truncate(&langid);
+        chain.push(langid);
+    }
+    assert_eq!(
+        chain,
+        convert_vec_str_to_langids_lossy([
+            "de-Latn-DE-1901-1996",
+            "de-Latn-DE-1901",
+            "de-Latn-DE",
+            "de-Latn",
+            "de",
+            "und"
+        ])
+    );
+
+    assert_eq!(
+        subtags::common_ancestor(&"de-DE-1901-1996".parse().unwrap(), &langid!("de-DE-1901")),
+        Some(langid!("de-DE-1901"))
+    );
+}
+
+#[test]
+fn convert_errors() {
+    assert_eq!(
+        convert_vec_str_to_langids(["en-US", "fr"]).ok(),
+        Some(vec![langid!("en-US"), langid!("fr")])
+    );
+
+    let err = match convert_vec_str_to_langids(["en-US", "fr", "de--AT", "x"]) {
+        Err(fluent_langneg::Error::Convert(err)) => err,
+        result => panic!("Unexpected result: {:?}", result),
+    };
+    assert_eq!(err.index, 2);
+    assert_eq!(err.input, "de--AT");
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Failed to parse language identifier \"de--AT\" at index 2: {}",
+            err.source
+        )
+    );
+}
+
+#[cfg(feature = "unic-langid")]
+#[test]
+fn unic_langid_tags() {
+    use fluent_langneg::negotiate::negotiate_tags;
+    use unic_langid::LanguageIdentifier as UnicLanguageIdentifier;
+
+    let requested: Vec<UnicLanguageIdentifier> =
+        vec!["de-AT".parse().unwrap(), "en".parse().unwrap()];
+    let available: Vec<UnicLanguageIdentifier> = vec![
+        "fr".parse().unwrap(),
+        "en-US".parse().unwrap(),
+        "de".parse().unwrap(),
+    ];
+    assert_eq!(
+        negotiate_tags(&requested, &available, None, NegotiationStrategy::Filtering),
+        &[&available[2], &available[1]]
+    );
+}
+
+#[cfg(feature = "language-tags")]
+#[test]
+fn language_tags_tags() {
+    use fluent_langneg::lang_tag::LangTag;
+    use fluent_langneg::negotiate::negotiate_tags;
+    use language_tags::LanguageTag;
+
+    let available: Vec<LanguageTag> = ["zh-yue-HK", "sl-rozaj-biske", "sl", "de-DE"]
+        .iter()
+        .map(|t| LanguageTag::parse(t).unwrap())
+        .collect();
+    assert_eq!(available[0].to_langid(), None);
+    assert_eq!(available[1].to_langid(), "sl-biske-rozaj".parse().ok());
+
+    let requested = [langid!("zh-HK"), langid!("sl-rozaj"), langid!("de")];
+    assert_eq!(
+        negotiate_tags(&requested, &available, None, NegotiationStrategy::Filtering),
+        &[&available[2], &available[1], &available[3]]
+    );
+}
+
+#[cfg(feature = "unic-langid")]
+#[test]
+fn negotiate_unic_fixtures() {
+    use fluent_langneg::unic;
+
+    test_negotiate_fixtures_with(|set| {
+        let convert = |langids: Vec<LanguageIdentifier>| {
+            unic::convert_vec_str_to_langids_lossy(langids.iter().map(|l| l.to_string()))
+        };
+        let requested = convert(set.requested());
+        let available = convert(set.available());
+        let default = convert(set.default().into_iter().collect());
+        unic::negotiate_languages(&requested, &available, default.first(), set.strategy())
+            .into_iter()
+            .map(|langid| langid.to_string())
+            .collect()
+    });
+}
+
+#[cfg(feature = "fluent-fallback")]
+#[test]
+fn fluent_fallback_provider() {
+    use fluent_fallback::env::LocalesProvider;
+    use fluent_langneg::unic::{convert_vec_str_to_langids_lossy, NegotiatedLocales};
+
+    let provider = NegotiatedLocales::new(
+        convert_vec_str_to_langids_lossy(["it", "de-AT"]),
+        convert_vec_str_to_langids_lossy(["en-US", "de-DE", "it"]),
+        "en-US".parse().ok(),
+        NegotiationStrategy::Filtering,
+    );
+    let locales: Vec<_> = provider.locales().collect();
+    assert_eq!(
+        locales,
+        convert_vec_str_to_langids_lossy(["it", "de-DE", "de", "en-US", "en"])
+    );
+
+    let provider = provider.with_requested(convert_vec_str_to_langids_lossy(["de-AT", "it"]));
+    let mut locales = provider.locales();
+    assert_eq!(locales.next(), "de-DE".parse().ok());
+    assert_eq!(locales.next(), "de".parse().ok());
+    assert_eq!(locales.next(), "it".parse().ok());
+    assert_eq!(locales.next(), "en-US".parse().ok());
+    assert_eq!(locales.next(), "en".parse().ok());
+    assert_eq!(locales.next(), None);
+
+    // The parents of a locale come before the next locale, and a parent
+    // which is supported as well is only yielded once.
+    let provider = NegotiatedLocales::new(
+        convert_vec_str_to_langids_lossy(["sr-Latn-RS", "fr", "sr"]),
+        convert_vec_str_to_langids_lossy(["sr-Latn-RS", "sr", "fr-FR-1694acad"]),
+        None,
+        NegotiationStrategy::Filtering,
+    );
+    let locales: Vec<_> = provider.locales().collect();
+    assert_eq!(
+        locales,
+        convert_vec_str_to_langids_lossy([
+            "sr-Latn-RS",
+            "sr-Latn",
+            "sr",
+            "fr-FR-1694acad",
+            "fr-FR",
+            "fr"
+        ])
+    );
+}
+
+#[cfg(feature = "oxilangtag")]
+#[test]
+fn oxilangtag_tags() {
+    use fluent_langneg::lang_tag::{to_oxilangtag, LangTag};
+    use fluent_langneg::negotiate::negotiate_tags;
+    use oxilangtag::LanguageTag;
+
+    let requested = [LanguageTag::parse("en-CA").unwrap()];
+    let available = [
+        LanguageTag::parse("fr").unwrap(),
+        LanguageTag::parse("en-GB").unwrap(),
+        LanguageTag::parse("en-US").unwrap(),
+    ];
+    assert_eq!(
+        negotiate_tags(&requested, &available, None, NegotiationStrategy::Filtering),
+        &[&available[2], &available[1]]
+    );
+
+    assert_eq!(LanguageTag::parse("zh-yue").unwrap().to_langid(), None);
+    let tag = to_oxilangtag(&langid!("de-AT-1996")).unwrap();
+    assert_eq!(tag.to_langid(), Some(langid!("de-AT-1996")));
+}
+
+#[test]
+fn shared_ownership_wrappers() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let available: Vec<Arc<LanguageIdentifier>> = convert_vec_str_to_langids_lossy(["fr", "de"])
+        .into_iter()
+        .map(Arc::new)
+        .collect();
+    let default = Arc::new(langid!("en-US"));
+
+    let requested = [Rc::new(langid!("de-AT"))];
+    assert_eq!(
+        negotiate_languages(
+            &requested,
+            &available,
+            Some(&default),
+            NegotiationStrategy::Filtering
+        ),
+        &[&available[1], &default]
+    );
+
+    let requested = [Box::new(langid!("fr-CA"))];
+    let available: Vec<Cow<LanguageIdentifier>> =
+        vec![Cow::Owned(langid!("de")), Cow::Borrowed(&available[0])];
+    assert_eq!(
+        negotiate_languages(&requested, &available, None, NegotiationStrategy::Lookup),
+        &[&available[1]]
+    );
+}
+
+#[test]
+fn negotiate_into_buffer() {
+    use fluent_langneg::negotiate::negotiate_languages_into;
+
+    test_negotiate_fixtures_with(|set| {
+        let requested = set.requested();
+        let available = set.available();
+        let default = set.default();
+
+        let mut out = vec![None; available.len() + 1];
+        let len = negotiate_languages_into(
+            &requested,
+            &available,
+            default.as_ref(),
+            set.strategy(),
+            &mut out,
+        );
+        out[..len]
+            .iter()
+            .flatten()
+            .map(|langid| langid.to_string())
+            .collect()
+    });
+
+    let available = [langid!("en-US"), langid!("en-GB"), langid!("de")];
+    let mut out = [None; 1];
+    let len = negotiate_languages_into(
+        &[langid!("en")],
+        &available,
+        Some(&available[2]),
+        NegotiationStrategy::Filtering,
+        &mut out,
+    );
+    assert_eq!(len, 1);
+    assert_eq!(out, [Some(&available[0])]);
+
+    let mut out: [Option<&LanguageIdentifier>; 0] = [];
+    let len = negotiate_languages_into(
+        &[langid!("en")],
+        &available,
+        None,
+        NegotiationStrategy::Filtering,
+        &mut out,
+    );
+    assert_eq!(len, 0);
+}
+
+#[test]
+fn prepared_available_locales() {
+    use fluent_langneg::negotiate::AvailableLocales;
+
+    let available = convert_vec_str_to_langids_lossy([
+        "en", "en-GB", "de", "de-CH", "fr", "sr-Cyrl", "sr-Latn", "und",
+    ]);
+    let prepared = AvailableLocales::new(&available);
+
+    for requested in [
+        &["en-CA", "de-AT"][..],
+        &["sr", "fr-CA", "en"],
+        &["de-CH", "en-US"],
+        &["en-CA", "de-AT"],
+    ] {
+        let requested = convert_vec_str_to_langids_lossy(requested);
+        for strategy in [
+            NegotiationStrategy::Filtering,
+            NegotiationStrategy::Matching,
+            NegotiationStrategy::Lookup,
+        ] {
+            assert_eq!(
+                prepared.negotiate_languages(&requested, Some(&available[0]), strategy),
+                negotiate_languages(&requested, &available, Some(&available[0]), strategy),
+            );
+        }
+    }
+    assert_eq!(prepared.as_slice().len(), 8);
+}
+
+#[test]
+fn negotiate_strings() {
+    use fluent_langneg::negotiate::negotiate;
+
+    test_negotiate_fixtures_with(|set| {
+        let (requested, available, default) = match &set.input {
+            NegotiateTestInput::NoDefault(r, a) => (r, a, None),
+            NegotiateTestInput::Default(r, a, d) => (r, a, Some(d.as_str())),
+        };
+        negotiate(requested, available, default, set.strategy())
+    });
+
+    let available = ["de-DE-1996", "de_de", "en-US", "EN-us"];
+    assert_eq!(
+        negotiate(
+            &["en-us", "de-DE"],
+            &available,
+            None,
+            NegotiationStrategy::Matching
+        ),
+        &["en-US", "de-DE"]
+    );
+    assert_eq!(
+        negotiate(
+            &["en-US", "en-US"],
+            &available,
+            None,
+            NegotiationStrategy::Matching
+        ),
+        &["en-US", "en-US"]
+    );
+    assert_eq!(
+        negotiate(
+            &["de-DE-1996"],
+            &["de-1996", "de-DE-1996"],
+            None,
+            NegotiationStrategy::Lookup
+        ),
+        &["de-DE-1996"]
+    );
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn cached_negotiation() {
+    use fluent_langneg::negotiate::AvailableLocales;
+
+    let available = [langid!("en-US"), langid!("de"), langid!("fr")];
+    let prepared = AvailableLocales::with_cache(&available, 2);
+    let filtering = NegotiationStrategy::Filtering;
+
+    let de = [langid!("de-AT")];
+    let fr = [langid!("fr-CA")];
+    let en = [langid!("en")];
+
+    assert_eq!(prepared.filter_matches(&de, filtering), &[&available[1]]);
+    assert_eq!(prepared.filter_matches(&fr, filtering), &[&available[2]]);
+    assert_eq!(prepared.filter_matches(&de, filtering), &[&available[1]]);
+    // Evicts `fr-CA`, which is the least recently used.
+    assert_eq!(prepared.filter_matches(&en, filtering), &[&available[0]]);
+    assert_eq!(
+        prepared.filter_matches(&de, NegotiationStrategy::Lookup),
+        &[&available[1]]
+    );
+    assert_eq!(prepared.filter_matches(&fr, filtering), &[&available[2]]);
+
+    let stats = prepared.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses), (1, 5));
+    assert_eq!((stats.len, stats.capacity), (2, 2));
+
+    assert_eq!(AvailableLocales::new(&available).cache_stats(), None);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn negotiate_many_parallel() {
+    use fluent_langneg::negotiate::negotiate_many;
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "en-GB", "de", "fr", "sr-Latn"]);
+    let requests: Vec<Vec<LanguageIdentifier>> =
+        [&["de-AT", "en"][..], &["sr-Latn-RS"], &[], &["pl", "fr-CA"]]
+            .iter()
+            .cycle()
+            .take(1000)
+            .map(|r| convert_vec_str_to_langids_lossy(*r))
+            .collect();
+
+    for strategy in [
+        NegotiationStrategy::Filtering,
+        NegotiationStrategy::Matching,
+        NegotiationStrategy::Lookup,
+    ] {
+        let results = negotiate_many(&requests, &available, Some(&available[0]), strategy);
+        assert_eq!(results.len(), requests.len());
+        for (requested, result) in requests.iter().zip(results) {
+            assert_eq!(
+                result,
+                negotiate_languages(requested, &available, Some(&available[0]), strategy)
+            );
+        }
+    }
+}
+
+#[test]
+fn negotiate_mask() {
+    use fluent_langneg::negotiate::{filter_matches, filter_matches_mask, AvailableLocales};
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "en-GB", "de", "fr", "sr-Latn"]);
+    let prepared = AvailableLocales::new(&available);
+    for requested in [&["de-AT", "en"][..], &["sr-RS", "fr-CA"], &["pl"]] {
+        let requested = convert_vec_str_to_langids_lossy(requested);
+        for strategy in [
+            NegotiationStrategy::Filtering,
+            NegotiationStrategy::Matching,
+            NegotiationStrategy::Lookup,
+        ] {
+            let supported = filter_matches(&requested, &available, strategy);
+            let expected: Vec<bool> = available
+                .iter()
+                .map(|a| supported.iter().any(|s| std::ptr::eq(*s, a)))
+                .collect();
+            assert_eq!(
+                filter_matches_mask(&requested, &available, strategy),
+                expected
+            );
+            assert_eq!(prepared.filter_matches_mask(&requested, strategy), expected);
+        }
+    }
+}
+
+#[test]
+fn negotiation_options() {
+    use fluent_langneg::negotiate::negotiate_languages_with;
+    use fluent_langneg::NegotiationOptions;
+
+    const OPTIONS: NegotiationOptions = NegotiationOptions::new();
+
+    assert_eq!(
+        NegotiationStrategy::default(),
+        NegotiationStrategy::Filtering
+    );
+    assert_eq!(OPTIONS, NegotiationOptions::default());
+    assert_eq!(
+        NegotiationOptions::from(NegotiationStrategy::Matching).strategy,
+        NegotiationStrategy::Matching
+    );
+
+    let available = [langid!("en-US"), langid!("en-GB")];
+    assert_eq!(
+        negotiate_languages_with(&[langid!("en")], &available, None, &OPTIONS),
+        &[&available[0], &available[1]]
+    );
+}
+
+#[test]
+fn negotiate_dedup() {
+    use fluent_langneg::negotiate::negotiate_languages_with;
+    use fluent_langneg::NegotiationOptions;
+
+    let available = convert_vec_str_to_langids_lossy(["en_US", "de", "en-US", "en-GB", "de"]);
+    let requested = [langid!("en"), langid!("de")];
+
+    let options = NegotiationOptions::new();
+    assert_eq!(
+        negotiate_languages_with(&requested, &available, Some(&available[2]), &options).len(),
+        5
+    );
+
+    let options = NegotiationOptions::new().dedup(true);
+    assert_eq!(
+        negotiate_languages_with(&requested, &available, Some(&available[2]), &options),
+        &[&available[0], &available[3], &available[1]]
+    );
+}
+
+#[test]
+fn prepare_requested_locales() {
+    use fluent_langneg::negotiate::{prepare_requested, prepare_requested_with};
+
+    let requested =
+        convert_vec_str_to_langids_lossy(["en-GB", "en_gb", "en", "und", "in-ID", "id"]);
+    assert_eq!(
+        prepare_requested(&requested),
+        convert_vec_str_to_langids_lossy(["en-GB", "id-ID"])
+    );
+    assert_eq!(
+        prepare_requested_with(&requested, false),
+        convert_vec_str_to_langids_lossy(["en-GB", "en", "und", "id-ID", "id"])
+    );
+
+    // A broader entry preceding a more specific one is kept.
+    let requested = convert_vec_str_to_langids_lossy(["en", "en-GB"]);
+    assert_eq!(prepare_requested(&requested), requested);
+}
+
+#[test]
+fn default_placement() {
+    use fluent_langneg::negotiate::{negotiate_languages_with, DefaultPlacement};
+    use fluent_langneg::NegotiationOptions;
+
+    let available = convert_vec_str_to_langids_lossy(["de", "de-AT", "en-US", "fr"]);
+    let requested = [langid!("de-AT"), langid!("de-CH")];
+    let default = Some(&available[2]);
+
+    let supported = |options: NegotiationOptions| -> Vec<String> {
+        negotiate_languages_with(&requested, &available, default, &options)
+            .into_iter()
+            .map(|l| l.to_string())
+            .collect()
+    };
+
+    let options = NegotiationOptions::new();
+    assert_eq!(supported(options), &["de-AT", "de", "en-US"]);
+    assert_eq!(
+        supported(options.default_placement(DefaultPlacement::Prepend)),
+        &["en-US", "de-AT", "de"]
+    );
+    assert_eq!(
+        supported(options.default_placement(DefaultPlacement::AfterExactMatches)),
+        &["de-AT", "en-US", "de"]
+    );
+
+    let options = NegotiationOptions::new().strategy(NegotiationStrategy::Lookup);
+    assert_eq!(supported(options), &["de-AT"]);
+    assert_eq!(
+        supported(options.always_add_default(true)),
+        &["de-AT", "en-US"]
+    );
+}
+
+#[test]
+fn skip_covered_default() {
+    use fluent_langneg::negotiate::negotiate_languages_with;
+    use fluent_langneg::NegotiationOptions;
+
+    let available = convert_vec_str_to_langids_lossy(["en-GB", "en-US", "de"]);
+    let default = Some(&available[1]);
+    let options = NegotiationOptions::new()
+        .strategy(NegotiationStrategy::Matching)
+        .skip_covered_default(true);
+
+    assert_eq!(
+        negotiate_languages_with(&[langid!("en-GB")], &available, default, &options),
+        &[&available[0]]
+    );
+    assert_eq!(
+        negotiate_languages_with(&[langid!("de")], &available, default, &options),
+        &[&available[2], &available[1]]
+    );
+    assert_eq!(
+        negotiate_languages_with(
+            &[langid!("en-GB")],
+            &available,
+            default,
+            &options.skip_covered_default(false)
+        ),
+        &[&available[0], &available[1]]
+    );
+}
+
+#[test]
+fn ecma402_matchers() {
+    use fluent_langneg::ecma402::{best_fit_matcher, lookup_matcher};
+
+    let available = convert_vec_str_to_langids_lossy(["en", "en-GB", "de", "sr-Latn"]);
+    let default = &available[0];
+
+    let requested: Vec<Locale> = ["fr-u-ca-buddhist", "sr-Latn-RS-x-private", "de"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    let result = lookup_matcher(&requested, &available, default);
+    assert_eq!(result.available, &available[3]);
+    assert_eq!(result.extension, None);
+
+    let requested = [locale!("en-GB-u-ca-buddhist")];
+    let result = lookup_matcher(&requested, &available, default);
+    assert_eq!(result.available, &available[1]);
+    assert_eq!(result.to_locale(), locale!("en-GB-u-ca-buddhist"));
+
+    let requested = [locale!("fr-u-ca-buddhist")];
+    for result in [
+        lookup_matcher(&requested, &available, default),
+        best_fit_matcher(&requested, &available, default),
+    ] {
+        assert_eq!(result.available, default);
+        assert_eq!(result.extension, None);
+    }
+}
+
+#[test]
+fn icu4c_accept_language() {
+    use fluent_langneg::icu4c::{accept_language, AcceptResult};
+
+    let available = convert_vec_str_to_langids_lossy(["sr-Latn", "de", "en-US"]);
+
+    // Longer fallbacks are tried first, regardless of the requested order.
+    let requested = convert_vec_str_to_langids_lossy(["de-AT", "sr-Latn-RS"]);
+    assert_eq!(
+        accept_language(&requested, &available),
+        (Some(&available[0]), AcceptResult::Fallback)
+    );
+
+    let requested = convert_vec_str_to_langids_lossy(["fr", "en-US"]);
+    assert_eq!(
+        accept_language(&requested, &available),
+        (Some(&available[2]), AcceptResult::Valid)
+    );
+
+    // Likely subtags are not used.
+    let requested = convert_vec_str_to_langids_lossy(["en-GB", "fr-CA"]);
+    assert_eq!(
+        accept_language(&requested, &available),
+        (None, AcceptResult::Failed)
+    );
+    assert_eq!(
+        accept_language::<LanguageIdentifier, _>(&[], &available),
+        (None, AcceptResult::Failed)
+    );
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_negotiation_invariants() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use fluent_langneg::arbitrary::{ArbitraryLangId, ArbitraryLocale};
+    use fluent_langneg::negotiate::negotiate_languages_with;
+    use fluent_langneg::NegotiationOptions;
+
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut data = vec![0u8; 512];
+    for _ in 0..500 {
+        for byte in data.iter_mut() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            *byte = seed as u8;
+        }
+        let mut u = Unstructured::new(&data);
+        let requested = Vec::<ArbitraryLocale>::arbitrary(&mut u).unwrap();
+        let available = Vec::<ArbitraryLangId>::arbitrary(&mut u).unwrap();
+        let options = NegotiationOptions::arbitrary(&mut u).unwrap();
+
+        for langid in &available {
+            assert_eq!(langid.0, langid.0.to_string().parse().unwrap());
+        }
+
+        let supported = negotiate_languages_with(&requested, &available, None, &options);
+        for (idx, locale) in supported.iter().enumerate() {
+            assert!(available.iter().any(|a| std::ptr::eq(a, *locale)));
+            assert!(!supported[..idx].iter().any(|s| std::ptr::eq(*s, *locale)));
+        }
+        if options.strategy == NegotiationStrategy::Lookup {
+            assert!(supported.len() <= 1);
+        }
+    }
+}
+
+#[test]
+fn result_order() {
+    use fluent_langneg::negotiate::{negotiate_languages_with, ResultOrder};
+    use fluent_langneg::NegotiationOptions;
+
+    let available = convert_vec_str_to_langids_lossy(["de", "fr", "de-DE", "fr-FR"]);
+    let requested = [langid!("fr-CA"), langid!("de-DE")];
+
+    let supported = |order: ResultOrder| -> Vec<String> {
+        let options = NegotiationOptions::new().order(order);
+        negotiate_languages_with(&requested, &available, Some(&available[0]), &options)
+            .into_iter()
+            .map(|l| l.to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        supported(ResultOrder::RequestedPriority),
+        &["fr", "fr-FR", "de-DE", "de"]
+    );
+    assert_eq!(
+        supported(ResultOrder::AvailablePriority),
+        &["de", "fr", "de-DE", "fr-FR"]
+    );
+    assert_eq!(
+        supported(ResultOrder::MatchQuality),
+        &["de-DE", "fr", "de", "fr-FR"]
+    );
+}
+
+#[test]
+fn locale_expander() {
+    use fluent_langneg::{LocaleExpander, TransformResult};
+
+    let lc = LocaleExpander::new();
+
+    let mut langid = langid!("en");
+    assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+    assert_eq!(langid, langid!("en-Latn-US"));
+    assert_eq!(lc.maximize(&mut langid), TransformResult::Unmodified);
+
+    // An explicit region is kept, with or without the `cldr` feature.
+    for (input, expected) in [("de-AT", "de-Latn-AT"), ("fr-CA", "fr-Latn-CA")] {
+        let mut langid: LanguageIdentifier = input.parse().unwrap();
+        assert_eq!(lc.maximize(&mut langid), TransformResult::Modified);
+        assert_eq!(langid.to_string(), expected);
+    }
+
+    // Without the `cldr` feature, only the most used languages are covered.
+    let mut langid = langid!("ks");
+    let result = lc.maximize(&mut langid);
+    if cfg!(feature = "cldr") {
+        assert_eq!(result, TransformResult::Modified);
+        assert_eq!(langid, langid!("ks-Arab-IN"));
+    } else {
+        assert_eq!(result, TransformResult::Unmodified);
+        assert_eq!(langid, langid!("ks"));
+    }
+
+    // Negotiation uses the same likely subtags.
+    let available = [langid!("en-GB"), langid!("en-US")];
+    let supported = negotiate_languages(
+        &[langid!("en")],
+        &available,
+        None,
+        NegotiationStrategy::Matching,
+    );
+    assert_eq!(supported, &[&available[1]]);
+}
+
+#[test]
+fn available_ranges() {
+    use fluent_langneg::negotiate::{filter_matches_with_ranges, LanguageRange, SupportedLocale};
+
+    let ranges: Vec<LanguageRange> = ["es-*", "*-Cyrl", "pt-*-BR"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    assert_eq!(
+        ranges.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+        &["es", "*-Cyrl", "pt-*-BR"]
+    );
+    assert!("es-Latn-MX-x".parse::<LanguageRange>().is_err());
+    assert!("e-*".parse::<LanguageRange>().is_err());
+
+    let requested = [langid!("pt-BR"), langid!("es-AR"), langid!("fr")];
+    let available = [langid!("fr"), langid!("es"), langid!("pt-PT")];
+
+    let supported = |strategy| -> Vec<String> {
+        filter_matches_with_ranges(&requested, &available, &ranges, strategy)
+            .into_iter()
+            .map(|s| match s {
+                SupportedLocale::Available(langid) => langid.to_string(),
+                SupportedLocale::Range { requested, range } => format!("{} ({})", requested, range),
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        supported(NegotiationStrategy::Filtering),
+        &["pt-PT", "pt-BR (pt-*-BR)", "es", "es-AR (es)", "fr"]
+    );
+    assert_eq!(
+        supported(NegotiationStrategy::Matching),
+        &["pt-PT", "es", "fr"]
+    );
+    assert_eq!(supported(NegotiationStrategy::Lookup), &["pt-PT"]);
+
+    let requested = [langid!("sr-Cyrl"), langid!("fr")];
+    assert_eq!(
+        filter_matches_with_ranges(&requested, &available, &ranges, NegotiationStrategy::Lookup),
+        &[SupportedLocale::Range {
+            requested: &requested[0],
+            range: &ranges[1]
+        }]
+    );
+}
+
+#[test]
+fn is_supported() {
+    use fluent_langneg::negotiate::{is_supported, AvailableLocales};
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "de", "sr-Cyrl", "und-Latn"]);
+    let prepared = AvailableLocales::new(&available);
+
+    for (requested, expected) in [
+        ("en-GB", true),
+        ("de-AT", true),
+        ("sr", true),
+        ("sr-Latn", true),
+        ("ja", false),
+        ("und", false),
+    ] {
+        let requested: LanguageIdentifier = requested.parse().unwrap();
+        assert_eq!(
+            is_supported(&requested, &available),
+            expected,
+            "{}",
+            requested
+        );
+        assert_eq!(prepared.is_supported(&requested), expected, "{}", requested);
+    }
+    assert!(!is_supported::<LanguageIdentifier>(&langid!("en"), &[]));
+}
+
+#[test]
+fn negotiation_report() {
+    use fluent_langneg::negotiate::report;
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "fr", "de-DE"]);
+    let requests = vec![
+        convert_vec_str_to_langids_lossy(["fr", "en"]),
+        convert_vec_str_to_langids_lossy(["fr-CA", "fr"]),
+        convert_vec_str_to_langids_lossy(["it", "de-DE"]),
+        convert_vec_str_to_langids_lossy(["it", "it"]),
+        vec![],
+    ];
+
+    let report = report(&requests, &available);
+    assert_eq!(report.requests, 5);
+    assert_eq!(report.exact, 1);
+    assert_eq!(report.fallback, 2);
+    assert_eq!(report.default, 2);
+    assert_eq!(
+        report.unavailable,
+        &[
+            (langid!("it"), 2),
+            (langid!("en"), 1),
+            (langid!("fr-CA"), 1)
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "fs")]
+fn filesystem_discovery() {
+    use fluent_langneg::fs::{discover_locale_dirs, discover_locale_files};
+
+    let root = std::env::temp_dir().join(format!("fluent-langneg-fs-{}", std::process::id()));
+    for dir in ["en-US", "de", ".git", "not a locale", "sr_Latn"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    for file in ["en-US/main.ftl", "fr.ftl", "pl.ftl", "x.ftl", "README.md"] {
+        File::create(root.join(file)).unwrap();
+    }
+
+    let mut rejected = vec![];
+    let dirs = discover_locale_dirs(&root, |err| rejected.push(err.input)).unwrap();
+    assert_eq!(dirs, &[langid!("de"), langid!("en-US"), langid!("sr-Latn")]);
+    assert_eq!(rejected, &["not a locale"]);
+
+    let mut rejected = vec![];
+    let files = discover_locale_files(&root, "ftl", |err| rejected.push(err.input)).unwrap();
+    assert_eq!(files, &[langid!("fr"), langid!("pl")]);
+    assert_eq!(rejected, &["x"]);
+
+    assert!(discover_locale_dirs(root.join("missing"), |_| {}).is_err());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(feature = "watch")]
+fn filesystem_watcher() {
+    use fluent_langneg::fs::LocaleWatcher;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let root = std::env::temp_dir().join(format!("fluent-langneg-watch-{}", std::process::id()));
+    fs::create_dir_all(root.join("en-US")).unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    let watcher = LocaleWatcher::watch_dirs(&root, move |available| {
+        let _ = sender.send(available.to_vec());
+    })
+    .unwrap();
+    assert_eq!(&*watcher.available(), &[langid!("en-US")]);
+
+    fs::create_dir(root.join("de")).unwrap();
+    let changed = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(changed, &[langid!("de"), langid!("en-US")]);
+    assert_eq!(&*watcher.available(), &[langid!("de"), langid!("en-US")]);
+
+    drop(watcher);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn fallback_chain() {
+    use fluent_langneg::negotiate::FallbackChain;
+
+    let chain = |langid: LanguageIdentifier| FallbackChain::new(langid).collect::<Vec<_>>();
+
+    assert_eq!(
+        chain(langid!("en")),
+        &[langid!("en"), langid!("en-Latn-US"), langid!("en-Latn")]
+    );
+    assert_eq!(chain(langid!("und")), &[langid!("und")]);
+    assert_eq!(
+        chain(langid!("fr-CA")),
+        &[
+            langid!("fr-CA"),
+            langid!("fr-Latn-CA"),
+            langid!("fr-Latn"),
+            langid!("fr"),
+        ]
+    );
+
+    // The default is not repeated when it's already in the chain.
+    let mut chain = FallbackChain::new(langid!("de-AT")).with_default(langid!("de"));
+    assert_eq!(chain.next(), Some(langid!("de-AT")));
+    assert_eq!(chain.find(|l| l.language != "de".parse().unwrap()), None);
+}
+
+#[test]
+fn cache_keys() {
+    use fluent_langneg::negotiate::cache_key;
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "sr-cyrl", "de"]);
+    for (requested, strategy, expected) in [
+        (
+            vec!["sr-Cyrl-RS", "de"],
+            NegotiationStrategy::Filtering,
+            "sr-Cyrl,de",
+        ),
+        (
+            vec!["sr", "de"],
+            NegotiationStrategy::Filtering,
+            "sr-Cyrl,de",
+        ),
+        (vec!["sr", "de"], NegotiationStrategy::Lookup, "sr-Cyrl"),
+        (vec!["de", "de-DE"], NegotiationStrategy::Matching, "de"),
+        (vec![], NegotiationStrategy::Filtering, ""),
+    ] {
+        let requested = convert_vec_str_to_langids_lossy(requested);
+        assert_eq!(cache_key(&requested, &available, strategy), expected);
+    }
+}
+
+#[test]
+fn accept_language_classes() {
+    use fluent_langneg::negotiate::AcceptLanguageClasses;
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "en-GB", "de"]);
+
+    let classes = AcceptLanguageClasses::new(&available, NegotiationStrategy::Filtering);
+    assert_eq!(classes.normalize("en-GB,de;q=0.5"), "en-GB,en-US,de");
+    assert_eq!(classes.normalize("en-gb, de-CH"), "en-GB,en-US,de");
+    assert_eq!(classes.normalize(""), "");
+    assert_eq!(classes.classes(), None);
+
+    let classes = AcceptLanguageClasses::new(&available, NegotiationStrategy::Lookup);
+    for header in ["en", "en-US;q=1", "en-CA,de"] {
+        assert_eq!(classes.normalize(header), "en-US", "{}", header);
+    }
+    assert_eq!(classes.classes().unwrap(), ["en-US", "en-GB", "de", ""]);
+
+    // A key negotiates to its own class.
+    let classes = AcceptLanguageClasses::new(&available, NegotiationStrategy::Matching);
+    let key = classes.normalize("de-AT,en-AU");
+    assert_eq!(key, "de,en-US");
+    assert_eq!(classes.normalize(&key), key);
+}
+
+#[test]
+fn explained_negotiation() {
+    use fluent_langneg::negotiate::{explain_negotiation, MatchStep};
+
+    let requested = convert_vec_str_to_langids_lossy(["fr", "it", "en-US", "de"]);
+    let available = convert_vec_str_to_langids_lossy(["en", "fr-CA", "fr", "en-US"]);
+    let default = langid!("en-US");
+
+    let explanation = explain_negotiation(
+        &requested,
+        &available,
+        Some(&default),
+        NegotiationStrategy::Filtering,
+    );
+    let steps: Vec<Vec<_>> = explanation
+        .requested
+        .iter()
+        .map(|r| {
+            r.candidates
+                .iter()
+                .map(|c| (c.available.to_string(), c.step, c.picked))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            vec![
+                ("fr".to_string(), MatchStep::Exact, true),
+                ("fr-CA".to_string(), MatchStep::Region, true),
+            ],
+            vec![],
+            vec![
+                ("en-US".to_string(), MatchStep::Exact, true),
+                ("en".to_string(), MatchStep::Range, true),
+            ],
+            vec![],
+        ]
+    );
+    assert_eq!(
+        explanation.supported,
+        convert_vec_str_to_langids_lossy(["fr", "fr-CA", "en-US", "en"])
+    );
+    assert!(!explanation.default_added);
+
+    let explanation = explain_negotiation(
+        &requested,
+        &available,
+        Some(&default),
+        NegotiationStrategy::Lookup,
+    );
+    assert_eq!(explanation.requested.len(), 1);
+    assert!(!explanation.requested[0].candidates[1].picked);
+    assert_eq!(explanation.supported, &[langid!("fr")]);
+
+    let requested = [langid!("ja")];
+    let explanation = explain_negotiation(
+        &requested,
+        &available,
+        Some(&default),
+        NegotiationStrategy::Lookup,
+    );
+    assert!(explanation.default_added);
+    assert_eq!(explanation.supported, &[langid!("en-US")]);
+
+    #[cfg(feature = "serde")]
+    assert_eq!(
+        serde_json::to_string(&explanation).unwrap(),
+        r#"{"strategy":"lookup","requested":[{"requested":"ja","candidates":[]}],"supported":["en-US"],"default_added":true}"#
+    );
+}
+
+#[test]
+fn crate_error() {
+    use fluent_langneg::accepted_languages::try_parse;
+    use fluent_langneg::{convert_vec_str_to_langids, parse_langid, Error};
+    use std::error::Error as _;
+
+    for (input, expected) in [("en--US", 3), ("x", 0), ("sr-Latn-R", 8), ("de_AT_$", 6)] {
+        match parse_langid(input) {
+            Err(Error::Parse { offset, .. }) => assert_eq!(offset, expected, "{}", input),
+            other => panic!("Unexpected result for {}: {:?}", input, other),
+        }
+    }
+
+    let err = try_parse("fr;q=0.9,en-US-$").unwrap_err();
+    assert!(matches!(err, Error::Header { offset: 15, .. }));
+    assert_eq!(
+        err.to_string(),
+        "Failed to parse Accept-Language header \"fr;q=0.9,en-US-$\" at byte 15: Invalid subtag"
+    );
+    assert!(err.source().is_some());
+
+    let err = convert_vec_str_to_langids(["en", "en-"]).unwrap_err();
+    assert!(matches!(&err, Error::Convert(err) if err.index == 1));
+
+    #[cfg(feature = "fs")]
+    {
+        let missing = std::env::temp_dir().join("fluent-langneg-missing");
+        let err = fluent_langneg::fs::discover_locale_dirs(&missing, |_| {}).unwrap_err();
+        assert!(matches!(err, Error::Io { ref path, .. } if *path == missing));
+    }
+}
+
+#[test]
+fn variant_options() {
+    use fluent_langneg::negotiate::{negotiate_languages_with, NegotiationOptions, ResultOrder};
+    use icu_locid::subtags::{variant, Variants};
+
+    let available = convert_vec_str_to_langids_lossy(["de", "de-DE-1901", "de-DE-1996"]);
+    let negotiate = |requested: &str, options: NegotiationOptions| {
+        let requested: LanguageIdentifier = requested.parse().unwrap();
+        negotiate_languages_with(&[requested], &available, None, &options)
+            .into_iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let options = NegotiationOptions::new();
+    assert_eq!(
+        negotiate("de-1996", options),
+        ["de", "de-DE-1996", "de-DE-1901"]
+    );
+    let options = options.prefer_exact_variants(true);
+    assert_eq!(
+        negotiate("de-1996", options),
+        ["de-DE-1996", "de", "de-DE-1901"]
+    );
+    assert_eq!(
+        negotiate("de-AT", options),
+        ["de", "de-DE-1901", "de-DE-1996"]
+    );
+    let options = options.strategy(NegotiationStrategy::Lookup);
+    assert_eq!(negotiate("de-1901", options), ["de-DE-1901"]);
+    let options = options.order(ResultOrder::MatchQuality);
+    assert_eq!(negotiate("de-1901", options), ["de-DE-1901"]);
+
+    let available = convert_vec_str_to_langids_lossy(["en-US-acmecorp", "en-US", "en-GB-oxendict"]);
+    let negotiate = |requested: &str, options: NegotiationOptions| {
+        let requested: LanguageIdentifier = requested.parse().unwrap();
+        negotiate_languages_with(&[requested], &available, None, &options)
+            .into_iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+    };
+    let options = NegotiationOptions::new();
+    assert_eq!(
+        negotiate("en-US", options),
+        ["en-US", "en-US-acmecorp", "en-GB-oxendict"]
+    );
+    let options = options.private_variants(true);
+    assert_eq!(negotiate("en-US", options), ["en-US", "en-GB-oxendict"]);
+    assert_eq!(
+        negotiate("en-US-acmecorp", options),
+        ["en-US-acmecorp", "en-US", "en-GB-oxendict"]
+    );
+
+    // Variants match regardless of their order.
+    let mut requested = langid!("de");
+    requested.variants = Variants::from_vec_unchecked(vec![variant!("fonipa"), variant!("1996")]);
+    let available = convert_vec_str_to_langids_lossy(["de-1996-fonipa"]);
+    assert_eq!(
+        negotiate_languages(&[requested], &available, None, NegotiationStrategy::Lookup),
+        [&available[0]]
+    );
+}
+
+#[test]
+fn raw_negotiation() {
+    use fluent_langneg::negotiate::negotiate_raw;
+
+    let available = ["en_us", "EN-GB", "i-klingon", "fr", "fr_CA", "not a tag"];
+    let negotiate = |requested: &[&str], strategy| {
+        negotiate_raw(requested, &available, None, strategy)
+            .into_iter()
+            .copied()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        negotiate(
+            &["en-GB", "I-KLINGON", "fr-ca"],
+            NegotiationStrategy::Filtering
+        ),
+        ["EN-GB", "en_us", "i-klingon", "fr_CA", "fr"]
+    );
+    assert_eq!(
+        negotiate(&["fr-CA", "en"], NegotiationStrategy::Matching),
+        ["fr_CA", "en_us"]
+    );
+    assert_eq!(
+        negotiate(&["tlh", "i_klingon", "en"], NegotiationStrategy::Lookup),
+        ["i-klingon"]
+    );
+    assert_eq!(
+        negotiate(&["NOT A TAG"], NegotiationStrategy::Lookup),
+        ["not a tag"]
+    );
+    assert_eq!(
+        negotiate(&["de", "x"], NegotiationStrategy::Filtering),
+        [] as [&str; 0]
+    );
+
+    let supported = negotiate_raw(
+        &["de"],
+        &available,
+        Some(&"EN-GB"),
+        NegotiationStrategy::Lookup,
+    );
+    assert_eq!(supported, [&"EN-GB"]);
+}
+
+#[test]
+fn tag_formats() {
+    use fluent_langneg::format::TagFormat;
+
+    let langids =
+        convert_vec_str_to_langids_lossy(["und", "EN_us", "sr-latn-rs-ekavsk", "und-Cyrl"]);
+    for (format, expected) in [
+        (
+            TagFormat::Canonical,
+            ["und", "en-US", "sr-Latn-RS-ekavsk", "und-Cyrl"],
+        ),
+        (
+            TagFormat::Lowercase,
+            ["und", "en-us", "sr-latn-rs-ekavsk", "und-cyrl"],
+        ),
+        (
+            TagFormat::Underscore,
+            ["und", "en_US", "sr_Latn_RS_ekavsk", "und_Cyrl"],
+        ),
+    ] {
+        let formatted: Vec<String> = langids.iter().map(|l| format.format(l)).collect();
+        assert_eq!(formatted, expected);
+    }
+    assert_eq!(
+        TagFormat::Canonical.format(&langids[2]),
+        langids[2].to_string()
+    );
+}
+
+#[test]
+fn url_routing() {
+    use fluent_langneg::routing::{
+        locale_from_host, locale_from_path, requested_locales, UrlPriority,
+    };
+
+    let allowed = convert_vec_str_to_langids_lossy(["en", "en-GB", "sr-Latn"]);
+    for (path, expected) in [
+        ("/en-GB/docs/intro", Some((1, "/docs/intro"))),
+        ("en", Some((0, ""))),
+        ("/sr_latn#top", Some((2, "#top"))),
+        ("/", None),
+        ("//en/docs", None),
+        ("/fr/docs", None),
+        ("/docs/en", None),
+    ] {
+        assert_eq!(
+            locale_from_path(path, &allowed),
+            expected.map(|(idx, rest)| (&allowed[idx], rest)),
+            "{}",
+            path
+        );
+    }
+    assert_eq!(
+        locale_from_host("en-gb.docs.example.com", &allowed),
+        Some(&allowed[1])
+    );
+    assert_eq!(locale_from_host("localhost:8080", &allowed), None);
+    assert_eq!(locale_from_host("example.com", &allowed), None);
+
+    let header = parse_accepted_languages("en-GB,fr;q=0.8,en;q=0.5");
+    let requested = |priority| {
+        requested_locales(Some(&langid!("en")), &header, priority)
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(requested(UrlPriority::First), ["en", "en-GB", "fr"]);
+    assert_eq!(requested(UrlPriority::Refined), ["en-GB", "en", "fr"]);
+    assert_eq!(requested(UrlPriority::Last), ["en-GB", "fr", "en"]);
+    assert_eq!(requested(UrlPriority::Exclusive), ["en"]);
+}
+
+#[test]
+fn requested_locales_builder() {
+    use fluent_langneg::negotiate::RequestedLocales;
+
+    let builder = RequestedLocales::new()
+        .os(convert_vec_str_to_langids_lossy(["pl", "en-US"]))
+        .accept_language("en-US, iw;q=0.8, x")
+        .url(Some(langid!("fr")))
+        .cookie(None)
+        .user_setting(Some(langid!("he")));
+    assert_eq!(
+        builder.build(),
+        convert_vec_str_to_langids_lossy(["he", "fr", "en-US", "pl"])
+    );
+
+    let builder = builder.user_setting(None).accept_language_locales(vec![]);
+    assert_eq!(
+        builder.build(),
+        convert_vec_str_to_langids_lossy(["fr", "pl", "en-US"])
+    );
+    assert!(RequestedLocales::new().build().is_empty());
+}
+
+#[test]
+fn html_lang() {
+    use fluent_langneg::html::{hreflang_alternates, parse_html_lang, to_html_lang};
+
+    for (langid, expected) in [
+        ("en-Latn-US", "en"),
+        ("en-US", "en"),
+        ("en-GB", "en-GB"),
+        ("fr-Latn-FR-1694acad", "fr-1694acad"),
+        ("zh-Hans-CN", "zh"),
+        ("und", "und"),
+    ] {
+        let langid: LanguageIdentifier = langid.parse().unwrap();
+        assert_eq!(to_html_lang(&langid), expected);
+    }
+
+    assert_eq!(parse_html_lang("sr-Latn").unwrap(), langid!("sr-Latn"));
+    assert!(parse_html_lang("sr_Latn").is_err());
+    assert!(parse_html_lang("sr--Latn").is_err());
+
+    let available = convert_vec_str_to_langids_lossy(["en", "en-US", "de-DE", "de-AT"]);
+    let alternates = hreflang_alternates(&available, None);
+    let hreflangs: Vec<_> = alternates.iter().map(|a| a.hreflang.as_str()).collect();
+    assert_eq!(hreflangs, ["en", "en-US", "de", "de-AT"]);
+    assert!(alternates
+        .iter()
+        .zip(&available)
+        .all(|(a, l)| a.locale == l));
+}
+
+#[test]
+fn rfc3066_xml_lang() {
+    use fluent_langneg::rfc3066::{parse, parse_xml_lang, to_xml_lang};
+
+    for (tag, expected) in [
+        ("i-klingon", "tlh"),
+        ("I-Navajo", "nv"),
+        ("zh-min-nan", "nan"),
+        ("zh-guoyu", "cmn"),
+        ("art-lojban", "jbo"),
+        ("en-GB-oed", "en-GB-oxendict"),
+        ("sgn-CH-DE", "sgg"),
+        ("iw-IL", "he-IL"),
+        ("en-US-x-custom", "en-US"),
+        ("sr-Latn", "sr-Latn"),
+    ] {
+        assert_eq!(parse(tag).unwrap().to_string(), expected, "{tag}");
+    }
+
+    for tag in [
+        "i-default",
+        "zh-min",
+        "x-private",
+        "i-unknown",
+        "en_US",
+        "en--US",
+        "1en",
+    ] {
+        assert!(parse(tag).is_err(), "{tag}");
+    }
+
+    assert_eq!(parse_xml_lang("").unwrap(), None);
+    assert_eq!(parse_xml_lang("  ").unwrap(), None);
+    assert_eq!(parse_xml_lang(" de-CH ").unwrap(), Some(langid!("de-CH")));
+    assert_eq!(parse_xml_lang("und").unwrap(), Some(langid!("und")));
+
+    assert_eq!(to_xml_lang(None), "");
+    assert_eq!(to_xml_lang(Some(&langid!("pt-BR"))), "pt-BR");
+}
+
+#[test]
+fn locale_map() {
+    use fluent_langneg::negotiate::LocaleMap;
+
+    let mut map: LocaleMap<u32> = [
+        (langid!("en-US"), 1),
+        (langid!("en-GB"), 2),
+        (langid!("fr"), 3),
+        (langid!("zh-Hant"), 4),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(map.get(&langid!("en-GB")), Some(&2));
+    assert_eq!(map.get(&langid!("fr-CA")), Some(&3));
+    assert_eq!(map.get(&langid!("zh-TW")), Some(&4));
+    assert_eq!(map.get(&langid!("en")), Some(&1));
+    assert_eq!(
+        map.get_key_value(&langid!("en-AU")).map(|(k, _)| k),
+        Some(&langid!("en-US"))
+    );
+    assert_eq!(map.get(&langid!("de")), None);
+
+    assert_eq!(map.insert(langid!("en-US"), 10), Some(1));
+    assert_eq!(map.remove(&langid!("en-GB")), Some(2));
+    assert_eq!(map.remove(&langid!("en-GB")), None);
+    assert_eq!(map.get(&langid!("en-GB")), Some(&10));
+    assert_eq!(map.get(&langid!("zh-TW")), Some(&4));
+    assert_eq!(map.len(), 3);
+    assert_eq!(
+        map.locales(),
+        &[langid!("en-US"), langid!("fr"), langid!("zh-Hant")]
+    );
+
+    let map = map.with_default(langid!("fr"));
+    assert_eq!(map.get(&langid!("de")), Some(&3));
+    assert!(LocaleMap::<u32>::new().get(&langid!("en")).is_none());
+}
+
+#[test]
+fn weighted_negotiation() {
+    use fluent_langneg::accepted_languages::parse_weighted;
+    use fluent_langneg::negotiate::negotiate_weighted;
+
+    assert_eq!(
+        parse_weighted("en;q=0.3, fr;q=bogus, de;q=2, , pl ;q=0"),
+        &[
+            (langid!("en"), 0.3),
+            (langid!("fr"), 1.0),
+            (langid!("de"), 1.0),
+            (langid!("pl"), 0.0)
+        ]
+    );
+
+    let available = [
+        (langid!("en-US"), 1.0),
+        (langid!("en-GB"), 0.8),
+        (langid!("pl"), 1.0),
+        (langid!("es"), 0.0),
+    ];
+    let default = Some(&available[0].0);
+
+    // The header isn't sorted by quality, both English locales are matched
+    // by en-GB, and the weight of en-GB puts it behind en-US.
+    let requested = parse_weighted("en;q=0.5, en-GB, pl;q=0, es");
+    assert_eq!(
+        negotiate_weighted(&requested, &available, default, 0.0),
+        &[&langid!("en-US"), &langid!("en-GB")]
+    );
+    let requested = parse_weighted("en-GB;q=0.6, pl;q=0.5");
+    assert_eq!(
+        negotiate_weighted(&requested, &available, default, 0.5),
+        &[&langid!("en-US"), &langid!("pl")]
+    );
+    assert_eq!(
+        negotiate_weighted(&requested, &available, None, 0.9),
+        Vec::<&LanguageIdentifier>::new()
+    );
+    assert_eq!(
+        negotiate_weighted(&requested, &available, default, 0.9),
+        &[&langid!("en-US")]
+    );
+}
+
+#[test]
+fn detected_requested_locales() {
+    use fluent_langneg::detect::LanguageDetector;
+    use fluent_langneg::negotiate::RequestedLocales;
+
+    struct Keywords;
+
+    impl LanguageDetector for Keywords {
+        fn detect(&self, text: &str) -> Vec<LanguageIdentifier> {
+            [("merci", "fr"), ("bonjour", "fr"), ("danke", "de")]
+                .iter()
+                .filter(|(word, _)| text.to_lowercase().contains(word))
+                .map(|(_, lang)| lang.parse().unwrap())
+                .collect()
+        }
+    }
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "fr-FR", "de-DE"]);
+    let requested = RequestedLocales::new()
+        .text_sample("Bonjour, merci beaucoup! Danke", &Keywords)
+        .build();
+    assert_eq!(requested, &[langid!("fr"), langid!("de")]);
+    assert_eq!(
+        negotiate_languages(
+            &requested,
+            &available,
+            Some(&available[0]),
+            NegotiationStrategy::Lookup
+        ),
+        &[&available[1]]
+    );
+
+    let requested = RequestedLocales::new()
+        .os([langid!("en-US")])
+        .text_sample("Bonjour", &Keywords)
+        .build();
+    assert_eq!(requested, &[langid!("en-US")]);
+
+    let requested = RequestedLocales::new().text_sample("?", &Keywords).build();
+    assert!(requested.is_empty());
+}
+
+#[test]
+fn keyword_availability() {
+    use fluent_langneg::ecma402::{resolve_locale, KeywordAvailability};
+    use icu_locid::extensions::unicode::{key, value};
+
+    let available = [
+        KeywordAvailability::new(langid!("en-US"))
+            .with(key!("hc"), [value!("h12"), value!("h23")])
+            .with(key!("nu"), [])
+            .with(key!("hc"), [value!("h12"), value!("h23"), value!("h24")]),
+        KeywordAvailability::new(langid!("de")).with(key!("hc"), [value!("h23")]),
+        KeywordAvailability::new(langid!("th")).with(key!("nu"), [value!("latn"), value!("thai")]),
+    ];
+    let default = &available[0];
+
+    assert_eq!(available[0].values(key!("nu")), &[]);
+    assert!(available[0].supports(key!("hc"), &value!("h24")));
+
+    let result = resolve_locale(&[locale!("en-GB-u-hc-h24")], &available, default);
+    assert_eq!(result.to_locale(), locale!("en-US-u-hc-h24"));
+    assert_eq!(result.keywords.len(), 1);
+
+    // Keys the available locale doesn't declare are dropped.
+    let requested: Locale = "de-AT-u-nu-thai-hc-h12".parse().unwrap();
+    let result = resolve_locale(&[requested], &available, default);
+    assert_eq!(result.to_locale(), locale!("de"));
+    assert_eq!(result.get(key!("hc")), Some(&value!("h23")));
+    assert_eq!(result.get(key!("nu")), None);
+
+    let result = resolve_locale(
+        &[locale!("fr-u-nu-thai"), locale!("th-TH-u-nu-thai")],
+        &available,
+        default,
+    );
+    assert_eq!(result.to_locale(), locale!("th-u-nu-thai"));
+
+    let result = resolve_locale(&[locale!("ja-u-hc-h24")], &available, default);
+    assert_eq!(result.available, default);
+    assert_eq!(result.to_locale(), locale!("en-US"));
+    assert_eq!(result.get(key!("hc")), Some(&value!("h12")));
+}
+
+#[test]
+fn intl_locale() {
+    use fluent_langneg::ecma402::{CaseFirst, HourCycle, IntlLocale};
+
+    let locale: IntlLocale = "de-Latn-DE-u-ca-gregory-co-phonebk-hc-h12-kf-upper-kn-false-nu-latn"
+        .parse()
+        .unwrap();
+    assert_eq!(locale.language(), "de");
+    assert_eq!(locale.script(), Some("Latn"));
+    assert_eq!(locale.region(), Some("DE"));
+    assert_eq!(locale.base_name(), "de-Latn-DE");
+    assert_eq!(locale.calendar().as_deref(), Some("gregory"));
+    assert_eq!(locale.collation().as_deref(), Some("phonebk"));
+    assert_eq!(locale.numbering_system().as_deref(), Some("latn"));
+    assert_eq!(locale.hour_cycle(), Some(HourCycle::H12));
+    assert_eq!(locale.case_first(), Some(CaseFirst::Upper));
+    assert!(!locale.numeric());
+    assert_eq!(
+        locale.minimize().to_string(),
+        "de-u-ca-gregory-co-phonebk-hc-h12-kf-upper-kn-false-nu-latn"
+    );
+
+    // The results of `Intl.Locale`, which keeps explicit subtags.
+    for (input, maximized, minimized) in [
+        ("de-AT", "de-Latn-AT", "de-AT"),
+        (
+            "fr-CA-u-ca-gregory",
+            "fr-Latn-CA-u-ca-gregory",
+            "fr-CA-u-ca-gregory",
+        ),
+        ("es-MX", "es-Latn-MX", "es-MX"),
+        ("sr-Latn", "sr-Latn-RS", "sr-Latn"),
+        ("zh-Hant", "zh-Hant-TW", "zh-TW"),
+        ("und-Cyrl-RS", "sr-Cyrl-RS", "sr"),
+    ] {
+        let locale: IntlLocale = input.parse().unwrap();
+        assert_eq!(locale.maximize().to_string(), maximized, "{input}");
+        assert_eq!(locale.minimize().to_string(), minimized, "{input}");
+        assert_eq!(
+            locale.maximize().minimize().to_string(),
+            minimized,
+            "{input}"
+        );
+    }
+
+    let locale = IntlLocale::from(locale!("ja-u-hc-h99"));
+    assert_eq!(locale.hour_cycle(), None);
+    assert_eq!(locale.script(), None);
+    assert!("ja-u-kn-true".parse::<IntlLocale>().unwrap().numeric());
+    assert!("en--US".parse::<IntlLocale>().is_err());
+    assert_eq!(Locale::from(locale), locale!("ja-u-hc-h99"));
+}
+
+#[test]
+fn langid_ext() {
+    use fluent_langneg::prelude::LangIdExt;
+
+    let langid = langid!("en-GB");
+    assert!(langid.matches_range("en"));
+    assert!(langid.matches_range("*-GB"));
+    assert!(langid.matches_range("*"));
+    assert!(!langid.matches_range("en-US"));
+    assert!(!langid.matches_range("en--"));
+
+    assert_eq!(langid!("en").maximized(), langid!("en-Latn-US"));
+    assert_eq!(langid!("en-Latn-US").minimized(), langid!("en"));
+    assert_eq!(langid!("und").minimized(), langid!("und"));
+    assert_eq!(
+        langid!("und-Latn").maximized().script,
+        langid!("und-Latn").script
+    );
+
+    assert_eq!(langid.parent(), Some(langid!("en")));
+    assert_eq!(langid!("en").parent(), Some(langid!("und")));
+    assert_eq!(langid!("und").parent(), None);
+
+    assert_eq!(
+        langid
+            .fallbacks()
+            .with_default(langid!("fr"))
+            .collect::<Vec<_>>()
+            .last(),
+        Some(&langid!("fr"))
+    );
+}
+
+#[test]
+fn collation_negotiation() {
+    use fluent_langneg::negotiate::negotiate_collation;
+    use icu_locid::extensions::unicode::value;
+
+    let available = [
+        locale!("de-u-co-phonebk"),
+        locale!("de"),
+        locale!("zh-u-co-stroke"),
+        locale!("zh-u-co-pinyin"),
+        locale!("sv"),
+    ];
+    let requested = [
+        locale!("zh-CN-u-co-pinyin"),
+        locale!("de"),
+        locale!("sv-u-co-trad"),
+    ];
+
+    let supported =
+        negotiate_collation(&requested, &available, None, NegotiationStrategy::Filtering);
+    let locales: Vec<_> = supported.iter().map(|m| m.locale.to_string()).collect();
+    assert_eq!(
+        locales,
+        &[
+            "zh-u-co-pinyin",
+            "zh-u-co-stroke",
+            "de",
+            "de-u-co-phonebk",
+            "sv"
+        ]
+    );
+    assert_eq!(supported[0].collation, Some(value!("pinyin")));
+    assert_eq!(supported[2].collation, None);
+    // No Swedish locale has the traditional collation.
+    assert_eq!(supported[4].collation, None);
+
+    let supported = negotiate_collation(
+        &requested[1..],
+        &available,
+        Some(&available[2]),
+        NegotiationStrategy::Matching,
+    );
+    let locales: Vec<_> = supported.iter().map(|m| m.locale.to_string()).collect();
+    assert_eq!(locales, &["de", "sv", "zh-u-co-stroke"]);
+}
+
+#[test]
+fn region_override_negotiation() {
+    use fluent_langneg::negotiate::{negotiate_with_region_override, region_override};
+
+    assert_eq!(region_override(&locale!("en-u-rg-001")), None);
+    assert_eq!(region_override(&locale!("en-u-rg-usca")), "US".parse().ok());
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "en-AU", "en-GB", "pt-PT"]);
+
+    // The base tag is available, and kept first.
+    let supported = negotiate_with_region_override(
+        &[locale!("en-US-u-rg-gbzzzz")],
+        &available,
+        None,
+        NegotiationStrategy::Filtering,
+    );
+    assert_eq!(supported, &[&available[0], &available[2], &available[1]]);
+
+    let requested: Vec<Locale> = ["en-NZ-u-rg-gbzzzz", "pt-u-rg-brzzzz"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    let supported =
+        negotiate_with_region_override(&requested, &available, None, NegotiationStrategy::Matching);
+    assert_eq!(supported, &[&available[2], &available[3]]);
+}
+
+#[cfg(feature = "region-preferences")]
+#[test]
+fn region_preferences() {
+    use fluent_langneg::preferences::{
+        first_day_of_week, measurement_system, preference_region, temperature_unit,
+        MeasurementSystem, TemperatureUnit, Weekday,
+    };
+
+    let available = convert_vec_str_to_langids_lossy(["en-GB", "ar-EG", "dv-MV", "de"]);
+    let supported = negotiate_languages(
+        &[langid!("de-AT")],
+        &available,
+        None,
+        NegotiationStrategy::Lookup,
+    );
+    // Preferences follow the negotiated locale.
+    let locale = Locale::from(supported[0].clone());
+    assert_eq!(preference_region(&locale), "DE".parse().unwrap());
+    assert_eq!(first_day_of_week(&locale), Weekday::Monday);
+
+    for (tag, weekday, system, unit) in [
+        (
+            "en-GB",
+            Weekday::Monday,
+            MeasurementSystem::UkSystem,
+            TemperatureUnit::Celsius,
+        ),
+        (
+            "ar-EG",
+            Weekday::Saturday,
+            MeasurementSystem::Metric,
+            TemperatureUnit::Celsius,
+        ),
+        (
+            "dv-MV",
+            Weekday::Friday,
+            MeasurementSystem::Metric,
+            TemperatureUnit::Celsius,
+        ),
+        (
+            "es-PR",
+            Weekday::Sunday,
+            MeasurementSystem::Metric,
+            TemperatureUnit::Fahrenheit,
+        ),
+        (
+            "und",
+            Weekday::Monday,
+            MeasurementSystem::Metric,
+            TemperatureUnit::Celsius,
+        ),
+        (
+            "en-US-u-fw-mon-ms-metric-mu-celsius",
+            Weekday::Monday,
+            MeasurementSystem::Metric,
+            TemperatureUnit::Celsius,
+        ),
+        (
+            "de-u-mu-fahrenhe",
+            Weekday::Monday,
+            MeasurementSystem::Metric,
+            TemperatureUnit::Fahrenheit,
+        ),
+    ] {
+        let locale: Locale = tag.parse().unwrap();
+        assert_eq!(first_day_of_week(&locale), weekday, "{tag}");
+        assert_eq!(measurement_system(&locale), system, "{tag}");
+        assert_eq!(temperature_unit(&locale), unit, "{tag}");
+    }
+}
+
+#[test]
+fn formatting_locales() {
+    use fluent_langneg::negotiate::formatting_locale;
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "de", "zh-Hans"]);
+    let os_locale = langid!("en-US");
+    let ui = negotiate_languages(
+        &[langid!("de-CH"), os_locale.clone()],
+        &available,
+        None,
+        NegotiationStrategy::Lookup,
+    )[0];
+    assert_eq!(formatting_locale(ui, os_locale.region), langid!("de-US"));
+
+    // The mock data doesn't know the likely script of Chinese in Taiwan.
+    #[cfg(feature = "cldr")]
+    assert_eq!(
+        formatting_locale(&langid!("zh"), "TW".parse().ok()),
+        langid!("zh-Hans-TW")
+    );
+    assert_eq!(
+        formatting_locale(&langid!("zh-Hant"), "CN".parse().ok()),
+        langid!("zh-Hant-CN")
+    );
+    assert_eq!(
+        formatting_locale(&langid!("und"), "FR".parse().ok()),
+        langid!("und-FR")
+    );
+}
+
+#[cfg(feature = "keyboard")]
+#[test]
+fn keyboard_layouts() {
+    use fluent_langneg::keyboard::{from_windows_klid, from_xkb_layout, from_xkb_layouts};
+    use fluent_langneg::negotiate::RequestedLocales;
+
+    assert_eq!(from_windows_klid("0409"), Some(langid!("en-US")));
+    assert_eq!(from_windows_klid("0000080C"), Some(langid!("fr-BE")));
+    assert_eq!(from_windows_klid("04110411"), Some(langid!("ja-JP")));
+    assert_eq!(from_windows_klid("0000ffff"), None);
+    assert_eq!(from_windows_klid("409"), None);
+    assert_eq!(from_windows_klid("0000040g"), None);
+
+    assert_eq!(from_xkb_layout("latam"), Some(langid!("es-419")));
+    assert_eq!(from_xkb_layout("US(colemak)"), Some(langid!("en-US")));
+    assert_eq!(from_xkb_layout(""), None);
+    assert_eq!(
+        from_xkb_layouts("us,us(intl), ru"),
+        &[langid!("en-US"), langid!("ru-RU")]
+    );
+
+    let requested = RequestedLocales::new()
+        .os(from_xkb_layouts("pl,us"))
+        .build();
+    assert_eq!(requested, &[langid!("pl-PL"), langid!("en-US")]);
+}
+
+#[test]
+fn region_languages() {
+    use fluent_langneg::negotiate::likely_languages_for_region;
+
+    // GeoIP only: the visitor is in Belgium.
+    let requested = likely_languages_for_region("BE".parse().unwrap());
+    assert_eq!(
+        requested,
+        convert_vec_str_to_langids_lossy(["nl-BE", "fr-BE", "de-BE"])
+    );
+    let available = convert_vec_str_to_langids_lossy(["en-US", "fr", "de"]);
+    assert_eq!(
+        negotiate_languages(
+            &requested,
+            &available,
+            Some(&available[0]),
+            NegotiationStrategy::Lookup
+        ),
+        &[&available[1]]
+    );
+
+    assert!(likely_languages_for_region("AQ".parse().unwrap())
+        .iter()
+        .all(|langid| langid.region == "AQ".parse().ok()));
+}
+
+#[cfg(feature = "timezones")]
+#[test]
+fn time_zone_hints() {
+    use fluent_langneg::timezone::{region_for_time_zone, requested_from_tz};
+
+    for (tz, region) in [
+        ("America/New_York", Some("US")),
+        ("Asia/Tokyo", Some("JP")),
+        ("Europe/London", Some("GB")),
+        ("Australia/Sydney", Some("AU")),
+        ("Asia/Calcutta", Some("IN")),
+        ("Etc/GMT+5", None),
+        ("Etc/Unknown", None),
+        ("Etc/GMT-14", None),
+        ("Mars/Olympus_Mons", None),
+    ] {
+        assert_eq!(
+            region_for_time_zone(tz),
+            region.map(|r| r.parse().unwrap()),
+            "{tz}"
+        );
+    }
+
+    assert_eq!(
+        requested_from_tz("Etc/UTC", Some(&langid!("de"))),
+        &[langid!("de")]
+    );
+    assert!(requested_from_tz("Etc/UTC", None).is_empty());
+    assert_eq!(
+        requested_from_tz("Europe/Zurich", Some(&langid!("en-US"))),
+        convert_vec_str_to_langids_lossy(["en-US", "de-CH", "fr-CH", "it-CH", "rm-CH"])
+    );
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "de-DE", "de-AT"]);
+    assert_eq!(
+        negotiate_languages(
+            &requested_from_tz("Europe/Vienna", Some(&langid!("de"))),
+            &available,
+            None,
+            NegotiationStrategy::Lookup
+        ),
+        &[&available[2]]
+    );
+}
+
+#[cfg(feature = "plurals")]
+#[test]
+fn plural_categories() {
+    use fluent_langneg::plurals::{cardinal_categories, ordinal_categories, PluralCategory};
+
+    let available = convert_vec_str_to_langids_lossy(["en-US", "ar", "ru"]);
+    let supported = negotiate_languages(
+        &[langid!("ar-EG"), langid!("ru-UA")],
+        &available,
+        None,
+        NegotiationStrategy::Filtering,
+    );
+    let categories: Vec<_> = supported.iter().map(|l| cardinal_categories(l)).collect();
+    assert_eq!(categories[0].len(), 6);
+    assert_eq!(
+        categories[1],
+        &[
+            PluralCategory::One,
+            PluralCategory::Few,
+            PluralCategory::Many,
+            PluralCategory::Other
+        ]
+    );
+
+    assert_eq!(ordinal_categories(&langid!("de")), &[PluralCategory::Other]);
+    assert_eq!(
+        cardinal_categories(&langid!("und")),
+        &[PluralCategory::Other]
+    );
+    assert_eq!(
+        cardinal_categories(&langid!("pt-PT")),
+        &[
+            PluralCategory::One,
+            PluralCategory::Many,
+            PluralCategory::Other
+        ]
+    );
+}
+
+#[test]
+fn negotiation_result() {
+    use fluent_langneg::negotiate::NegotiationResult;
+
+    let available = [locale!("en-US-u-hc-h12"), locale!("fr")];
+    let result: NegotiationResult<_> = negotiate_languages(
+        &[langid!("en-GB"), langid!("fr-CA")],
+        &available,
+        None,
+        NegotiationStrategy::Matching,
+    )
+    .into();
+    // Only the language identifiers are displayed.
+    assert_eq!(format!("{result}"), "en-US, fr");
+    assert_eq!(result.iter().count(), 2);
+    assert_eq!(result.as_slice(), &[&available[0], &available[1]]);
+
+    let owned: Vec<String> = result.clone().into_iter().map(|l| l.to_string()).collect();
+    assert_eq!(owned, &["en-US-u-hc-h12", "fr"]);
+    assert_eq!(result.into_vec().len(), 2);
+
+    let empty: NegotiationResult<LanguageIdentifier> = Vec::new().into();
+    assert!(empty.is_empty());
+    assert_eq!(empty.first(), None);
+    assert_eq!(empty.to_string(), "");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn negotiation_protocol() {
+    use fluent_langneg::negotiate::{NegotiationRequest, NegotiationResponse};
+
+    let mut request = NegotiationRequest::new(
+        vec![langid!("fr-CA"), langid!("en")],
+        vec![langid!("en-US"), langid!("fr"), langid!("de")],
+        Some(langid!("en-US")),
+        NegotiationStrategy::Lookup,
+    );
+    let json = serde_json::to_string(&request).unwrap();
+    assert_eq!(
+        json,
+        r#"{"requested":["fr-CA","en"],"available":["en-US","fr","de"],"default":"en-US","strategy":"lookup"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<NegotiationRequest>(&json).unwrap(),
+        request
+    );
+
+    let response = request.negotiate();
+    assert_eq!(response.supported, &[langid!("fr")]);
+    assert_eq!(response.explanation, None);
+
+    request.explain = true;
+    let request: NegotiationRequest =
+        serde_json::from_str(&serde_json::to_string(&request).unwrap()).unwrap();
+    let response = request.negotiate();
+    let explanation = response.explanation.as_ref().unwrap();
+    assert_eq!(explanation.supported, response.supported);
+    assert_eq!(explanation.strategy, NegotiationStrategy::Lookup);
+
+    let json = serde_json::to_string(&response).unwrap();
+    assert_eq!(
+        serde_json::from_str::<NegotiationResponse>(&json).unwrap(),
+        response
+    );
+
+    assert!(serde_json::from_str::<NegotiationRequest>(r#"{"requested":["en"]}"#).is_err());
+}
+
+#[test]
+fn plain_types() {
+    use fluent_langneg::ecma402::{best_fit_matcher, resolve_locale, KeywordAvailability};
+    use fluent_langneg::negotiate::negotiate_collation;
+    use fluent_langneg::plain::{
+        PlainCollationMatch, PlainMatcherResult, PlainRequest, PlainResolvedLocale, PlainResult,
+    };
+    use icu_locid::extensions::unicode::{key, value};
+
+    let request = PlainRequest {
+        requested: vec!["fr-CA".to_string(), "en-".to_string(), "de".to_string()],
+        available: vec!["en-US".to_string(), "fr".to_string(), "de-DE".to_string()],
+        default: Some("en-US".to_string()),
+        strategy: NegotiationStrategy::Matching,
+    };
+    let result = request.negotiate().unwrap();
+    assert_eq!(result.supported, &["fr", "de-DE", "en-US"]);
+
+    let available = [langid!("fr"), langid!("en-US")];
+    assert_eq!(result.resolve(&available), None);
+    let available = [langid!("en-US"), langid!("fr"), langid!("de-DE")];
+    let resolved = result.resolve(&available).unwrap();
+    assert_eq!(PlainResult::from(resolved.clone()), result);
+    assert_eq!(resolved.first(), Some(&available[1]));
+
+    let invalid = PlainRequest {
+        available: vec!["en-US".to_string(), "x".to_string()],
+        ..request.clone()
+    };
+    assert!(
+        matches!(invalid.negotiate(), Err(fluent_langneg::Error::Convert(err)) if err.index == 1)
+    );
+    let invalid = PlainRequest {
+        default: Some("en-".to_string()),
+        ..request
+    };
+    assert!(invalid.negotiate().is_err());
+
+    let available: Vec<Locale> = ["de", "de-u-co-phonebk"]
+        .iter()
+        .map(|tag| tag.parse().unwrap())
+        .collect();
+    let requested: Locale = "de-AT-u-co-phonebk".parse().unwrap();
+    let matched =
+        negotiate_collation(&[requested], &available, None, NegotiationStrategy::Lookup).remove(0);
+    let plain = PlainCollationMatch::from(matched.clone());
+    assert_eq!(plain.locale, "de-u-co-phonebk");
+    assert_eq!(plain.collation.as_deref(), Some("phonebk"));
+    assert_eq!(plain.resolve(&available), Some(matched));
+
+    let available = [langid!("th"), langid!("en")];
+    let requested: Locale = "th-TH-u-ca-buddhist".parse().unwrap();
+    let result = best_fit_matcher(&[requested], &available, &available[1]);
+    let plain = PlainMatcherResult::from(result.clone());
+    assert_eq!(plain.available, "th");
+    assert_eq!(plain.extension.as_deref(), Some("u-ca-buddhist"));
+    assert_eq!(plain.resolve(&available), Some(result));
+
+    let available =
+        [KeywordAvailability::new(langid!("en"))
+            .with(key!("nu"), [value!("latn"), value!("arab")])];
+    let requested: Locale = "en-u-nu-arab".parse().unwrap();
+    let resolved = resolve_locale(&[requested], &available, &available[0]);
+    let plain = PlainResolvedLocale::from(resolved.clone());
+    assert_eq!(plain.available, "en");
+    assert_eq!(plain.keywords[0].key, "nu");
+    assert_eq!(plain.keywords[0].value, "arab");
+    assert!(plain.keywords[0].requested);
+    assert_eq!(plain.resolve(&available), Some(resolved));
+}
+
+#[test]
+fn shared_negotiator() {
+    use fluent_langneg::negotiate::SharedNegotiator;
+    use std::thread;
+
+    let negotiator = SharedNegotiator::new(vec![langid!("en-US")], Some(langid!("en-US")));
+    let before = negotiator.snapshot();
+    assert_eq!(before.generation(), 0);
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let negotiator = negotiator.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let supported = negotiator
+                        .negotiate_languages(&[langid!("de-AT")], NegotiationStrategy::Lookup);
+                    assert_eq!(supported.len(), 1);
+                }
+                if i == 0 {
+                    negotiator.replace(
+                        vec![langid!("en-US"), langid!("de")],
+                        Some(langid!("en-US")),
+                    );
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let after = negotiator.snapshot();
+    assert_eq!(after.generation(), 1);
+    assert_eq!(
+        after.negotiate_languages(&[langid!("de-AT")], NegotiationStrategy::Lookup),
+        &[&langid!("de")]
+    );
+    assert_eq!(
+        before.negotiate_languages(&[langid!("de-AT")], NegotiationStrategy::Lookup),
+        &[&langid!("en-US")]
+    );
+    assert!(after.is_supported(&langid!("de-CH")));
+    assert!(!before.is_supported(&langid!("de-CH")));
+    assert_eq!(after.default_locale(), Some(&langid!("en-US")));
+
+    let previous = negotiator.replace(vec![], None);
+    assert_eq!(previous.available(), after.available());
+    assert!(negotiator
+        .negotiate_languages(&[langid!("de")], NegotiationStrategy::Filtering)
+        .is_empty());
+}
+
+#[test]
+fn negotiation_scratch() {
+    use fluent_langneg::negotiate::{
+        filter_matches, filter_matches_with_scratch, NegotiationScratch,
+    };
+
+    let large = convert_vec_str_to_langids_lossy([
+        "en-US", "fr", "de", "en-GB", "it", "pl", "ru", "sr-Cyrl", "sr-Latn", "zh-Hant", "zh-Hans",
+        "ja-JP", "he-IL", "de-DE", "de-IT", "und",
+    ]);
+    let small = convert_vec_str_to_langids_lossy(["sr-Latn", "en"]);
+    let requests = [
+        convert_vec_str_to_langids_lossy(["de-CH", "it", "ru"]),
+        convert_vec_str_to_langids_lossy(["sr", "zh-TW", "en"]),
+        convert_vec_str_to_langids_lossy(["ko"]),
+    ];
+
+    let mut scratch = NegotiationScratch::new();
+    let mut supported = vec![];
+    for available in [&large, &small, &large] {
+        for requested in &requests {
+            for strategy in [
+                NegotiationStrategy::Filtering,
+                NegotiationStrategy::Matching,
+                NegotiationStrategy::Lookup,
+            ] {
+                filter_matches_with_scratch(
+                    requested,
+                    available,
+                    strategy,
+                    &mut scratch,
+                    &mut supported,
+                );
+                assert_eq!(supported, filter_matches(requested, available, strategy));
+            }
+        }
+    }
+}
+
+#[test]
+fn benchmark_corpus() {
+    use fluent_langneg::testing::corpus::{
+        available_locales, max_available_locales, requested_locales, RequestKind,
+        ACCEPT_LANGUAGE_SAMPLES,
+    };
+
+    assert_eq!(available_locales(10), available_locales(1000)[..10]);
+    assert_eq!(available_locales(0), &[] as &[LanguageIdentifier]);
+    assert!(max_available_locales() >= 1000);
+
+    for count in [10, 100, 1000] {
+        let available = available_locales(count);
+        for n in [1, 5, 20] {
+            let hit = requested_locales(&available, n, RequestKind::Hit);
+            assert_eq!(hit.len(), n.min(count));
+            assert!(hit.iter().all(|langid| available.contains(langid)));
+
+            let miss = requested_locales(&available, n, RequestKind::Miss);
+            assert_eq!(miss.len(), n);
+            assert!(
+                negotiate_languages(&miss, &available, None, NegotiationStrategy::Filtering)
+                    .is_empty()
+            );
+
+            let maximize = requested_locales(&available, n, RequestKind::Maximize);
+            assert_eq!(maximize.len(), n);
+            assert!(maximize.iter().all(|langid| !available.contains(langid)));
+        }
+    }
+    assert!(requested_locales(&[], 5, RequestKind::Hit).is_empty());
+
+    for header in ACCEPT_LANGUAGE_SAMPLES
+        .iter()
+        .filter(|header| **header != "*")
+    {
+        assert!(!parse_accepted_languages(header).is_empty(), "{header}");
+    }
+}
+
+#[cfg(feature = "interner")]
+#[test]
+fn locale_interner() {
+    use fluent_langneg::interner::LocaleInterner;
+
+    let mut interner: LocaleInterner = convert_vec_str_to_langids_lossy(["en-US", "fr", "de"])
+        .into_iter()
+        .collect();
+    assert_eq!(interner.len(), 3);
+    let available: Vec<_> = interner.iter().map(|(id, _)| id).collect();
+
+    let header = "fr-CA,fr;q=0.9,en;q=0.8";
+    let requests: Vec<Vec<_>> = (0..1000)
+        .map(|_| {
+            parse_accepted_languages(header)
+                .into_iter()
+                .map(|langid| interner.intern(langid))
+                .collect()
+        })
+        .collect();
+    assert_eq!(interner.len(), 5);
+    assert!(requests.iter().all(|requested| *requested == requests[0]));
+
+    let fr = interner.get(&langid!("fr")).unwrap();
+    let en_us = interner.get(&langid!("en-US")).unwrap();
+    assert_eq!(
+        interner.filter_matches(&requests[0], &available, NegotiationStrategy::Matching),
+        &[fr, en_us]
+    );
+    assert_eq!(
+        interner.negotiate_languages(
+            &requests[0],
+            &available,
+            Some(en_us),
+            NegotiationStrategy::Lookup
+        ),
+        &[fr]
+    );
+
+    let other = LocaleInterner::from_iter([
+        langid!("en"),
+        langid!("ja"),
+        langid!("ko"),
+        langid!("it"),
+        langid!("pl"),
+        langid!("zh"),
+    ]);
+    let foreign = other.get(&langid!("zh")).unwrap();
+    assert_eq!(interner.resolve(foreign), None);
+    assert_eq!(
+        interner.negotiate_languages(&[foreign], &available, None, NegotiationStrategy::Filtering),
+        &[]
+    );
+    assert!(interner.intern_str("en-").is_err());
+    assert_eq!(interner.len(), 5);
+}
+
+#[test]
+fn tokenized_negotiation() {
+    use fluent_langneg::negotiate::{negotiate_tokenized, TokenizedLocale};
+    use icu_locid::subtags::{language, region, script, variant};
+
+    let with_variants: (&str, Option<&str>, Option<&str>, &[&str]) =
+        ("de", None, Some("CH"), &["1996", "fonipa"]);
+    assert_eq!(
+        with_variants.to_langid(),
+        Some("de-CH-fonipa-1996".parse().unwrap())
+    );
+    let invalid: (&str, Option<&str>, Option<&str>, &[&str]) = ("de", None, None, &["x"]);
+    assert_eq!(invalid.to_langid(), None);
+    assert_eq!(("de", Some("Latin"), None).to_langid(), None);
+
+    let typed = [
+        (language!("en"), None, Some(region!("GB"))),
+        (language!("zh"), Some(script!("Hant")), None),
+    ];
+    let supported = negotiate_tokenized(
+        &[("zh", Some("Hant"), Some("TW")), ("en", None, None)],
+        &typed,
+        None,
+        NegotiationStrategy::Filtering,
+    );
+    assert_eq!(supported, &[&typed[1], &typed[0]]);
+
+    let variants = [variant!("valencia")];
+    let available = [
+        (language!("ca"), None, None, &[][..]),
+        (language!("ca"), None, None, &variants[..]),
+    ];
+    let supported = negotiate_tokenized(
+        &[("ca", None, None, &["valencia"][..])],
+        &available,
+        None,
+        NegotiationStrategy::Matching,
+    );
+    assert_eq!(supported, &[&available[1]]);
+
+    // Same results as parsed tags.
+    let requested = ["de-AT", "sr-Latn", "en"];
+    let available = ["en-US", "de", "sr-Cyrl", "sr-Latn-RS", "fr"];
+    let split = |tag: &&str| {
+        let langid: LanguageIdentifier = tag.parse().unwrap();
+        (langid.language, langid.script, langid.region)
+    };
+    let tokenized_requested: Vec<_> = requested.iter().map(split).collect();
+    let tokenized_available: Vec<_> = available.iter().map(split).collect();
+    let parsed_requested = convert_vec_str_to_langids_lossy(requested);
+    let parsed_available = convert_vec_str_to_langids_lossy(available);
+    for strategy in [
+        NegotiationStrategy::Filtering,
+        NegotiationStrategy::Matching,
+        NegotiationStrategy::Lookup,
+    ] {
+        let tokenized: Vec<_> =
+            negotiate_tokenized(&tokenized_requested, &tokenized_available, None, strategy)
+                .into_iter()
+                .map(|subtags| subtags.to_langid().unwrap())
+                .collect();
+        let parsed: Vec<_> =
+            negotiate_languages(&parsed_requested, &parsed_available, None, strategy)
+                .into_iter()
+                .cloned()
+                .collect();
+        assert_eq!(tokenized, parsed);
+    }
+}
+
+#[test]
+fn no_panic_on_malformed_input() {
+    use fluent_langneg::accepted_languages;
+    use fluent_langneg::negotiate::{explain_negotiation, negotiate_languages_into, negotiate_raw};
+    use fluent_langneg::rfc3066;
+    use fluent_langneg::testing::corpus::{available_locales, ACCEPT_LANGUAGE_SAMPLES};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut inputs: Vec<String> = [
+        "",
+        ",",
+        ";",
+        ",,;;",
+        ";q=",
+        "en;q=",
+        "en;q=nan",
+        "en;q=-1",
+        "en;q=1e40",
+        "q=0.5",
+        "-",
+        "--",
+        "_",
+        "en-",
+        "-en",
+        "en--US",
+        "x-",
+        "i-",
+        "en-x-",
+        "en-x-a-x-b",
+        "und",
+        "und-Latn",
+        "und-419",
+        "und-Zzzz-ZZ",
+        "root",
+        "*",
+        "*;q=0",
+        "\0",
+        "en\0-US",
+        "é",
+        "en-😀",
+        "😀;q=😀",
+        "en-US-u-",
+        "en-u-ca",
+        "en-t-",
+        "zh-min-nan",
+        "art-lojban",
+    ]
+    .iter()
+    .map(|input| input.to_string())
+    .collect();
+    for header in ACCEPT_LANGUAGE_SAMPLES {
+        inputs.extend((0..header.len()).filter_map(|end| header.get(..end).map(String::from)));
+    }
+    inputs.push("en-".repeat(1000));
+    inputs.push("a".repeat(1000));
+
+    let available = available_locales(100);
+    let available_tags: Vec<String> = available.iter().map(|langid| langid.to_string()).collect();
+    let strategies = [
+        NegotiationStrategy::Filtering,
+        NegotiationStrategy::Matching,
+        NegotiationStrategy::Lookup,
+    ];
+
+    let panicked: Vec<&String> = inputs
+        .iter()
+        .filter(|input| {
+            catch_unwind(AssertUnwindSafe(|| {
+                let _ = canonicalize::parse(input);
+                let _ = rfc3066::parse(input);
+                let _ = accepted_languages::try_parse(input);
+                let _ = accepted_languages::parse_weighted(input);
+                let requested = parse_accepted_languages(input);
+                let raw: Vec<&str> = input.split(',').collect();
+                for strategy in strategies {
+                    negotiate_languages(&requested, &available, available.first(), strategy);
+                    negotiate_raw(&raw, &available_tags, available_tags.first(), strategy);
+                    explain_negotiation(&requested, &available, None, strategy);
+                    for len in [0, 1, 3] {
+                        let mut out = vec![None; len];
+                        negotiate_languages_into(
+                            &requested,
+                            &available,
+                            available.first(),
+                            strategy,
+                            &mut out,
+                        );
+                    }
+                }
+            }))
+            .is_err()
+        })
+        .collect();
+    assert!(panicked.is_empty(), "panicked on {:?}", panicked);
+}
+
+#[test]
+fn accept_language_stable_order() {
+    use fluent_langneg::accepted_languages::{
+        parse_weighted, serialize_weighted, stable_sort_by_quality,
+    };
+    use fluent_langneg::negotiate::negotiate_weighted;
+
+    let header = "fr;q=0.8, it, de-CH;q=0.8, pl;q=0, en-GB, es;q=-0, de;q=0.8";
+    let mut requested = parse_weighted(header);
+    stable_sort_by_quality(&mut requested);
+    assert_eq!(
+        serialize_weighted(&requested),
+        "it,en-GB,fr;q=0.8,de-CH;q=0.8,de;q=0.8,pl;q=0,es;q=0"
+    );
+
+    let available = [
+        (langid!("de"), 1.0),
+        (langid!("en-US"), 1.0),
+        (langid!("es"), 1.0),
+        (langid!("fr"), 1.0),
+        (langid!("it-CH"), 1.0),
+        (langid!("pl"), 1.0),
+    ];
+    let supported = negotiate_weighted(&requested, &available, None, 0.0);
+    assert_eq!(
+        supported,
+        &[
+            &langid!("it-CH"),
+            &langid!("en-US"),
+            &langid!("fr"),
+            &langid!("de"),
+        ]
+    );
+
+    // Identical headers serialize to identical negotiated values.
+    for _ in 0..10 {
+        let mut requested = parse_weighted(header);
+        stable_sort_by_quality(&mut requested);
+        let supported: Vec<_> = negotiate_weighted(&requested, &available, None, 0.0)
+            .into_iter()
+            .map(|langid| (langid, 1.0))
+            .collect();
+        assert_eq!(serialize_weighted(&supported), "it-CH,en-US,fr,de");
+    }
+
+    let mut entries = vec![
+        ("a", f32::NAN),
+        ("b", 0.5),
+        ("c", -0.0),
+        ("d", 0.0),
+        ("e", 1.0),
+    ];
+    stable_sort_by_quality(&mut entries);
+    let order: Vec<_> = entries.iter().map(|(tag, _)| *tag).collect();
+    assert_eq!(order, ["e", "b", "c", "d", "a"]);
+
+    for header in ["de-AT,de;q=0.8,en;q=0.5", "en-US,en;q=0.9,fr;q=0.123", ""] {
+        assert_eq!(serialize_weighted(&parse_weighted(header)), header);
+    }
+
+    let out_of_range = [
+        (langid!("de"), f32::NAN),
+        (langid!("fr"), -0.5),
+        (langid!("it"), 1.5),
+        (langid!("es"), f32::INFINITY),
+        (langid!("pl"), -0.0),
+    ];
+    assert_eq!(
+        serialize_weighted(&out_of_range),
+        "de;q=0,fr;q=0,it,es,pl;q=0"
+    );
+}